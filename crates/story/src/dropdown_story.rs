@@ -6,7 +6,7 @@ use gpui::{
 use ui::{
     checkbox::Checkbox,
     dropdown::{Dropdown, DropdownEvent, DropdownItem, SearchableVec},
-    h_flex, v_flex, ActiveTheme, FocusableCycle, IconName, Sizable,
+    h_flex, v_flex, ActiveTheme, FocusableCycle, Icon, IconName, Sizable,
 };
 
 actions!(dropdown_story, [Tab, TabPrev]);
@@ -45,6 +45,74 @@ impl DropdownItem for Country {
     }
 }
 
+#[derive(Clone)]
+struct Project {
+    name: SharedString,
+    path: SharedString,
+    recent: bool,
+    archived: bool,
+}
+
+impl Project {
+    pub fn new(name: impl Into<SharedString>, path: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            recent: false,
+            archived: false,
+        }
+    }
+
+    pub fn recent(mut self) -> Self {
+        self.recent = true;
+        self
+    }
+
+    pub fn archived(mut self) -> Self {
+        self.archived = true;
+        self
+    }
+}
+
+impl DropdownItem for Project {
+    type Value = SharedString;
+
+    fn title(&self) -> SharedString {
+        self.name.clone()
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.path
+    }
+
+    fn icon(&self) -> Option<Icon> {
+        Some(Icon::new(if self.recent {
+            IconName::Star
+        } else {
+            IconName::BookOpen
+        }))
+    }
+
+    fn description(&self) -> Option<SharedString> {
+        Some(self.path.clone())
+    }
+
+    fn disabled(&self) -> bool {
+        self.archived
+    }
+
+    fn group(&self) -> Option<SharedString> {
+        Some(
+            if self.recent {
+                "Recent"
+            } else {
+                "All projects"
+            }
+            .into(),
+        )
+    }
+}
+
 pub struct DropdownStory {
     disabled: bool,
     country_dropdown: Entity<Dropdown<Vec<Country>>>,
@@ -53,6 +121,10 @@ pub struct DropdownStory {
     simple_dropdown2: Entity<Dropdown<SearchableVec<SharedString>>>,
     simple_dropdown3: Entity<Dropdown<Vec<SharedString>>>,
     disabled_dropdown: Entity<Dropdown<Vec<SharedString>>>,
+    multiple_dropdown: Entity<Dropdown<SearchableVec<SharedString>>>,
+    combobox_dropdown: Entity<Dropdown<SearchableVec<SharedString>>>,
+    project_dropdown: Entity<Dropdown<SearchableVec<Project>>>,
+    combobox_text: Option<SharedString>,
 }
 
 impl super::Story for DropdownStory {
@@ -111,9 +183,68 @@ impl DropdownStory {
                 .menu_width(px(320.))
         });
 
+        let multiple_dropdown = cx.new(|cx| {
+            Dropdown::new(
+                "dropdown-multiple",
+                SearchableVec::new(vec![
+                    "Cheese".into(),
+                    "Mushroom".into(),
+                    "Onion".into(),
+                    "Pepperoni".into(),
+                    "Olive".into(),
+                    "Pineapple".into(),
+                ]),
+                None,
+                window,
+                cx,
+            )
+            .multiple()
+            .cleanable()
+            .placeholder("Toppings")
+        });
+
+        let combobox_dropdown = cx.new(|cx| {
+            Dropdown::new(
+                "dropdown-combobox",
+                SearchableVec::new(vec![
+                    "bug".into(),
+                    "documentation".into(),
+                    "enhancement".into(),
+                    "question".into(),
+                ]),
+                None,
+                window,
+                cx,
+            )
+            .placeholder("Label")
+            .combobox(window, cx)
+            .on_create(|text, labels, _, _| labels.push(text.to_string().into()))
+        });
+
+        let projects = SearchableVec::new(vec![
+            Project::new("components", "~/code/components").recent(),
+            Project::new("zed", "~/code/zed").recent(),
+            Project::new("blog", "~/code/blog"),
+            Project::new("components", "~/code/components"),
+            Project::new("dotfiles", "~/dotfiles"),
+            Project::new("legacy-app", "~/archive/legacy-app").archived(),
+            Project::new("notes", "~/documents/notes"),
+            Project::new("website", "~/code/website"),
+            Project::new("zed", "~/code/zed"),
+        ]);
+        let project_dropdown = cx.new(|cx| {
+            Dropdown::new("dropdown-projects", projects, None, window, cx)
+                .placeholder("Project")
+                .menu_width(px(320.))
+        });
+
         cx.new(|cx| {
             cx.subscribe_in(&country_dropdown, window, Self::on_dropdown_event)
                 .detach();
+            cx.subscribe_in(&combobox_dropdown, window, Self::on_combobox_event)
+                .detach();
+            cx.observe(&multiple_dropdown, |_, _, cx| cx.notify())
+                .detach();
 
             Self {
                 disabled: false,
@@ -170,6 +301,10 @@ impl DropdownStory {
                     .small()
                     .disabled(true)
                 }),
+                multiple_dropdown,
+                combobox_dropdown,
+                project_dropdown,
+                combobox_text: None,
             }
         })
     }
@@ -187,9 +322,26 @@ impl DropdownStory {
     ) {
         match event {
             DropdownEvent::Confirm(value) => println!("Selected country: {:?}", value),
+            DropdownEvent::ConfirmMultiple(values) => println!("Selected countries: {:?}", values),
+            DropdownEvent::ConfirmText(text) => println!("Confirmed text: {}", text),
         }
     }
 
+    fn on_combobox_event(
+        &mut self,
+        _: &Entity<Dropdown<SearchableVec<SharedString>>>,
+        event: &DropdownEvent<SearchableVec<SharedString>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.combobox_text = match event {
+            DropdownEvent::Confirm(value) => value.clone(),
+            DropdownEvent::ConfirmText(text) => Some(text.clone()),
+            DropdownEvent::ConfirmMultiple(_) => return,
+        };
+        cx.notify();
+    }
+
     fn on_key_tab(&mut self, _: &Tab, window: &mut Window, cx: &mut Context<Self>) {
         self.cycle_focus(true, window, cx);
         cx.notify();
@@ -212,6 +364,12 @@ impl DropdownStory {
             .update(cx, |this, _| this.set_disabled(disabled));
         self.simple_dropdown3
             .update(cx, |this, _| this.set_disabled(disabled));
+        self.multiple_dropdown
+            .update(cx, |this, _| this.set_disabled(disabled));
+        self.combobox_dropdown
+            .update(cx, |this, _| this.set_disabled(disabled));
+        self.project_dropdown
+            .update(cx, |this, _| this.set_disabled(disabled));
     }
 }
 
@@ -226,6 +384,9 @@ impl FocusableCycle for DropdownStory {
             self.simple_dropdown1.focus_handle(cx),
             self.simple_dropdown2.focus_handle(cx),
             self.simple_dropdown3.focus_handle(cx),
+            self.multiple_dropdown.focus_handle(cx),
+            self.combobox_dropdown.focus_handle(cx),
+            self.project_dropdown.focus_handle(cx),
         ]
     }
 }
@@ -252,7 +413,10 @@ impl Render for DropdownStory {
                     .items_center()
                     .gap_4()
                     .child(self.country_dropdown.clone())
-                    .child(self.fruit_dropdown.clone()),
+                    .child(self.fruit_dropdown.clone())
+                    .child(self.multiple_dropdown.clone())
+                    .child(self.combobox_dropdown.clone())
+                    .child(self.project_dropdown.clone()),
             )
             .child(
                 v_flex()
@@ -280,6 +444,11 @@ impl Render for DropdownStory {
                         "Language: {:?}",
                         self.simple_dropdown2.read(cx).selected_value()
                     ))
+                    .child(format!(
+                        "Toppings: {:?}",
+                        self.multiple_dropdown.read(cx).selected_values()
+                    ))
+                    .child(format!("Label: {:?}", self.combobox_text))
                     .child("This is other text."),
             )
             .child(
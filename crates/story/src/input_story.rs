@@ -1,16 +1,18 @@
 use gpui::{
     actions, div, px, App, AppContext as _, Context, Entity, FocusHandle, Focusable,
     InteractiveElement, IntoElement, KeyBinding, ParentElement as _, Render, SharedString, Styled,
-    Window,
+    Task, Window,
 };
-use regex::Regex;
 
 use crate::section;
 use ui::{
     button::{Button, ButtonVariant, ButtonVariants as _},
     checkbox::Checkbox,
     h_flex,
-    input::{InputEvent, NumberInput, NumberInputEvent, OtpInput, TextInput},
+    input::{
+        CompletionItem, CompletionProvider, ConfigHighlighter, Diagnostic, DiagnosticSeverity,
+        InputEvent, NumberInput, NumberInputEvent, OtpInput, TagInput, TextInput,
+    },
     prelude::FluentBuilder as _,
     v_flex, FocusableCycle, IconName, Sizable,
 };
@@ -26,14 +28,51 @@ pub fn init(cx: &mut App) {
     ])
 }
 
+/// Completes the keys of the Cargo manifest.
+struct CargoKeyCompletion;
+
+impl CompletionProvider for CargoKeyCompletion {
+    fn completions(
+        &self,
+        text: &str,
+        offset: usize,
+        _: &mut Window,
+        _: &mut App,
+    ) -> Task<anyhow::Result<Vec<CompletionItem>>> {
+        const KEYS: [(&str, &str); 8] = [
+            ("name", "package"),
+            ("version", "package"),
+            ("edition", "package"),
+            ("publish", "package"),
+            ("description", "package"),
+            ("license", "package"),
+            ("workspace", "dependency"),
+            ("features", "dependency"),
+        ];
+
+        let query = self
+            .trigger_range(text, offset)
+            .map(|range| text[range].to_lowercase())
+            .unwrap_or_default();
+        let items = KEYS
+            .iter()
+            .filter(|(key, _)| key.starts_with(&query) && *key != query)
+            .map(|(key, detail)| CompletionItem::new(*key).detail(*detail))
+            .collect();
+
+        Task::ready(Ok(items))
+    }
+}
+
 pub struct InputStory {
     input1: Entity<TextInput>,
     input2: Entity<TextInput>,
     textarea: Entity<TextInput>,
-    number_input1_value: i64,
+    code_editor: Entity<TextInput>,
+    number_input1_value: f64,
     number_input1: Entity<NumberInput>,
     number_input2: Entity<NumberInput>,
-    number_input2_value: u64,
+    number_input2_value: f64,
     mash_input: Entity<TextInput>,
     disabled_input: Entity<TextInput>,
     prefix_input1: Entity<TextInput>,
@@ -41,6 +80,11 @@ pub struct InputStory {
     both_input1: Entity<TextInput>,
     large_input: Entity<TextInput>,
     small_input: Entity<TextInput>,
+    phone_input: Entity<TextInput>,
+    date_input: Entity<TextInput>,
+    plate_input: Entity<TextInput>,
+    email_tag_input: Entity<TagInput>,
+    language_tag_input: Entity<TagInput>,
     otp_masked: bool,
     otp_input: Entity<OtpInput>,
     otp_value: Option<SharedString>,
@@ -119,10 +163,65 @@ impl InputStory {
         cx.subscribe_in(&textarea, window, Self::on_input_event)
             .detach();
 
-        let number_input1_value = 1;
+        let code_editor = cx.new(|cx| {
+            let mut input = TextInput::new(window, cx)
+                .multi_line()
+                .rows(8)
+                .highlighter(ConfigHighlighter::new())
+                .completion_provider(CargoKeyCompletion);
+            input.set_text(
+                unindent::unindent(
+                    r#"# This is a TOML file
+                [package]
+                name = "story"
+                version = "0.1.0"
+                publish = false
+
+                [dependencies]
+                gpui = { git = "https://github.com/zed-industries/zed.git" }
+                "#,
+                ),
+                window,
+                cx,
+            );
+
+            let text = input.text();
+            let diagnostics = [
+                (
+                    "\"story\"",
+                    DiagnosticSeverity::Error,
+                    "The name is already taken.",
+                ),
+                (
+                    "publish",
+                    DiagnosticSeverity::Warning,
+                    "The package will not be published.",
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(needle, severity, message)| {
+                let start = text.find(needle)?;
+                Some(Diagnostic::new(
+                    start..start + needle.len(),
+                    severity,
+                    message,
+                ))
+            })
+            .collect();
+            input.set_diagnostics(diagnostics, window, cx);
+            input
+        });
+
+        let number_input1_value = 1.;
         let number_input1 = cx.new(|cx| {
-            let input = NumberInput::new(window, cx).placeholder("Number Input", window, cx);
-            input.set_value(number_input1_value.to_string(), window, cx);
+            let mut input = NumberInput::new(window, cx)
+                .placeholder("Number Input", window, cx)
+                .label("Scale")
+                .min(0.)
+                .max(100.)
+                .step(0.1)
+                .precision(2);
+            input.set_value(number_input1_value, window, cx);
             input
         });
         cx.subscribe_in(&number_input1, window, Self::on_number_input1_event)
@@ -131,7 +230,7 @@ impl InputStory {
         let number_input2 = cx.new(|cx| {
             NumberInput::new(window, cx)
                 .placeholder("Unsized Integer Number Input", window, cx)
-                .pattern(Regex::new(r"^\d+$").unwrap(), window, cx)
+                .min(0.)
                 .small()
         });
 
@@ -166,6 +265,39 @@ impl InputStory {
                 .placeholder("This input have prefix and suffix.")
         });
 
+        let phone_input = cx.new(|cx| TextInput::new(window, cx).mask_pattern("(999) 999-9999"));
+        cx.observe(&phone_input, |_, _, cx| cx.notify()).detach();
+
+        let email_tag_input = cx.new(|cx| {
+            let mut input = TagInput::new(window, cx)
+                .placeholder("Add recipients...", window, cx)
+                .separators([',', ';', ' '])
+                .validate(|s| s.contains('@'));
+            input.set_values(["jason@example.com"], window, cx);
+            input
+        });
+        cx.observe(&email_tag_input, |_, _, cx| cx.notify())
+            .detach();
+
+        let language_tag_input = cx.new(|cx| {
+            TagInput::new(window, cx)
+                .placeholder("Add languages...", window, cx)
+                .suggestions(
+                    [
+                        "Rust",
+                        "Go",
+                        "C++",
+                        "JavaScript",
+                        "TypeScript",
+                        "Python",
+                        "Ruby",
+                    ]
+                    .into_iter()
+                    .map(SharedString::from)
+                    .collect::<Vec<_>>(),
+                )
+        });
+
         let otp_input = cx.new(|cx| OtpInput::new(6, window, cx).masked(true));
         cx.subscribe(&otp_input, |this, _, ev: &InputEvent, cx| match ev {
             InputEvent::Change(text) => {
@@ -180,10 +312,11 @@ impl InputStory {
             input1,
             input2,
             textarea,
+            code_editor,
             number_input1,
             number_input1_value,
             number_input2,
-            number_input2_value: 0,
+            number_input2_value: 0.,
             mash_input: mask_input,
             disabled_input: cx.new(|cx| {
                 let mut input = TextInput::new(window, cx);
@@ -202,6 +335,11 @@ impl InputStory {
                     .validate(|s| s.parse::<f32>().is_ok())
                     .placeholder("validate to limit float number.")
             }),
+            phone_input,
+            date_input: cx.new(|cx| TextInput::new(window, cx).mask_pattern("99/99/9999")),
+            plate_input: cx.new(|cx| TextInput::new(window, cx).mask_pattern("AAA-0000")),
+            email_tag_input,
+            language_tag_input,
             prefix_input1,
             suffix_input1,
             both_input1,
@@ -259,7 +397,7 @@ impl InputStory {
         &mut self,
         _: &Entity<NumberInput>,
         event: &NumberInputEvent,
-        window: &mut Window,
+        _: &mut Window,
         cx: &mut Context<Self>,
     ) {
         match event {
@@ -269,20 +407,12 @@ impl InputStory {
                 InputEvent::Focus => println!("Focus"),
                 InputEvent::Blur => println!("Blur"),
             },
-            NumberInputEvent::Step(step_action) => match step_action {
-                ui::input::StepAction::Decrement => {
-                    self.number_input1_value = self.number_input1_value - 1;
-                    self.number_input1.update(cx, |input, cx| {
-                        input.set_value(self.number_input1_value.to_string(), window, cx);
-                    });
-                }
-                ui::input::StepAction::Increment => {
-                    self.number_input1_value = self.number_input1_value + 1;
-                    self.number_input1.update(cx, |input, cx| {
-                        input.set_value(self.number_input1_value.to_string(), window, cx);
-                    });
-                }
-            },
+            NumberInputEvent::Step(_) => {}
+            NumberInputEvent::ValueChanged(value) => {
+                self.number_input1_value = *value;
+                println!("ValueChanged: {}", value);
+                cx.notify();
+            }
         }
     }
 
@@ -290,7 +420,7 @@ impl InputStory {
         &mut self,
         _: &Entity<NumberInput>,
         event: &NumberInputEvent,
-        window: &mut Window,
+        _: &mut Window,
         cx: &mut Context<Self>,
     ) {
         match event {
@@ -300,24 +430,12 @@ impl InputStory {
                 InputEvent::Focus => println!("Focus"),
                 InputEvent::Blur => println!("Blur"),
             },
-            NumberInputEvent::Step(step_action) => match step_action {
-                ui::input::StepAction::Decrement => {
-                    if self.number_input2_value.le(&0) {
-                        return;
-                    }
-
-                    self.number_input2_value = self.number_input2_value - 1;
-                    self.number_input2.update(cx, |input, cx| {
-                        input.set_value(self.number_input2_value.to_string(), window, cx);
-                    });
-                }
-                ui::input::StepAction::Increment => {
-                    self.number_input2_value = self.number_input2_value + 1;
-                    self.number_input2.update(cx, |input, cx| {
-                        input.set_value(self.number_input2_value.to_string(), window, cx);
-                    });
-                }
-            },
+            NumberInputEvent::Step(_) => {}
+            NumberInputEvent::ValueChanged(value) => {
+                self.number_input2_value = *value;
+                println!("ValueChanged: {}", value);
+                cx.notify();
+            }
         }
     }
 
@@ -350,6 +468,11 @@ impl FocusableCycle for InputStory {
             self.suffix_input1.focus_handle(cx),
             self.large_input.focus_handle(cx),
             self.small_input.focus_handle(cx),
+            self.phone_input.focus_handle(cx),
+            self.date_input.focus_handle(cx),
+            self.plate_input.focus_handle(cx),
+            self.email_tag_input.focus_handle(cx),
+            self.language_tag_input.focus_handle(cx),
             self.otp_input.focus_handle(cx),
         ]
         .to_vec()
@@ -383,12 +506,19 @@ impl Render for InputStory {
                                 v_flex()
                                     .gap_y_4()
                                     .w_full()
-                                    .child("Number Input")
+                                    .child(format!(
+                                        "Number Input: {}, {}",
+                                        self.number_input1_value, self.number_input2_value
+                                    ))
                                     .child(self.number_input1.clone())
                                     .child(self.number_input2.clone()),
                             ),
                     )
-                    .child(section("Textarea", cx).child(self.textarea.clone()))
+                    .child(
+                        section("Textarea", cx)
+                            .child(self.textarea.clone())
+                            .child(self.code_editor.clone()),
+                    )
                     .child(
                         section("Input State", cx)
                             .child(self.disabled_input.clone())
@@ -409,6 +539,31 @@ impl Render for InputStory {
                         section("Input Size", cx)
                             .child(self.large_input.clone())
                             .child(self.small_input.clone()),
+                    )
+                    .child(
+                        section("Input Mask", cx)
+                            .child(self.phone_input.clone())
+                            .child(format!(
+                                "Raw value: {}",
+                                self.phone_input.read(cx).raw_value()
+                            ))
+                            .child(self.date_input.clone())
+                            .child(self.plate_input.clone()),
+                    )
+                    .child(
+                        section("Tag Input", cx)
+                            .child(self.email_tag_input.clone())
+                            .child(format!(
+                                "Recipients: {}",
+                                self.email_tag_input
+                                    .read(cx)
+                                    .values()
+                                    .iter()
+                                    .map(|value| value.as_ref())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ))
+                            .child(self.language_tag_input.clone()),
                     ),
             )
             .child(
@@ -19,6 +19,7 @@ mod table_story;
 mod text_story;
 mod title_bar;
 mod tooltip_story;
+mod tree_story;
 mod webview_story;
 
 pub use assets::Assets;
@@ -51,10 +52,12 @@ pub use table_story::TableStory;
 pub use text_story::TextStory;
 pub use title_bar::AppTitleBar;
 pub use tooltip_story::TooltipStory;
+pub use tree_story::TreeStory;
 pub use webview_story::WebViewStory;
 
 use ui::{
     button::Button,
+    command_palette::{register_command, Command},
     divider::Divider,
     dock::{register_panel, Panel, PanelControl, PanelEvent, PanelInfo, PanelState, TitleStyle},
     h_flex,
@@ -188,6 +191,9 @@ pub fn init(cx: &mut App) {
     dropdown_story::init(cx);
     popup_story::init(cx);
 
+    register_command(cx, Command::new("Quit", Quit).category("App"));
+    register_command(cx, Command::new("Open...", Open).category("File"));
+
     register_panel(cx, PANEL_NAME, |_, _, info, window, cx| {
         let story_state = match info {
             PanelInfo::Panel(value) => StoryState::from_value(value.clone()),
@@ -404,6 +410,7 @@ impl StoryState {
             "TableStory" => story!(TableStory),
             "TextStory" => story!(TextStory),
             "TooltipStory" => story!(TooltipStory),
+            "TreeStory" => story!(TreeStory),
             "WebViewStory" => story!(WebViewStory),
             "AccordionStory" => story!(AccordionStory),
             "SidebarStory" => story!(SidebarStory),
@@ -3,15 +3,15 @@ use std::time::Duration;
 
 use fake::Fake;
 use gpui::{
-    actions, div, px, App, AppContext, Context, ElementId, Entity, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, ParentElement, Render, RenderOnce, SharedString, Styled,
-    Subscription, Task, Timer, Window,
+    actions, div, prelude::FluentBuilder as _, px, AnyElement, App, AppContext, Context, ElementId,
+    Entity, FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement, Render,
+    RenderOnce, SharedString, Styled, Subscription, Task, Timer, Window,
 };
 
 use ui::{
     button::Button,
     checkbox::Checkbox,
-    h_flex, hsl,
+    fuzzy, h_flex, hsl,
     label::Label,
     list::{List, ListDelegate, ListEvent, ListItem},
     v_flex, ActiveTheme, Sizable,
@@ -52,13 +52,21 @@ struct CompanyListItem {
     base: ListItem,
     ix: usize,
     company: Company,
+    highlights: Vec<usize>,
     selected: bool,
 }
 
 impl CompanyListItem {
-    pub fn new(id: impl Into<ElementId>, company: Company, ix: usize, selected: bool) -> Self {
+    pub fn new(
+        id: impl Into<ElementId>,
+        company: Company,
+        highlights: Vec<usize>,
+        ix: usize,
+        selected: bool,
+    ) -> Self {
         CompanyListItem {
             company,
+            highlights,
             ix,
             base: ListItem::new(id),
             selected,
@@ -105,7 +113,11 @@ impl RenderOnce for CompanyListItem {
                             .max_w(px(500.))
                             .overflow_x_hidden()
                             .flex_nowrap()
-                            .child(Label::new(self.company.name.clone()).whitespace_nowrap())
+                            .child(
+                                Label::new(self.company.name.clone())
+                                    .highlights(self.highlights)
+                                    .whitespace_nowrap(),
+                            )
                             .child(
                                 div().text_sm().overflow_x_hidden().child(
                                     Label::new(self.company.industry.clone())
@@ -143,8 +155,10 @@ impl RenderOnce for CompanyListItem {
 
 struct CompanyListDelegate {
     companies: Vec<Company>,
-    matched_companies: Vec<Company>,
+    /// The matched companies and the positions of the matched chars in the name.
+    matched_companies: Vec<(Company, Vec<usize>)>,
     selected_index: Option<usize>,
+    selected_indices: Vec<usize>,
     confirmed_index: Option<usize>,
     query: String,
     loading: bool,
@@ -165,12 +179,11 @@ impl ListDelegate for CompanyListDelegate {
         _: &mut Context<List<Self>>,
     ) -> Task<()> {
         self.query = query.to_string();
-        self.matched_companies = self
-            .companies
-            .iter()
-            .filter(|company| company.name.to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
-            .collect();
+        self.matched_companies =
+            fuzzy::match_items(query, &self.companies, |company| company.name.clone())
+                .into_iter()
+                .map(|(ix, m)| (self.companies[ix].clone(), m.positions))
+                .collect();
 
         Task::ready(())
     }
@@ -190,15 +203,33 @@ impl ListDelegate for CompanyListDelegate {
         cx.notify();
     }
 
+    fn set_selected_indices(
+        &mut self,
+        indices: &[usize],
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+        self.selected_indices = indices.to_vec();
+        cx.notify();
+    }
+
     fn render_item(
         &self,
         ix: usize,
         _: &mut Window,
         _: &mut Context<List<Self>>,
     ) -> Option<Self::Item> {
-        let selected = Some(ix) == self.selected_index || Some(ix) == self.confirmed_index;
-        if let Some(company) = self.matched_companies.get(ix) {
-            return Some(CompanyListItem::new(ix, company.clone(), ix, selected));
+        let selected = Some(ix) == self.selected_index
+            || Some(ix) == self.confirmed_index
+            || self.selected_indices.contains(&ix);
+        if let Some((company, highlights)) = self.matched_companies.get(ix) {
+            return Some(CompanyListItem::new(
+                ix,
+                company.clone(),
+                highlights.clone(),
+                ix,
+                selected,
+            ));
         }
 
         None
@@ -208,6 +239,12 @@ impl ListDelegate for CompanyListDelegate {
         self.loading
     }
 
+    fn move_item(&mut self, from: usize, to: usize, _: &mut Window, cx: &mut Context<List<Self>>) {
+        let item = self.matched_companies.remove(from);
+        self.matched_companies.insert(to, item);
+        cx.notify();
+    }
+
     fn can_load_more(&self, _: &App) -> bool {
         return !self.loading && !self.is_eof;
     }
@@ -244,10 +281,210 @@ impl CompanyListDelegate {
     }
 }
 
+/// The contacts in the sections by the first letter of the name.
+struct ContactListDelegate {
+    sections: Vec<(SharedString, Vec<SharedString>)>,
+    selected_index: Option<usize>,
+}
+
+impl ContactListDelegate {
+    fn new(mut names: Vec<String>) -> Self {
+        names.sort();
+
+        let mut sections: Vec<(SharedString, Vec<SharedString>)> = vec![];
+        for name in names {
+            let letter: SharedString = name.chars().take(1).collect::<String>().into();
+            match sections.last_mut() {
+                Some((last, contacts)) if *last == letter => contacts.push(name.into()),
+                _ => sections.push((letter, vec![name.into()])),
+            }
+        }
+
+        Self {
+            sections,
+            selected_index: None,
+        }
+    }
+}
+
+impl ListDelegate for ContactListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _: &App) -> usize {
+        self.sections
+            .iter()
+            .map(|(_, contacts)| contacts.len())
+            .sum()
+    }
+
+    fn sections_count(&self, _: &App) -> usize {
+        self.sections.len()
+    }
+
+    fn section_items_count(&self, section: usize, _: &App) -> usize {
+        self.sections
+            .get(section)
+            .map_or(0, |(_, contacts)| contacts.len())
+    }
+
+    fn render_section_header(
+        &self,
+        section: usize,
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) -> Option<AnyElement> {
+        let (letter, _) = self.sections.get(section)?;
+
+        Some(
+            ListItem::new(("contact-section", section))
+                .bg(cx.theme().list_head)
+                .child(
+                    div()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(letter.clone()),
+                )
+                .into_any_element(),
+        )
+    }
+
+    fn render_item(
+        &self,
+        ix: usize,
+        _: &mut Window,
+        _: &mut Context<List<Self>>,
+    ) -> Option<Self::Item> {
+        let name = self
+            .sections
+            .iter()
+            .flat_map(|(_, contacts)| contacts)
+            .nth(ix)?;
+
+        Some(
+            ListItem::new(("contact", ix))
+                .selected(Some(ix) == self.selected_index)
+                .child(name.clone()),
+        )
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: Option<usize>,
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+}
+
+/// The log lines, the older lines are loaded when scrolling to the top,
+/// and the new lines are appended to the bottom.
+struct LogListDelegate {
+    lines: Vec<SharedString>,
+    /// The line number of the first line.
+    first_line: usize,
+    loading_before: bool,
+    selected_index: Option<usize>,
+}
+
+impl LogListDelegate {
+    fn new(last_line: usize) -> Self {
+        let first_line = last_line.saturating_sub(50);
+
+        Self {
+            lines: (first_line..last_line).map(Self::log_line).collect(),
+            first_line,
+            loading_before: false,
+            selected_index: None,
+        }
+    }
+
+    fn log_line(line: usize) -> SharedString {
+        format!(
+            "#{} {}",
+            line,
+            fake::faker::lorem::en::Word().fake::<String>()
+        )
+        .into()
+    }
+
+    fn push_line(&mut self) {
+        let line = self.first_line + self.lines.len();
+        self.lines.push(Self::log_line(line));
+    }
+}
+
+impl ListDelegate for LogListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _: &App) -> usize {
+        self.lines.len()
+    }
+
+    fn render_item(
+        &self,
+        ix: usize,
+        _: &mut Window,
+        _: &mut Context<List<Self>>,
+    ) -> Option<Self::Item> {
+        let line = self.lines.get(ix)?;
+
+        Some(
+            ListItem::new(("log", ix))
+                .selected(Some(ix) == self.selected_index)
+                .child(line.clone()),
+        )
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: Option<usize>,
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn load_more_threshold(&self) -> usize {
+        10
+    }
+
+    fn can_load_more_before(&self, _: &App) -> bool {
+        !self.loading_before && self.first_line > 0
+    }
+
+    fn load_more_before(&mut self, window: &mut Window, cx: &mut Context<List<Self>>) {
+        self.loading_before = true;
+        cx.spawn_in(window, |view, mut window| async move {
+            // Simulate network request, delay 1s to load the older lines.
+            Timer::after(Duration::from_secs(1)).await;
+
+            _ = view.update_in(&mut window, move |view, window, cx| {
+                let delegate = view.delegate_mut();
+                let first_line = delegate.first_line.saturating_sub(50);
+                let count = delegate.first_line - first_line;
+                delegate.lines.splice(
+                    0..0,
+                    (first_line..delegate.first_line).map(LogListDelegate::log_line),
+                );
+                delegate.first_line = first_line;
+                delegate.loading_before = false;
+                view.prepend_items(count, window, cx);
+            });
+        })
+        .detach();
+    }
+}
+
 pub struct ListStory {
     focus_handle: FocusHandle,
     company_list: Entity<List<CompanyListDelegate>>,
+    contact_list: Entity<List<ContactListDelegate>>,
+    log_list: Entity<List<LogListDelegate>>,
     selected_company: Option<Company>,
+    multiple: bool,
+    selected_count: usize,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -276,25 +513,44 @@ impl ListStory {
             .collect::<Vec<Company>>();
 
         let delegate = CompanyListDelegate {
-            matched_companies: companies.clone(),
+            matched_companies: companies
+                .iter()
+                .map(|company| (company.clone(), vec![]))
+                .collect(),
             companies,
             selected_index: None,
+            selected_indices: vec![],
             confirmed_index: None,
             query: "".to_string(),
             loading: false,
             is_eof: false,
         };
 
-        let company_list = cx.new(|cx| List::new(delegate, window, cx));
+        let company_list = cx.new(|cx| List::new(delegate, window, cx).reorderable());
+        let contact_list = cx.new(|cx| {
+            let names = (0..200)
+                .map(|_| fake::faker::name::en::Name().fake::<String>())
+                .collect();
+            List::new(ContactListDelegate::new(names), window, cx).no_query()
+        });
+        let log_list = cx.new(|cx| {
+            List::new(LogListDelegate::new(500), window, cx)
+                .no_query()
+                .stick_to_bottom()
+        });
         // company_list.update(cx, |list, cx| {
         //     list.set_selected_index(Some(3), cx);
         // });
         let _subscriptions =
             vec![
-                cx.subscribe(&company_list, |_, _, ev: &ListEvent, _| match ev {
+                cx.subscribe(&company_list, |this, _, ev: &ListEvent, cx| match ev {
                     ListEvent::Select(ix) => {
                         println!("List Selected: {:?}", ix);
                     }
+                    ListEvent::SelectMultiple(indices) => {
+                        this.selected_count = indices.len();
+                        cx.notify();
+                    }
                     ListEvent::Confirm(ix) => {
                         println!("List Confirmed: {:?}", ix);
                     }
@@ -325,10 +581,29 @@ impl ListStory {
         })
         .detach();
 
+        // Append a new log line every second, the log list follows it when scrolled to the bottom.
+        cx.spawn(move |this, mut cx| async move {
+            loop {
+                Timer::after(Duration::from_secs(1)).await;
+                this.update(&mut cx, |this, cx| {
+                    this.log_list.update(cx, |list, cx| {
+                        list.delegate_mut().push_line();
+                        cx.notify();
+                    });
+                })
+                .ok();
+            }
+        })
+        .detach();
+
         Self {
             focus_handle: cx.focus_handle(),
             company_list,
+            contact_list,
+            log_list,
             selected_company: None,
+            multiple: false,
+            selected_count: 0,
             _subscriptions,
         }
     }
@@ -420,16 +695,72 @@ impl Render for ListStory {
                                     cx.notify();
                                 })
                             })),
-                    ),
+                    )
+                    .child(
+                        Checkbox::new("multiple")
+                            .label("Multiple Selection")
+                            .checked(self.multiple)
+                            .on_click(cx.listener(|this, check: &bool, window, cx| {
+                                this.multiple = *check;
+                                this.company_list.update(cx, |list, cx| {
+                                    list.set_multiple(*check, window, cx);
+                                })
+                            })),
+                    )
+                    .when(self.multiple, |this| {
+                        this.child(format!("{} selected", self.selected_count))
+                    }),
             )
             .child(
-                div()
+                h_flex()
                     .flex_1()
                     .w_full()
-                    .border_1()
-                    .border_color(cx.theme().border)
-                    .rounded_md()
-                    .child(self.company_list.clone()),
+                    .gap_4()
+                    .child(
+                        div()
+                            .flex_1()
+                            .h_full()
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .rounded_md()
+                            .child(self.company_list.clone()),
+                    )
+                    .child(
+                        v_flex()
+                            .w(px(240.))
+                            .h_full()
+                            .gap_2()
+                            .child(
+                                Button::new("scroll-to-last-section")
+                                    .child("Scroll to Last Section")
+                                    .small()
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.contact_list.update(cx, |list, cx| {
+                                            let section = list
+                                                .delegate()
+                                                .sections_count(cx)
+                                                .saturating_sub(1);
+                                            list.scroll_to_item((section, 0), window, cx);
+                                        })
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .rounded_md()
+                                    .child(self.contact_list.clone()),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .rounded_md()
+                                    .child(self.log_list.clone()),
+                            ),
+                    ),
             )
     }
 }
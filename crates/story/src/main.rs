@@ -6,7 +6,7 @@ use story::{
     AccordionStory, AppState, AppTitleBar, Assets, ButtonStory, CalendarStory, DropdownStory,
     FormStory, IconStory, ImageStory, InputStory, ListStory, ModalStory, Open, PopupStory,
     ProgressStory, Quit, ResizableStory, ScrollableStory, SidebarStory, StoryContainer,
-    SwitchStory, TableStory, TextStory, TooltipStory,
+    SwitchStory, TableStory, TextStory, TooltipStory, TreeStory,
 };
 use ui::{
     button::{Button, ButtonVariants as _},
@@ -353,6 +353,7 @@ impl StoryWorkspace {
                     Arc::new(StoryContainer::panel::<AccordionStory>(window, cx)),
                     Arc::new(StoryContainer::panel::<SidebarStory>(window, cx)),
                     Arc::new(StoryContainer::panel::<FormStory>(window, cx)),
+                    Arc::new(StoryContainer::panel::<TreeStory>(window, cx)),
                     // Arc::new(StoryContainer::panel::<WebViewStory>(window, cx)),
                 ],
                 None,
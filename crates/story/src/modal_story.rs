@@ -10,10 +10,12 @@ use gpui::{
 use ui::{
     button::{Button, ButtonVariant, ButtonVariants as _},
     checkbox::Checkbox,
+    command_palette::ToggleCommandPalette,
     date_picker::DatePicker,
     dropdown::Dropdown,
-    h_flex,
+    fuzzy, h_flex,
     input::TextInput,
+    label::Label,
     list::{List, ListDelegate, ListItem},
     modal::ModalButtonProps,
     notification::{Notification, NotificationType},
@@ -29,7 +31,8 @@ pub struct ListItemDeletegate {
     confirmed_index: Option<usize>,
     selected_index: Option<usize>,
     items: Vec<Arc<String>>,
-    matches: Vec<Arc<String>>,
+    /// The matched items and the positions of the matched chars.
+    matches: Vec<(Arc<String>, Vec<usize>)>,
 }
 
 impl ListDelegate for ListItemDeletegate {
@@ -52,13 +55,12 @@ impl ListDelegate for ListItemDeletegate {
             Timer::after(Duration::from_secs_f64(sleep)).await;
 
             this.update(&mut cx, |this, cx| {
-                this.delegate_mut().matches = this
-                    .delegate()
-                    .items
-                    .iter()
-                    .filter(|item| item.to_lowercase().contains(&query.to_lowercase()))
-                    .cloned()
+                let items = &this.delegate().items;
+                let matches = fuzzy::match_items(&query, items, |item| item.to_string().into())
+                    .into_iter()
+                    .map(|(ix, m)| (items[ix].clone(), m.positions))
                     .collect();
+                this.delegate_mut().matches = matches;
                 cx.notify();
             })
             .ok();
@@ -74,7 +76,7 @@ impl ListDelegate for ListItemDeletegate {
         let confirmed = Some(ix) == self.confirmed_index;
         let selected = Some(ix) == self.selected_index;
 
-        if let Some(item) = self.matches.get(ix) {
+        if let Some((item, highlights)) = self.matches.get(ix) {
             let list_item = ListItem::new(("item", ix))
                 .check_icon(ui::IconName::Check)
                 .confirmed(confirmed)
@@ -85,7 +87,7 @@ impl ListDelegate for ListItemDeletegate {
                     h_flex()
                         .items_center()
                         .justify_between()
-                        .child(item.to_string()),
+                        .child(Label::new(item.to_string()).highlights(highlights.clone())),
                 )
                 .suffix(|_, _| {
                     Button::new("like")
@@ -130,7 +132,7 @@ impl ListDelegate for ListItemDeletegate {
     fn confirm(&mut self, ix: usize, window: &mut Window, cx: &mut Context<List<Self>>) {
         _ = self.story.update(cx, |this, cx| {
             self.confirmed_index = Some(ix);
-            if let Some(item) = self.matches.get(ix) {
+            if let Some((item, _)) = self.matches.get(ix) {
                 this.selected_value = Some(SharedString::from(item.to_string()));
             }
 
@@ -248,7 +250,7 @@ impl ModalStory {
             selected_index: None,
             confirmed_index: None,
             items: items.clone(),
-            matches: items.clone(),
+            matches: items.iter().map(|item| (item.clone(), vec![])).collect(),
         };
         let list = cx.new(|cx| {
             let mut list = List::new(delegate, window, cx);
@@ -520,6 +522,13 @@ impl Render for ModalStory {
                             .items_start()
                             .gap_3()
                             .flex_wrap()
+                            .child(
+                                Button::new("command-palette")
+                                    .label("Command Palette...")
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(Box::new(ToggleCommandPalette), cx);
+                                    }),
+                            )
                             .child(Button::new("webview").label("Open WebView").on_click(
                                 cx.listener(|_, _, window, cx| {
                                     let webview = cx.new(|cx| {
@@ -19,7 +19,7 @@ use ui::{
     label::Label,
     popup_menu::{PopupMenu, PopupMenuExt},
     prelude::FluentBuilder as _,
-    table::{ColFixed, ColSort, Table, TableDelegate, TableEvent},
+    table::{ColFilter, ColFixed, ColSort, Table, TableDelegate, TableEvent},
     v_flex, ActiveTheme as _, Selectable, Sizable as _, Size, StyleSized as _,
 };
 
@@ -80,6 +80,37 @@ struct Stock {
 }
 
 impl Stock {
+    /// Returns the plain text of the column.
+    fn text(&self, col_id: &str) -> SharedString {
+        let value = match col_id {
+            "id" => return self.id.to_string().into(),
+            "name" => return self.name.clone().into(),
+            "symbol" => return self.symbol.clone().into(),
+            "price" => self.price,
+            "change" => self.change,
+            "change_percent" => self.change_percent,
+            "volume" => self.volume,
+            "turnover" => self.turnover,
+            "market_cap" => self.market_cap,
+            "ttm" => self.ttm,
+            "five_mins_ranking" => self.five_mins_ranking,
+            "bid" => self.bid,
+            "bid_volume" => self.bid_volume,
+            "ask" => self.ask,
+            "ask_volume" => self.ask_volume,
+            "open" => self.open,
+            "prev_close" => self.prev_close,
+            "high" => self.high,
+            "low" => self.low,
+            "volume_ratio" => self.volume_ratio,
+            "bid_ask_ratio" => self.bid_ask_ratio,
+            "shares" => return self.shares.to_string().into(),
+            "shares_float" => return self.shares_float.to_string().into(),
+            _ => return "--".into(),
+        };
+        format!("{:.3}", value).into()
+    }
+
     fn random_update(&mut self) {
         self.price = (-300.0..999.999).fake::<f64>();
         self.change = (-0.1..5.0).fake::<f64>();
@@ -173,6 +204,8 @@ impl Column {
 
 struct StockTableDelegate {
     stocks: Vec<Stock>,
+    /// The stocks are hidden by the column filters.
+    hidden_stocks: Vec<Stock>,
     columns: Vec<Column>,
     size: Size,
     loop_selection: bool,
@@ -180,6 +213,8 @@ struct StockTableDelegate {
     col_order: bool,
     col_sort: bool,
     col_selection: bool,
+    col_filter: bool,
+    cell_edit: bool,
     loading: bool,
     full_loading: bool,
     fixed_cols: bool,
@@ -193,6 +228,7 @@ impl StockTableDelegate {
         Self {
             size: Size::default(),
             stocks: random_stocks(size),
+            hidden_stocks: vec![],
             columns: vec![
                 Column::new("id", "ID", None),
                 Column::new("symbol", "Symbol", Some(ColSort::Default)),
@@ -244,6 +280,8 @@ impl StockTableDelegate {
             col_order: true,
             col_sort: true,
             col_selection: true,
+            col_filter: true,
+            cell_edit: false,
             fixed_cols: false,
             loading: false,
             full_loading: false,
@@ -255,6 +293,7 @@ impl StockTableDelegate {
 
     fn update_stocks(&mut self, size: usize) {
         self.stocks = random_stocks(size);
+        self.hidden_stocks.clear();
         self.is_eof = size <= 50;
         self.loading = false;
         self.full_loading = false;
@@ -449,6 +488,92 @@ impl TableDelegate for StockTableDelegate {
         }
     }
 
+    fn can_edit_cell(&self, _: usize, col_ix: usize, _: &App) -> bool {
+        self.cell_edit
+            && self
+                .columns
+                .get(col_ix)
+                .is_some_and(|col| matches!(col.id.as_ref(), "symbol" | "name" | "price"))
+    }
+
+    fn commit_cell_edit(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        value: SharedString,
+        _: &mut Window,
+        _: &mut Context<Table<Self>>,
+    ) -> Result<(), SharedString> {
+        let (Some(stock), Some(col)) = (self.stocks.get_mut(row_ix), self.columns.get(col_ix))
+        else {
+            return Ok(());
+        };
+
+        let value = value.trim();
+        match col.id.as_ref() {
+            "symbol" | "name" if value.is_empty() => {
+                return Err(format!("{} can not be empty.", col.name).into());
+            }
+            "symbol" => stock.symbol = value.to_uppercase(),
+            "name" => stock.name = value.to_string(),
+            "price" => {
+                stock.price = value
+                    .parse::<f64>()
+                    .map_err(|_| SharedString::from("Price must be a number."))?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn cell_text(&self, row_ix: usize, col_ix: usize, _: &App) -> SharedString {
+        let (Some(stock), Some(col)) = (self.stocks.get(row_ix), self.columns.get(col_ix)) else {
+            return "".into();
+        };
+
+        stock.text(&col.id)
+    }
+
+    fn can_filter_col(&self, col_ix: usize, _: &App) -> bool {
+        self.col_filter
+            && self
+                .columns
+                .get(col_ix)
+                .is_some_and(|col| matches!(col.id.as_ref(), "symbol" | "name" | "change"))
+    }
+
+    fn col_filter_values(&self, col_ix: usize, _: &App) -> Option<Vec<SharedString>> {
+        match self.columns.get(col_ix)?.id.as_ref() {
+            "change" => Some(vec!["Up".into(), "Down".into()]),
+            _ => None,
+        }
+    }
+
+    fn perform_filter(
+        &mut self,
+        filters: Vec<(usize, ColFilter)>,
+        _: &mut Window,
+        _: &mut Context<Table<Self>>,
+    ) {
+        let filters = filters
+            .into_iter()
+            .filter_map(|(col_ix, filter)| Some((self.columns.get(col_ix)?.id.clone(), filter)))
+            .collect::<Vec<_>>();
+
+        let mut stocks = std::mem::take(&mut self.stocks);
+        stocks.append(&mut self.hidden_stocks);
+        stocks.sort_by_key(|stock| stock.id);
+        (self.stocks, self.hidden_stocks) = stocks.into_iter().partition(|stock| {
+            filters
+                .iter()
+                .all(|(col_id, filter)| match col_id.as_ref() {
+                    // The checklist of the change column is the direction, instead of the cell text.
+                    "change" => filter.matches(if stock.change >= 0. { "Up" } else { "Down" }),
+                    _ => filter.matches(&stock.text(col_id)),
+                })
+        });
+    }
+
     fn can_loop_select(&self, _: &App) -> bool {
         self.loop_selection
     }
@@ -562,6 +687,8 @@ pub struct TableStory {
     table: Entity<Table<StockTableDelegate>>,
     num_stocks_input: Entity<TextInput>,
     stripe: bool,
+    multiple: bool,
+    cell_selection: bool,
     refresh_data: bool,
     size: Size,
 }
@@ -645,6 +772,8 @@ impl TableStory {
             table,
             num_stocks_input,
             stripe: false,
+            multiple: false,
+            cell_selection: false,
             refresh_data: false,
             size: Size::default(),
         }
@@ -712,6 +841,20 @@ impl TableStory {
         });
     }
 
+    fn toggle_col_filter(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.table.update(cx, |table, cx| {
+            table.delegate_mut().col_filter = *checked;
+            cx.notify();
+        });
+    }
+
+    fn toggle_cell_edit(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.table.update(cx, |table, cx| {
+            table.delegate_mut().cell_edit = *checked;
+            cx.notify();
+        });
+    }
+
     fn toggle_stripe(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
         self.stripe = *checked;
         let stripe = self.stripe;
@@ -721,6 +864,22 @@ impl TableStory {
         });
     }
 
+    fn toggle_multiple(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.multiple = *checked;
+        let multiple = self.multiple;
+        self.table.update(cx, |table, cx| {
+            table.set_multiple(multiple, cx);
+        });
+    }
+
+    fn toggle_cell_selection(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.cell_selection = *checked;
+        let cell_selection = self.cell_selection;
+        self.table.update(cx, |table, cx| {
+            table.set_cell_selection(cell_selection, cx);
+        });
+    }
+
     fn toggle_fixed_cols(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
         self.table.update(cx, |table, cx| {
             table.delegate_mut().fixed_cols = *checked;
@@ -756,6 +915,10 @@ impl TableStory {
             TableEvent::SelectCol(ix) => println!("Select col: {}", ix),
             TableEvent::DoubleClickedRow(ix) => println!("Double clicked row: {}", ix),
             TableEvent::SelectRow(ix) => println!("Select row: {}", ix),
+            TableEvent::SelectRows(rows) => println!("Select rows: {} selected", rows.len()),
+            TableEvent::SelectCells(rows, cols) => {
+                println!("Select cells: rows {:?}, cols {:?}", rows, cols)
+            }
             TableEvent::MoveCol(origin_idx, target_idx) => {
                 println!("Move col index: {} -> {}", origin_idx, target_idx);
             }
@@ -809,12 +972,36 @@ impl Render for TableStory {
                             .selected(delegate.col_selection)
                             .on_click(cx.listener(Self::toggle_col_selection)),
                     )
+                    .child(
+                        Checkbox::new("col-filter")
+                            .label("Column Filter")
+                            .selected(delegate.col_filter)
+                            .on_click(cx.listener(Self::toggle_col_filter)),
+                    )
+                    .child(
+                        Checkbox::new("cell-edit")
+                            .label("Cell Edit")
+                            .selected(delegate.cell_edit)
+                            .on_click(cx.listener(Self::toggle_cell_edit)),
+                    )
                     .child(
                         Checkbox::new("stripe")
                             .label("Stripe")
                             .selected(self.stripe)
                             .on_click(cx.listener(Self::toggle_stripe)),
                     )
+                    .child(
+                        Checkbox::new("multiple")
+                            .label("Multiple Selection")
+                            .selected(self.multiple)
+                            .on_click(cx.listener(Self::toggle_multiple)),
+                    )
+                    .child(
+                        Checkbox::new("cell-selection")
+                            .label("Cell Selection")
+                            .selected(self.cell_selection)
+                            .on_click(cx.listener(Self::toggle_cell_selection)),
+                    )
                     .child(
                         Checkbox::new("fixed-cols")
                             .label("Fixed Columns")
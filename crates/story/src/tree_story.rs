@@ -0,0 +1,497 @@
+use std::{collections::HashMap, time::Duration};
+
+use fake::Fake;
+use gpui::{
+    div, prelude::FluentBuilder as _, px, App, AppContext, Context, Entity, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Pixels, Render, SharedString, Styled,
+    Subscription, Task, Timer, Window,
+};
+
+use ui::{
+    checkbox::Checkbox,
+    h_flex,
+    table::{Table, TableDelegate, TableEvent},
+    tree::{Tree, TreeDelegate, TreeEntry, TreeEvent},
+    v_flex, ActiveTheme, Icon, IconName, Sizable,
+};
+
+/// The path of the root folder.
+const ROOT: &str = "";
+
+/// A fake file system, the children of the folders are loaded when expanded.
+struct FileTreeDelegate {
+    /// The path of the folders and their children, a folder without entry is not loaded yet.
+    children: HashMap<SharedString, Vec<SharedString>>,
+    folders: Vec<SharedString>,
+}
+
+impl FileTreeDelegate {
+    fn new() -> Self {
+        let mut this = Self {
+            children: HashMap::new(),
+            folders: vec![],
+        };
+        let roots = ["src", "docs", "assets", "tests"]
+            .into_iter()
+            .map(|name| this.add_folder(ROOT, name))
+            .chain(["Cargo.toml", "README.md"].map(SharedString::from))
+            .collect();
+        this.children.insert(ROOT.into(), roots);
+        this
+    }
+
+    fn add_folder(&mut self, parent: &str, name: &str) -> SharedString {
+        let path = Self::join(parent, name);
+        self.folders.push(path.clone());
+        path
+    }
+
+    fn join(parent: &str, name: &str) -> SharedString {
+        if parent.is_empty() {
+            name.to_string().into()
+        } else {
+            format!("{}/{}", parent, name).into()
+        }
+    }
+
+    fn name(path: &str) -> &str {
+        path.rsplit('/').next().unwrap_or(path)
+    }
+
+    fn is_folder(&self, path: &SharedString) -> bool {
+        self.folders.contains(path)
+    }
+}
+
+impl TreeDelegate for FileTreeDelegate {
+    type Node = SharedString;
+    type Item = gpui::Div;
+
+    fn children(&self, parent: Option<&SharedString>, _: &App) -> Option<Vec<SharedString>> {
+        let parent = parent.map_or(ROOT, |parent| parent.as_ref());
+        self.children.get(parent).cloned()
+    }
+
+    fn has_children(&self, node: &SharedString, _: &App) -> bool {
+        self.is_folder(node)
+    }
+
+    fn load_children(
+        &mut self,
+        node: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Task<()> {
+        let node = node.clone();
+        cx.spawn_in(window, |view, mut cx| async move {
+            // Simulate reading the folder, delay 0.5s to load the children.
+            Timer::after(Duration::from_millis(500)).await;
+
+            _ = view.update_in(&mut cx, |view, _, _| {
+                let delegate = view.delegate_mut();
+                let depth = node.split('/').count();
+                let mut children = vec![];
+                if depth < 4 {
+                    for ix in 0..(1..4).fake::<usize>() {
+                        children.push(delegate.add_folder(&node, &format!("folder_{}", ix)));
+                    }
+                }
+                for _ in 0..(2..8).fake::<usize>() {
+                    let name = fake::faker::filesystem::en::FileName().fake::<String>();
+                    children.push(Self::join(&node, &name));
+                }
+                delegate.children.insert(node, children);
+            });
+        })
+    }
+
+    fn label(&self, node: &SharedString, _: &App) -> SharedString {
+        Self::name(node).to_string().into()
+    }
+
+    fn render_item(
+        &self,
+        entry: &TreeEntry<SharedString>,
+        _: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Option<Self::Item> {
+        let icon = if entry.has_children {
+            IconName::Inbox
+        } else {
+            IconName::BookOpen
+        };
+
+        Some(
+            h_flex()
+                .gap_2()
+                .child(
+                    Icon::new(icon)
+                        .small()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .child(Self::name(&entry.node).to_string()),
+        )
+    }
+
+    fn can_drag(&self, _: &SharedString, _: &App) -> bool {
+        true
+    }
+
+    fn move_nodes(
+        &mut self,
+        nodes: Vec<SharedString>,
+        parent: &SharedString,
+        _: &mut Window,
+        _: &mut Context<Tree<Self>>,
+    ) {
+        for children in self.children.values_mut() {
+            children.retain(|child| !nodes.contains(child));
+        }
+        // Keep the moved nodes as they are, only the parent is changed.
+        self.children
+            .entry(parent.clone())
+            .or_default()
+            .extend(nodes);
+    }
+}
+
+/// A row of the tree grid, the children of a folder are loaded when expanded.
+#[derive(Clone)]
+struct FileRow {
+    path: SharedString,
+    depth: usize,
+    folder: bool,
+    size: usize,
+    expanded: bool,
+    loading: bool,
+}
+
+impl FileRow {
+    fn new(path: SharedString, depth: usize, folder: bool) -> Self {
+        Self {
+            path,
+            depth,
+            folder,
+            size: if folder { 0 } else { (100..1_000_000).fake() },
+            expanded: false,
+            loading: false,
+        }
+    }
+}
+
+/// A fake file system displayed as the tree rows of a table, with the sizes of the files.
+struct FileTableDelegate {
+    rows: Vec<FileRow>,
+    /// The loaded children of the folders, the folder is expanded again without loading.
+    children: HashMap<SharedString, Vec<FileRow>>,
+}
+
+impl FileTableDelegate {
+    fn new() -> Self {
+        let rows = ["src", "docs", "assets", "tests"]
+            .into_iter()
+            .map(|name| FileRow::new(name.into(), 0, true))
+            .chain(
+                ["Cargo.toml", "README.md"]
+                    .into_iter()
+                    .map(|name| FileRow::new(name.into(), 0, false)),
+            )
+            .collect();
+
+        Self {
+            rows,
+            children: HashMap::new(),
+        }
+    }
+
+    fn row_ix(&self, path: &SharedString) -> Option<usize> {
+        self.rows.iter().position(|row| &row.path == path)
+    }
+
+    /// Insert the children after the folder, if the folder is still expanded.
+    fn insert_children(&mut self, path: &SharedString) {
+        let (Some(row_ix), Some(children)) = (self.row_ix(path), self.children.get(path)) else {
+            return;
+        };
+
+        if self.rows[row_ix].expanded {
+            self.rows
+                .splice(row_ix + 1..row_ix + 1, children.iter().cloned());
+        }
+    }
+}
+
+impl TableDelegate for FileTableDelegate {
+    fn cols_count(&self, _: &App) -> usize {
+        3
+    }
+
+    fn rows_count(&self, _: &App) -> usize {
+        self.rows.len()
+    }
+
+    fn col_name(&self, col_ix: usize, _: &App) -> SharedString {
+        match col_ix {
+            0 => "Name",
+            1 => "Size",
+            _ => "Kind",
+        }
+        .into()
+    }
+
+    fn col_width(&self, col_ix: usize, _: &App) -> Pixels {
+        if col_ix == 0 {
+            px(280.)
+        } else {
+            px(100.)
+        }
+    }
+
+    fn render_td(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        _: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> impl IntoElement {
+        let row = &self.rows[row_ix];
+        let name = FileTreeDelegate::name(&row.path);
+
+        match col_ix {
+            0 => h_flex()
+                .gap_2()
+                .child(
+                    Icon::new(if row.folder {
+                        IconName::Inbox
+                    } else {
+                        IconName::BookOpen
+                    })
+                    .small()
+                    .text_color(cx.theme().muted_foreground),
+                )
+                .child(name.to_string())
+                .into_any_element(),
+            1 if row.folder => "--".into_any_element(),
+            1 => format!("{:.1} KB", row.size as f64 / 1024.).into_any_element(),
+            _ if row.folder => "Folder".into_any_element(),
+            _ => name
+                .rsplit_once('.')
+                .map_or("File".to_string(), |(_, ext)| ext.to_uppercase())
+                .into_any_element(),
+        }
+    }
+
+    fn can_load_more(&self, _: &App) -> bool {
+        false
+    }
+
+    fn is_tree(&self, _: &App) -> bool {
+        true
+    }
+
+    fn row_depth(&self, row_ix: usize, _: &App) -> usize {
+        self.rows.get(row_ix).map_or(0, |row| row.depth)
+    }
+
+    fn row_expandable(&self, row_ix: usize, _: &App) -> bool {
+        self.rows.get(row_ix).is_some_and(|row| row.folder)
+    }
+
+    fn row_expanded(&self, row_ix: usize, _: &App) -> bool {
+        self.rows.get(row_ix).is_some_and(|row| row.expanded)
+    }
+
+    fn row_loading(&self, row_ix: usize, _: &App) -> bool {
+        self.rows.get(row_ix).is_some_and(|row| row.loading)
+    }
+
+    fn expand_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Table<Self>>) {
+        let Some(row) = self.rows.get_mut(row_ix) else {
+            return;
+        };
+
+        row.expanded = true;
+        let path = row.path.clone();
+        if self.children.contains_key(&path) {
+            self.insert_children(&path);
+            return;
+        }
+        if row.loading {
+            return;
+        }
+
+        row.loading = true;
+        let depth = row.depth + 1;
+        cx.spawn_in(window, |view, mut cx| async move {
+            // Simulate reading the folder, delay 0.5s to load the children.
+            Timer::after(Duration::from_millis(500)).await;
+
+            _ = view.update_in(&mut cx, |view, _, cx| {
+                let delegate = view.delegate_mut();
+                let mut children = vec![];
+                if depth < 4 {
+                    for ix in 0..(1..4).fake::<usize>() {
+                        let path = FileTreeDelegate::join(&path, &format!("folder_{}", ix));
+                        children.push(FileRow::new(path, depth, true));
+                    }
+                }
+                for _ in 0..(2..8).fake::<usize>() {
+                    let name = fake::faker::filesystem::en::FileName().fake::<String>();
+                    let path = FileTreeDelegate::join(&path, &name);
+                    children.push(FileRow::new(path, depth, false));
+                }
+
+                delegate.children.insert(path.clone(), children);
+                if let Some(row_ix) = delegate.row_ix(&path) {
+                    delegate.rows[row_ix].loading = false;
+                }
+                delegate.insert_children(&path);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn collapse_row(&mut self, row_ix: usize, _: &mut Window, _: &mut Context<Table<Self>>) {
+        let Some(row) = self.rows.get_mut(row_ix) else {
+            return;
+        };
+
+        row.expanded = false;
+        let depth = row.depth;
+        let end = self.rows[row_ix + 1..]
+            .iter()
+            .position(|row| row.depth <= depth)
+            .map_or(self.rows.len(), |len| row_ix + 1 + len);
+        self.rows.drain(row_ix + 1..end);
+    }
+}
+
+pub struct TreeStory {
+    focus_handle: FocusHandle,
+    tree: Entity<Tree<FileTreeDelegate>>,
+    table: Entity<Table<FileTableDelegate>>,
+    multiple: bool,
+    message: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl super::Story for TreeStory {
+    fn title() -> &'static str {
+        "Tree"
+    }
+
+    fn description() -> &'static str {
+        "A tree displays a hierarchical list of items."
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render + Focusable> {
+        Self::view(window, cx)
+    }
+}
+
+impl TreeStory {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let tree = cx.new(|cx| Tree::new(FileTreeDelegate::new(), window, cx));
+        let table = cx.new(|cx| Table::new(FileTableDelegate::new(), window, cx));
+
+        let _subscriptions = vec![
+            cx.subscribe(&tree, |this, _, ev: &TreeEvent<SharedString>, cx| {
+                this.message = match ev {
+                    TreeEvent::Select(node) => format!("Selected: {}", node).into(),
+                    TreeEvent::SelectMultiple(nodes) => format!("{} selected", nodes.len()).into(),
+                    TreeEvent::Confirm(node) => format!("Confirmed: {}", node).into(),
+                    TreeEvent::Expand(node) => format!("Expanded: {}", node).into(),
+                    TreeEvent::Collapse(node) => format!("Collapsed: {}", node).into(),
+                    TreeEvent::Cancel => "".into(),
+                };
+                cx.notify();
+            }),
+            cx.subscribe(&table, |this, table, ev: &TableEvent, cx| {
+                let path = |row_ix: &usize| {
+                    table
+                        .read(cx)
+                        .delegate()
+                        .rows
+                        .get(*row_ix)
+                        .map(|row| row.path.clone())
+                        .unwrap_or_default()
+                };
+                this.message = match ev {
+                    TableEvent::SelectRow(row_ix) => format!("Selected: {}", path(row_ix)),
+                    TableEvent::ExpandRow(row_ix) => format!("Expanded: {}", path(row_ix)),
+                    TableEvent::CollapseRow(row_ix) => format!("Collapsed: {}", path(row_ix)),
+                    _ => return,
+                }
+                .into();
+                cx.notify();
+            }),
+        ];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            tree,
+            table,
+            multiple: false,
+            message: "".into(),
+            _subscriptions,
+        }
+    }
+}
+
+impl Focusable for TreeStory {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for TreeStory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .gap_4()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Checkbox::new("multiple")
+                            .label("Multiple Selection")
+                            .checked(self.multiple)
+                            .on_click(cx.listener(|this, check: &bool, window, cx| {
+                                this.multiple = *check;
+                                this.tree.update(cx, |tree, cx| {
+                                    tree.set_multiple(*check, window, cx);
+                                })
+                            })),
+                    )
+                    .when(!self.message.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(self.message.clone()),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .items_start()
+                    .gap_4()
+                    .child(
+                        div()
+                            .w(px(320.))
+                            .h_full()
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .rounded_md()
+                            .child(self.tree.clone()),
+                    )
+                    .child(div().flex_1().h_full().child(self.table.clone())),
+            )
+    }
+}
@@ -0,0 +1,421 @@
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, px, Action, App, AppContext, Context, Entity,
+    FocusHandle, Focusable, Global, InteractiveElement, IntoElement, KeyBinding, Keystroke,
+    ParentElement, Render, SharedString, Styled, Task, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    fuzzy, h_flex,
+    label::Label,
+    list::{List, ListDelegate, ListItem},
+    popup_menu::key_shortcut,
+    v_flex, ActiveTheme, ContextModal,
+};
+
+actions!(command_palette, [ToggleCommandPalette]);
+
+/// The max number of the recently used commands to keep.
+const MAX_RECENTS: usize = 8;
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-shift-p", ToggleCommandPalette, None),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-p", ToggleCommandPalette, None),
+    ]);
+}
+
+/// A command of the command palette, the action is dispatched to the focused element
+/// before the command palette is opened.
+pub struct Command {
+    pub label: SharedString,
+    pub category: Option<SharedString>,
+    action: Box<dyn Action>,
+}
+
+impl Clone for Command {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            category: self.category.clone(),
+            action: self.action.boxed_clone(),
+        }
+    }
+}
+
+impl Command {
+    pub fn new(label: impl Into<SharedString>, action: impl Action) -> Self {
+        Self {
+            label: label.into(),
+            category: None,
+            action: Box::new(action),
+        }
+    }
+
+    /// Set the category of the command, it is displayed before the label, e.g.: `File: Open`.
+    pub fn category(mut self, category: impl Into<SharedString>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Create a command with the humanized name of the action, e.g.: `list::SelectNext` is `List: Select Next`.
+    fn from_action(action: Box<dyn Action>) -> Self {
+        let (category, label) = humanize_action_name(action.name());
+        Self {
+            label: label.into(),
+            category: category.map(Into::into),
+            action,
+        }
+    }
+
+    /// The text to display and search.
+    fn title(&self) -> SharedString {
+        match &self.category {
+            Some(category) => format!("{}: {}", category, self.label).into(),
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// The registered commands and the recently used commands.
+#[derive(Default)]
+struct CommandRegistry {
+    commands: Vec<Command>,
+    /// The titles of the recently used commands, the latest first.
+    recents: Vec<SharedString>,
+}
+
+impl Global for CommandRegistry {}
+
+impl CommandRegistry {
+    /// The registry is created on the first use, so the commands can be registered before `init`.
+    fn global(cx: &mut App) -> &Self {
+        cx.default_global::<Self>()
+    }
+
+    fn global_mut(cx: &mut App) -> &mut Self {
+        cx.default_global::<Self>()
+    }
+
+    fn push_recent(&mut self, title: SharedString) {
+        self.recents.retain(|recent| recent != &title);
+        self.recents.insert(0, title);
+        self.recents.truncate(MAX_RECENTS);
+    }
+}
+
+/// Register a command to the command palette.
+///
+/// The command replaces the humanized name of the available action that equals to it.
+pub fn register_command(cx: &mut App, command: Command) {
+    CommandRegistry::global_mut(cx).commands.push(command);
+}
+
+/// Split the action name to the humanized category and label.
+///
+/// For example: `list::SelectNext` is `(Some("List"), "Select Next")`.
+fn humanize_action_name(name: &str) -> (Option<String>, String) {
+    let mut parts = name.split("::").map(humanize).collect::<Vec<_>>();
+    let label = parts.pop().unwrap_or_default();
+    let category = (!parts.is_empty()).then(|| parts.join(" "));
+    (category, label)
+}
+
+/// Humanize the `snake_case` or `CamelCase` name to words, e.g.: `popup_menu` is `Popup Menu`.
+fn humanize(name: &str) -> String {
+    let mut text = String::with_capacity(name.len() + 4);
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        if c == '_' {
+            text.push(' ');
+        } else if prev.map_or(true, |prev| prev == '_') {
+            text.extend(c.to_uppercase());
+        } else {
+            if c.is_uppercase()
+                && prev.is_some_and(|prev| prev.is_lowercase() || prev.is_ascii_digit())
+            {
+                text.push(' ');
+            }
+            text.push(c);
+        }
+        prev = Some(c);
+    }
+    text
+}
+
+struct CommandItem {
+    command: Command,
+    title: SharedString,
+    keystrokes: Vec<Keystroke>,
+    recent: bool,
+}
+
+struct CommandListDelegate {
+    items: Vec<CommandItem>,
+    /// The index of the matched items and the positions of the matched chars in the title.
+    matches: Vec<(usize, Vec<usize>)>,
+    selected_index: Option<usize>,
+    /// The focused element before the command palette is opened, the action is dispatched to it.
+    action_focus_handle: Option<FocusHandle>,
+}
+
+impl ListDelegate for CommandListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _: &App) -> usize {
+        self.matches.len()
+    }
+
+    fn perform_search(
+        &mut self,
+        query: &str,
+        _: &mut Window,
+        _: &mut Context<List<Self>>,
+    ) -> Task<()> {
+        self.matches = fuzzy::match_items(query, &self.items, |item| item.title.clone())
+            .into_iter()
+            .map(|(ix, m)| (ix, m.positions))
+            .collect();
+
+        Task::ready(())
+    }
+
+    fn render_item(
+        &self,
+        ix: usize,
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) -> Option<Self::Item> {
+        let (item_ix, positions) = self.matches.get(ix)?;
+        let item = self.items.get(*item_ix)?;
+
+        Some(
+            ListItem::new(ix)
+                .selected(Some(ix) == self.selected_index)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .justify_between()
+                        .child(
+                            Label::new(item.title.clone())
+                                .highlights(positions.clone())
+                                .whitespace_nowrap(),
+                        )
+                        .child(
+                            h_flex()
+                                .flex_none()
+                                .gap_2()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .when(item.recent, |this| {
+                                    this.child(t!("CommandPalette.recently_used").to_string())
+                                })
+                                .children(
+                                    item.keystrokes.iter().map(|key| key_shortcut(key.clone())),
+                                ),
+                        ),
+                ),
+        )
+    }
+
+    fn render_empty(&self, _: &mut Window, cx: &mut Context<List<Self>>) -> impl IntoElement {
+        h_flex()
+            .justify_center()
+            .py_6()
+            .text_color(cx.theme().muted_foreground)
+            .child(t!("CommandPalette.no_results").to_string())
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: Option<usize>,
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn confirm(&mut self, ix: usize, window: &mut Window, cx: &mut Context<List<Self>>) {
+        let Some(item) = self
+            .matches
+            .get(ix)
+            .and_then(|(item_ix, _)| self.items.get(*item_ix))
+        else {
+            return;
+        };
+
+        let action = item.command.action.boxed_clone();
+        CommandRegistry::global_mut(cx).push_recent(item.title.clone());
+
+        window.close_modal(cx);
+        // Focus back to the element that the action is listened on.
+        if let Some(handle) = self.action_focus_handle.as_ref() {
+            window.focus(handle);
+        }
+        window.dispatch_action(action, cx);
+    }
+
+    fn cancel(&mut self, window: &mut Window, cx: &mut Context<List<Self>>) {
+        window.close_modal(cx);
+    }
+}
+
+/// A command palette lists the registered commands and the available actions of the focused element,
+/// with the key bindings, filtered by the query.
+///
+/// Only the actions handled in the focus path (the element focused before opening and its ancestors)
+/// are listed, the actions of the other elements are not. The registered commands are always listed.
+///
+/// Press `cmd-shift-p` (`ctrl-shift-p` on Windows and Linux) to open it,
+/// the [`crate::Root`] is required to be the root view of the window.
+pub struct CommandPalette {
+    list: Entity<List<CommandListDelegate>>,
+}
+
+impl CommandPalette {
+    /// Open the command palette as a Modal for the focused element.
+    pub fn open(window: &mut Window, cx: &mut App) {
+        let action_focus_handle = window.focused(cx);
+        let items = Self::collect_items(window, cx);
+        let palette = cx.new(|cx| Self::new(items, action_focus_handle, window, cx));
+
+        window.open_modal(cx, {
+            let palette = palette.clone();
+            move |modal, _, _| {
+                modal
+                    .show_close(false)
+                    .p_0()
+                    .width(px(560.))
+                    .child(palette.clone())
+            }
+        });
+        palette.focus_handle(cx).focus(window);
+    }
+
+    fn new(
+        items: Vec<CommandItem>,
+        action_focus_handle: Option<FocusHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = CommandListDelegate {
+            matches: (0..items.len()).map(|ix| (ix, vec![])).collect(),
+            items,
+            selected_index: None,
+            action_focus_handle,
+        };
+        let list = cx.new(|cx| {
+            let mut list = List::new(delegate, window, cx).max_h(px(360.));
+            if list.delegate().items_count(cx) > 0 {
+                list.set_selected_index(Some(0), window, cx);
+            }
+            list
+        });
+
+        Self { list }
+    }
+
+    /// Returns the registered commands and the available actions of the focused element,
+    /// the recently used commands first, then the others sorted by the title.
+    fn collect_items(window: &mut Window, cx: &mut App) -> Vec<CommandItem> {
+        let registry = CommandRegistry::global(cx);
+        let mut commands = registry.commands.clone();
+        let recents = registry.recents.clone();
+
+        for action in window.available_actions(cx) {
+            if action.partial_eq(&ToggleCommandPalette)
+                || commands
+                    .iter()
+                    .any(|command| command.action.partial_eq(action.as_ref()))
+            {
+                continue;
+            }
+
+            commands.push(Command::from_action(action));
+        }
+
+        let mut items = commands
+            .into_iter()
+            .map(|command| {
+                let title = command.title();
+                let keystrokes = window
+                    .bindings_for_action(command.action.as_ref())
+                    .first()
+                    .map(|binding| binding.keystrokes().to_vec())
+                    .unwrap_or_default();
+
+                CommandItem {
+                    recent: recents.contains(&title),
+                    command,
+                    title,
+                    keystrokes,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        items.sort_by_cached_key(|item| {
+            let recent_ix = recents.iter().position(|recent| recent == &item.title);
+            (recent_ix.unwrap_or(usize::MAX), item.title.clone())
+        });
+        items
+    }
+
+    fn on_action_toggle(
+        &mut self,
+        _: &ToggleCommandPalette,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.close_modal(cx);
+    }
+}
+
+impl Focusable for CommandPalette {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.list.focus_handle(cx)
+    }
+}
+
+impl Render for CommandPalette {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .overflow_hidden()
+            .rounded_lg()
+            .on_action(cx.listener(Self::on_action_toggle))
+            .child(div().w_full().child(self.list.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::humanize_action_name;
+
+    #[test]
+    fn test_humanize_action_name() {
+        let humanize = |name| {
+            let (category, label) = humanize_action_name(name);
+            (category.as_deref().map(String::from), label)
+        };
+
+        assert_eq!(
+            humanize("list::SelectNext"),
+            (Some("List".to_string()), "Select Next".to_string())
+        );
+        assert_eq!(
+            humanize("popup_menu::Dismiss"),
+            (Some("Popup Menu".to_string()), "Dismiss".to_string())
+        );
+        assert_eq!(
+            humanize("editor::display_map::FoldAt2Lines"),
+            (
+                Some("Editor Display Map".to_string()),
+                "Fold At2 Lines".to_string()
+            )
+        );
+        assert_eq!(humanize("Quit"), (None, "Quit".to_string()));
+    }
+}
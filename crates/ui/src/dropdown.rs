@@ -1,3 +1,5 @@
+use std::{cmp::Reverse, collections::HashMap, rc::Rc};
+
 use gpui::{
     actions, anchored, canvas, deferred, div, prelude::FluentBuilder, px, rems, AnyElement, App,
     AppContext, Bounds, ClickEvent, Context, DismissEvent, ElementId, Entity, EventEmitter,
@@ -7,9 +9,13 @@ use gpui::{
 use rust_i18n::t;
 
 use crate::{
+    button::{Button, ButtonVariants as _},
+    fuzzy::{FuzzyMatch, FuzzyMatcher},
     h_flex,
-    input::ClearButton,
+    input::{ClearButton, InputEvent, TextInput},
+    label::Label,
     list::{self, List, ListDelegate, ListItem},
+    tag::Tag,
     v_flex, ActiveTheme, Disableable, Icon, IconName, Sizable, Size, StyleSized, StyledExt,
 };
 
@@ -40,6 +46,29 @@ pub trait DropdownItem {
     type Value: Clone;
     fn title(&self) -> SharedString;
     fn value(&self) -> &Self::Value;
+
+    /// Returns the icon to display before the title, default is None.
+    fn icon(&self) -> Option<Icon> {
+        None
+    }
+
+    /// Returns the secondary text to display after the title, default is None.
+    fn description(&self) -> Option<SharedString> {
+        None
+    }
+
+    /// Returns true if the item can not be selected, default is false.
+    fn disabled(&self) -> bool {
+        false
+    }
+
+    /// Returns the group of the item, each group is a section of the menu list with a header,
+    /// when there are more than one group.
+    ///
+    /// The items of the same group should be adjacent in the delegate.
+    fn group(&self) -> Option<SharedString> {
+        None
+    }
 }
 
 impl DropdownItem for String {
@@ -77,6 +106,13 @@ pub trait DropdownDelegate: Sized {
 
     fn get(&self, ix: usize) -> Option<&Self::Item>;
 
+    /// Returns all the items, including the items not matched the search.
+    ///
+    /// Default is the items of `get`, the delegate that filters the items should override it.
+    fn all_items(&self) -> Vec<&Self::Item> {
+        (0..self.len()).filter_map(|ix| self.get(ix)).collect()
+    }
+
     fn position<V>(&self, value: &V) -> Option<usize>
     where
         Self::Item: DropdownItem<Value = V>,
@@ -89,6 +125,12 @@ pub trait DropdownDelegate: Sized {
         false
     }
 
+    /// Returns the byte offsets of the chars in the title of the item at the index to highlight,
+    /// e.g.: the positions matched the search.
+    fn highlights(&self, _ix: usize) -> &[usize] {
+        &[]
+    }
+
     fn perform_search(
         &mut self,
         _query: &str,
@@ -119,10 +161,59 @@ impl<T: DropdownItem> DropdownDelegate for Vec<T> {
     }
 }
 
+/// Returns the query to search the items of a combobox.
+///
+/// The combobox input is also the query input of the list, the confirmed text (`combobox_text`)
+/// is not a query, so all the items are listed when the menu is reopened after confirming.
+fn combobox_query<'a>(query: &'a str, combobox_text: &str) -> &'a str {
+    if query == combobox_text.trim() {
+        ""
+    } else {
+        query
+    }
+}
+
 struct DropdownListDelegate<D: DropdownDelegate + 'static> {
     delegate: D,
     dropdown: WeakEntity<Dropdown<D>>,
     selected_index: Option<usize>,
+    /// The groups of the adjacent items and the number of their items, each group is a section
+    /// of the list, updated when the items are changed.
+    sections: Vec<(Option<SharedString>, usize)>,
+}
+
+impl<D> DropdownListDelegate<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    /// Update the sections of the menu by the groups of the items.
+    ///
+    /// This must be called after the items of the delegate are changed.
+    fn update_sections(&mut self) {
+        self.sections.clear();
+        for ix in 0..self.delegate.len() {
+            let group = self.delegate.get(ix).and_then(|item| item.group());
+            match self.sections.last_mut() {
+                Some((last_group, count)) if *last_group == group => *count += 1,
+                _ => self.sections.push((group, 1)),
+            }
+        }
+    }
+
+    fn render_group_header(&self, group: SharedString, size: Size, cx: &App) -> ListItem {
+        ListItem::new("list-group")
+            .disabled(true)
+            .input_text_size(size)
+            .list_size(size)
+            .bg(cx.theme().background)
+            .child(
+                div()
+                    .whitespace_nowrap()
+                    .font_semibold()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(group),
+            )
+    }
 }
 
 impl<D> ListDelegate for DropdownListDelegate<D>
@@ -135,6 +226,32 @@ where
         self.delegate.len()
     }
 
+    fn sections_count(&self, _: &App) -> usize {
+        self.sections.len()
+    }
+
+    fn section_items_count(&self, section: usize, _: &App) -> usize {
+        self.sections.get(section).map_or(0, |(_, count)| *count)
+    }
+
+    fn render_section_header(
+        &self,
+        section: usize,
+        _: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) -> Option<AnyElement> {
+        // The items without group are listed under an empty header among the groups.
+        let (group, _) = self.sections.get(section)?;
+        let size = self
+            .dropdown
+            .upgrade()
+            .map_or(Size::Medium, |dropdown| dropdown.read(cx).size);
+        Some(
+            self.render_group_header(group.clone().unwrap_or_default(), size, cx)
+                .into_any_element(),
+        )
+    }
+
     fn render_item(
         &self,
         ix: usize,
@@ -144,23 +261,59 @@ where
         let selected = self
             .selected_index
             .map_or(false, |selected_index| selected_index == ix);
-        let size = self
-            .dropdown
-            .upgrade()
+        let dropdown = self.dropdown.upgrade();
+        let size = dropdown
+            .as_ref()
             .map_or(Size::Medium, |dropdown| dropdown.read(cx).size);
 
-        if let Some(item) = self.delegate.get(ix) {
-            let list_item = ListItem::new(("list-item", ix))
-                .check_icon(IconName::Check)
-                .cursor_pointer()
-                .selected(selected)
-                .input_text_size(size)
-                .list_size(size)
-                .child(div().whitespace_nowrap().child(item.title().to_string()));
-            Some(list_item)
-        } else {
-            None
-        }
+        let item = self.delegate.get(ix)?;
+        let disabled = item.disabled();
+        let checked = dropdown.map_or(false, |dropdown| {
+            dropdown.read(cx).is_selected_value(item.value())
+        });
+        let list_item = ListItem::new(("list-item", ix))
+            .check_icon(IconName::Check)
+            .when(!disabled, |this| this.cursor_pointer())
+            .selected(selected)
+            .confirmed(checked)
+            .disabled(disabled)
+            .input_text_size(size)
+            .list_size(size)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .overflow_hidden()
+                    .when(disabled, |this| {
+                        this.text_color(cx.theme().muted_foreground)
+                    })
+                    .when_some(item.icon(), |this, icon| {
+                        this.child(icon.small().text_color(cx.theme().muted_foreground))
+                    })
+                    .child(
+                        Label::new(item.title())
+                            .highlights(self.delegate.highlights(ix))
+                            .whitespace_nowrap()
+                            .when(disabled, |this| {
+                                this.text_color(cx.theme().muted_foreground)
+                            }),
+                    )
+                    .when_some(item.description(), |this, description| {
+                        this.child(
+                            div()
+                                .flex_1()
+                                .overflow_hidden()
+                                .whitespace_nowrap()
+                                .text_ellipsis()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(description),
+                        )
+                    }),
+            );
+        Some(list_item)
+    }
+
+    fn can_select(&self, ix: usize, _: &App) -> bool {
+        self.delegate.get(ix).map_or(false, |item| !item.disabled())
     }
 
     fn cancel(&mut self, window: &mut Window, cx: &mut Context<List<Self>>) {
@@ -174,16 +327,32 @@ where
     }
 
     fn confirm(&mut self, ix: usize, window: &mut Window, cx: &mut Context<List<Self>>) {
+        // The disabled items can not be confirmed.
+        if !self.can_select(ix, cx) {
+            return;
+        }
         self.selected_index = Some(ix);
 
-        let selected_value = self
-            .selected_index
-            .and_then(|ix| self.delegate.get(ix))
-            .map(|item| item.value().clone());
+        let selected_item = self
+            .delegate
+            .get(ix)
+            .map(|item| (item.title(), item.value().clone()));
         let dropdown = self.dropdown.clone();
 
         cx.defer_in(window, move |_, window, cx| {
             _ = dropdown.update(cx, |this, cx| {
+                // Toggle the item and keep the menu open in the multiple mode.
+                if this.is_multiple() {
+                    if let Some((title, value)) = selected_item {
+                        this.toggle_selected_value(title, value, cx);
+                    }
+                    return;
+                }
+
+                let selected_value = selected_item.map(|(title, value)| {
+                    this.set_combobox_text(title, window, cx);
+                    value
+                });
                 cx.emit(DropdownEvent::Confirm(selected_value.clone()));
                 this.selected_value = selected_value;
                 this.open = false;
@@ -198,9 +367,14 @@ where
         window: &mut Window,
         cx: &mut Context<List<Self>>,
     ) -> Task<()> {
-        self.dropdown.upgrade().map_or(Task::ready(()), |dropdown| {
-            dropdown.update(cx, |_, cx| self.delegate.perform_search(query, window, cx))
-        })
+        let task = self.dropdown.upgrade().map_or(Task::ready(()), |dropdown| {
+            dropdown.update(cx, |dropdown, cx| {
+                let query = combobox_query(query, &dropdown.combobox_text);
+                self.delegate.perform_search(query, window, cx)
+            })
+        });
+        self.update_sections();
+        task
     }
 
     fn set_selected_index(
@@ -232,6 +406,10 @@ where
 
 pub enum DropdownEvent<D: DropdownDelegate + 'static> {
     Confirm(Option<<D::Item as DropdownItem>::Value>),
+    /// The selected values are changed in the multiple mode.
+    ConfirmMultiple(Vec<<D::Item as DropdownItem>::Value>),
+    /// The text not matched any item is confirmed in the combobox mode.
+    ConfirmText(SharedString),
 }
 
 /// A Dropdown element.
@@ -246,6 +424,17 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
     placeholder: Option<SharedString>,
     title_prefix: Option<SharedString>,
     selected_value: Option<<D::Item as DropdownItem>::Value>,
+    /// The function to compare the values, only set in the multiple mode.
+    multiple:
+        Option<fn(&<D::Item as DropdownItem>::Value, &<D::Item as DropdownItem>::Value) -> bool>,
+    /// The title and value of the selected items in the multiple mode.
+    selected_values: Vec<(SharedString, <D::Item as DropdownItem>::Value)>,
+    max_visible_tags: usize,
+    /// The input of the combobox mode.
+    combobox: Option<Entity<TextInput>>,
+    /// The text set to the combobox input by the dropdown, to ignore its change event.
+    combobox_text: SharedString,
+    on_create: Option<Rc<dyn Fn(&str, &mut D, &mut Window, &mut App)>>,
     empty: Option<Box<dyn Fn(&Window, &App) -> AnyElement + 'static>>,
     width: Length,
     menu_width: Length,
@@ -254,19 +443,31 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
     disabled: bool,
 }
 
+/// A [`DropdownDelegate`] of the items that can be searched by the fuzzy matcher,
+/// the matched items are ranked by the score in each group.
 pub struct SearchableVec<T> {
     items: Vec<T>,
     matched_items: Vec<T>,
+    /// The byte offsets of the matched chars in the title of the matched items.
+    highlights: Vec<Vec<usize>>,
 }
 
 impl<T: DropdownItem + Clone> SearchableVec<T> {
     pub fn new(items: impl Into<Vec<T>>) -> Self {
         let items = items.into();
         Self {
+            highlights: vec![vec![]; items.len()],
             items: items.clone(),
             matched_items: items,
         }
     }
+
+    /// Append an item, it is also appended to the matched items of the current search.
+    pub fn push(&mut self, item: T) {
+        self.items.push(item.clone());
+        self.matched_items.push(item);
+        self.highlights.push(vec![]);
+    }
 }
 
 impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
@@ -280,6 +481,10 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
         self.matched_items.get(ix)
     }
 
+    fn all_items(&self) -> Vec<&Self::Item> {
+        self.items.iter().collect()
+    }
+
     fn position<V>(&self, value: &V) -> Option<usize>
     where
         Self::Item: DropdownItem<Value = V>,
@@ -298,17 +503,65 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
         true
     }
 
+    fn highlights(&self, ix: usize) -> &[usize] {
+        self.highlights.get(ix).map_or(&[], |positions| positions)
+    }
+
     fn perform_search(
         &mut self,
         query: &str,
         _window: &mut Window,
         _: &mut Context<Dropdown<Self>>,
     ) -> Task<()> {
-        self.matched_items = self
+        // Search the title and the description of the items in all the groups,
+        // the items only matched by the description are ranked after the others.
+        let matcher = FuzzyMatcher::new(query);
+        // The index of the first item of each group.
+        let mut group_ixs = HashMap::new();
+        for (ix, item) in self.items.iter().enumerate() {
+            group_ixs.entry(item.group()).or_insert(ix);
+        }
+
+        let mut matches = self
             .items
             .iter()
-            .filter(|item| item.title().to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
+            .enumerate()
+            .filter_map(|(ix, item)| match matcher.match_text(&item.title()) {
+                Some(m) => Some((ix, true, m)),
+                None => item
+                    .description()
+                    .and_then(|description| matcher.match_text(&description))
+                    .map(|m| {
+                        (
+                            ix,
+                            false,
+                            FuzzyMatch {
+                                positions: vec![],
+                                ..m
+                            },
+                        )
+                    }),
+            })
+            .map(|(ix, title_matched, m)| {
+                // Keep the items of the same group adjacent, in the order of the groups.
+                let group_ix = group_ixs
+                    .get(&self.items[ix].group())
+                    .copied()
+                    .unwrap_or(ix);
+                (group_ix, !title_matched, Reverse(m.score), ix, m.positions)
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(group_ix, not_title_matched, score, _, _)| {
+            (*group_ix, *not_title_matched, *score)
+        });
+
+        self.matched_items = matches
+            .iter()
+            .map(|(_, _, _, ix, _)| self.items[*ix].clone())
+            .collect();
+        self.highlights = matches
+            .into_iter()
+            .map(|(_, _, _, _, positions)| positions)
             .collect();
 
         Task::ready(())
@@ -317,10 +570,7 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
 
 impl From<Vec<SharedString>> for SearchableVec<SharedString> {
     fn from(items: Vec<SharedString>) -> Self {
-        Self {
-            items: items.clone(),
-            matched_items: items,
-        }
+        Self::new(items)
     }
 }
 
@@ -336,11 +586,13 @@ where
         cx: &mut Context<Self>,
     ) -> Self {
         let focus_handle = cx.focus_handle();
-        let delegate = DropdownListDelegate {
+        let mut delegate = DropdownListDelegate {
             delegate,
             dropdown: cx.model().downgrade(),
-            selected_index,
+            selected_index: None,
+            sections: vec![],
         };
+        delegate.update_sections();
 
         let searchable = delegate.delegate.can_search();
 
@@ -364,6 +616,12 @@ where
             size: Size::Medium,
             icon: None,
             selected_value: None,
+            multiple: None,
+            selected_values: vec![],
+            max_visible_tags: 3,
+            combobox: None,
+            combobox_text: SharedString::default(),
+            on_create: None,
             open: false,
             cleanable: false,
             title_prefix: None,
@@ -411,6 +669,64 @@ where
         self
     }
 
+    /// Set the dropdown to select multiple values.
+    ///
+    /// The menu is kept open when toggling the items, and the [`DropdownEvent::ConfirmMultiple`] is emitted.
+    pub fn multiple(mut self) -> Self
+    where
+        <D::Item as DropdownItem>::Value: PartialEq,
+    {
+        self.multiple = Some(|a, b| a == b);
+        self
+    }
+
+    /// Set the max number of the selected items to display as tags in the multiple mode,
+    /// display the count of the selected items if more than it, default: 3
+    pub fn max_visible_tags(mut self, max: usize) -> Self {
+        self.max_visible_tags = max;
+        self
+    }
+
+    /// Set the dropdown to the combobox mode, the trigger becomes an input to filter the items.
+    ///
+    /// Pressing Enter confirms the highlighted item, or the item that the title is the text,
+    /// otherwise the text is confirmed as [`DropdownEvent::ConfirmText`].
+    ///
+    /// The placeholder should be set before this.
+    pub fn combobox(mut self, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| {
+            let mut input = TextInput::new(window, cx).appearance(false);
+            if let Some(placeholder) = self.placeholder.clone() {
+                input.set_placeholder(placeholder);
+            }
+            input
+        });
+
+        self.list.update(cx, |list, cx| {
+            list.set_query_input(input.clone(), window, cx);
+            list.set_query_input_visible(false, cx);
+        });
+        cx.subscribe_in(&input, window, Self::on_combobox_input_event)
+            .detach();
+        cx.on_blur(&input.focus_handle(cx), window, Self::on_blur)
+            .detach();
+
+        self.combobox = Some(input);
+        self
+    }
+
+    /// Set the callback to create an item with the text in the combobox mode,
+    /// a "Create" item is shown in the menu when the text does not match any item.
+    ///
+    /// The callback should append the new item to the delegate, and then the item will be confirmed.
+    pub fn on_create(
+        mut self,
+        on_create: impl Fn(&str, &mut D, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_create = Some(Rc::new(on_create));
+        self
+    }
+
     /// Set true to show the clear button when the input field is not empty.
     pub fn cleanable(mut self) -> Self {
         self.cleanable = true;
@@ -476,8 +792,209 @@ where
         self.selected_value.as_ref()
     }
 
-    pub fn focus(&self, window: &mut Window, _: &mut App) {
-        self.focus_handle.focus(window);
+    /// Returns true if the dropdown is in the multiple mode.
+    pub fn is_multiple(&self) -> bool {
+        self.multiple.is_some()
+    }
+
+    /// Returns the selected values in the multiple mode.
+    pub fn selected_values(&self) -> Vec<<D::Item as DropdownItem>::Value> {
+        self.selected_values
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    /// Set the selected values in the multiple mode, the values not in the items are ignored.
+    ///
+    /// The values are looked up in all the items, regardless of the search.
+    pub fn set_selected_values(
+        &mut self,
+        values: &[<D::Item as DropdownItem>::Value],
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(eq) = self.multiple else {
+            return;
+        };
+
+        let delegate = &self.list.read(cx).delegate().delegate;
+        self.selected_values = delegate
+            .all_items()
+            .into_iter()
+            .filter(|item| values.iter().any(|value| eq(item.value(), value)))
+            .map(|item| (item.title(), item.value().clone()))
+            .collect();
+        cx.notify();
+    }
+
+    /// Select all the items (matched the search) in the multiple mode, the disabled items are skipped.
+    pub fn select_all(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_multiple() {
+            return;
+        }
+
+        let delegate = &self.list.read(cx).delegate().delegate;
+        let items = (0..delegate.len())
+            .filter_map(|ix| delegate.get(ix))
+            .filter(|item| !item.disabled())
+            .map(|item| (item.title(), item.value().clone()))
+            .collect::<Vec<_>>();
+        for (title, value) in items {
+            if !self.is_selected_value(&value) {
+                self.selected_values.push((title, value));
+            }
+        }
+        self.emit_selected_values(cx);
+    }
+
+    /// Deselect all the items in the multiple mode.
+    pub fn deselect_all(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_multiple() {
+            return;
+        }
+
+        self.selected_values.clear();
+        self.emit_selected_values(cx);
+    }
+
+    fn is_selected_value(&self, value: &<D::Item as DropdownItem>::Value) -> bool {
+        self.multiple.map_or(false, |eq| {
+            self.selected_values.iter().any(|(_, v)| eq(v, value))
+        })
+    }
+
+    fn toggle_selected_value(
+        &mut self,
+        title: SharedString,
+        value: <D::Item as DropdownItem>::Value,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(eq) = self.multiple else {
+            return;
+        };
+
+        if let Some(ix) = self.selected_values.iter().position(|(_, v)| eq(v, &value)) {
+            self.selected_values.remove(ix);
+        } else {
+            self.selected_values.push((title, value));
+        }
+        self.emit_selected_values(cx);
+    }
+
+    fn emit_selected_values(&mut self, cx: &mut Context<Self>) {
+        cx.emit(DropdownEvent::ConfirmMultiple(self.selected_values()));
+        cx.notify();
+    }
+
+    pub fn focus(&self, window: &mut Window, cx: &mut App) {
+        if let Some(input) = self.combobox.as_ref() {
+            input.focus_handle(cx).focus(window);
+        } else {
+            self.focus_handle.focus(window);
+        }
+    }
+
+    fn set_combobox_text(
+        &mut self,
+        text: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(input) = self.combobox.clone() else {
+            return;
+        };
+
+        self.combobox_text = text.clone();
+        input.update(cx, |input, cx| {
+            input.set_text(text, window, cx);
+            input.move_to(input.text().len(), window, cx);
+        });
+    }
+
+    /// Returns the index of the enabled item that the title is the `text`.
+    fn position_by_title(&self, text: &str, cx: &App) -> Option<usize> {
+        let delegate = &self.list.read(cx).delegate().delegate;
+        (0..delegate.len()).find(|ix| {
+            delegate.get(*ix).map_or(false, |item| {
+                !item.disabled() && item.title().as_ref() == text
+            })
+        })
+    }
+
+    fn on_combobox_input_event(
+        &mut self,
+        _: &Entity<TextInput>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                if *text == self.combobox_text {
+                    return;
+                }
+
+                // The list performs the search, and the highlighted item is reset for typing.
+                self.open = true;
+                self.list
+                    .update(cx, |list, cx| list.set_selected_index(None, window, cx));
+                cx.notify();
+            }
+            InputEvent::PressEnter => {
+                // The highlighted item is confirmed by the list.
+                if self.list.read(cx).selected_index().is_some() {
+                    return;
+                }
+
+                let text: SharedString = self.combobox_input_text(cx).trim().to_string().into();
+                if text.is_empty() {
+                    return;
+                }
+
+                if self.on_create.is_some() && self.position_by_title(&text, cx).is_none() {
+                    self.create_item(text, window, cx);
+                } else {
+                    self.confirm_text(text, window, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn combobox_input_text(&self, cx: &App) -> SharedString {
+        self.combobox
+            .as_ref()
+            .map(|input| input.read(cx).text())
+            .unwrap_or_default()
+    }
+
+    /// Confirm the item that the title is the `text`, or the `text` if no item matched.
+    fn confirm_text(&mut self, text: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.position_by_title(&text, cx);
+        self.set_selected_index(ix, window, cx);
+        self.set_combobox_text(text.clone(), window, cx);
+        if ix.is_some() {
+            cx.emit(DropdownEvent::Confirm(self.selected_value.clone()));
+        } else {
+            cx.emit(DropdownEvent::ConfirmText(text));
+        }
+
+        self.open = false;
+        cx.notify();
+    }
+
+    fn create_item(&mut self, text: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(on_create) = self.on_create.clone() else {
+            return;
+        };
+
+        self.list.update(cx, |list, cx| {
+            let delegate = list.delegate_mut();
+            on_create(&text, &mut delegate.delegate, window, cx);
+            delegate.update_sections();
+        });
+        self.confirm_text(text, window, cx);
     }
 
     fn on_blur(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -494,6 +1011,12 @@ where
         if !self.open {
             return;
         }
+        // Keep the focus in the input of the combobox.
+        if self.combobox.is_some() {
+            self.list
+                .update(cx, |list, cx| list.select_prev(window, cx));
+            return;
+        }
         self.list.focus_handle(cx).focus(window);
         cx.dispatch_action(&list::SelectPrev);
     }
@@ -503,6 +1026,12 @@ where
             self.open = true;
         }
 
+        if self.combobox.is_some() {
+            self.list
+                .update(cx, |list, cx| list.select_next(window, cx));
+            cx.notify();
+            return;
+        }
         self.list.focus_handle(cx).focus(window);
         cx.dispatch_action(&list::SelectNext);
     }
@@ -539,23 +1068,54 @@ where
     }
 
     fn clean(&mut self, _: &ClickEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_multiple() {
+            self.deselect_all(window, cx);
+            return;
+        }
+
         self.set_selected_index(None, window, cx);
+        self.set_combobox_text(SharedString::default(), window, cx);
         cx.emit(DropdownEvent::Confirm(None));
     }
 
     fn display_title(&self, _: &Window, cx: &App) -> impl IntoElement {
-        let title = if let Some(selected_index) = &self.selected_index(cx) {
-            let title = self
-                .list
-                .read(cx)
-                .delegate()
-                .delegate
-                .get(*selected_index)
+        // The selected index is only the highlighted item in the multiple mode.
+        let selected_index = if self.is_multiple() {
+            None
+        } else {
+            self.selected_index(cx)
+        };
+
+        let title = if !self.selected_values.is_empty() {
+            let count = self.selected_values.len();
+
+            h_flex()
+                .gap_1()
+                .when_some(self.title_prefix.clone(), |this, prefix| this.child(prefix))
+                .map(|this| {
+                    if count > self.max_visible_tags {
+                        this.child(t!("Dropdown.selected", count = count).to_string())
+                    } else {
+                        this.children(
+                            self.selected_values
+                                .iter()
+                                .map(|(title, _)| Tag::secondary().small().child(title.clone())),
+                        )
+                    }
+                })
+        } else if let Some(selected_index) = &selected_index {
+            let item = self.list.read(cx).delegate().delegate.get(*selected_index);
+            let title = item
                 .map(|item| item.title().to_string())
                 .unwrap_or_default();
+            let icon = item.and_then(|item| item.icon());
 
             h_flex()
                 .when_some(self.title_prefix.clone(), |this, prefix| this.child(prefix))
+                .when_some(icon, |this, icon| {
+                    this.gap_2()
+                        .child(icon.small().text_color(cx.theme().muted_foreground))
+                })
                 .child(title.clone())
         } else {
             div().text_color(cx.theme().accent_foreground).child(
@@ -589,7 +1149,9 @@ where
     D: DropdownDelegate,
 {
     fn focus_handle(&self, cx: &App) -> FocusHandle {
-        if self.open {
+        if let Some(input) = self.combobox.as_ref() {
+            input.focus_handle(cx)
+        } else if self.open {
             self.list.focus_handle(cx)
         } else {
             self.focus_handle.clone()
@@ -602,8 +1164,23 @@ where
     D: DropdownDelegate + 'static,
 {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let is_focused = self.focus_handle.is_focused(window);
-        let show_clean = self.cleanable && self.selected_index(cx).is_some();
+        let is_focused = self.focus_handle.is_focused(window)
+            || self
+                .combobox
+                .as_ref()
+                .map_or(false, |input| input.focus_handle(cx).is_focused(window));
+        let create_text = self
+            .combobox
+            .as_ref()
+            .filter(|_| self.on_create.is_some())
+            .map(|input| input.read(cx).text().trim().to_string())
+            .filter(|text| !text.is_empty() && self.position_by_title(text, cx).is_none());
+        let show_clean = self.cleanable
+            && if self.is_multiple() {
+                !self.selected_values.is_empty()
+            } else {
+                self.selected_index(cx).is_some()
+            };
         let view = cx.model().clone();
         let bounds = self.bounds;
         let allow_open = !(self.open || self.disabled);
@@ -652,7 +1229,14 @@ where
                         Length::Auto => this.w_full(),
                     })
                     .when(outline_visible, |this| this.outline(cx))
-                    .input_size(self.size)
+                    .map(|this| {
+                        // The input of the combobox has its own padding.
+                        if self.combobox.is_some() {
+                            this.input_h(self.size).input_pr(self.size)
+                        } else {
+                            this.input_size(self.size)
+                        }
+                    })
                     .when(allow_open, |this| {
                         this.on_click(cx.listener(Self::toggle_menu))
                     })
@@ -662,12 +1246,12 @@ where
                             .items_center()
                             .justify_between()
                             .gap_1()
-                            .child(
-                                div()
-                                    .w_full()
-                                    .overflow_hidden()
-                                    .child(self.display_title(window, cx)),
-                            )
+                            .child(div().w_full().overflow_hidden().map(|this| {
+                                match self.combobox.clone() {
+                                    Some(input) => this.child(input),
+                                    None => this.child(self.display_title(window, cx)),
+                                }
+                            }))
                             .when(show_clean, |this| {
                                 this.child(ClearButton::new(window, cx).map(|this| {
                                     if self.disabled {
@@ -731,7 +1315,76 @@ where
                                         .on_mouse_down_out(|_, _, cx| {
                                             cx.dispatch_action(&Escape);
                                         })
-                                        .child(self.list.clone()),
+                                        .child(self.list.clone())
+                                        .when_some(create_text, |this, text| {
+                                            this.child(
+                                                div()
+                                                    .p_1()
+                                                    .border_t_1()
+                                                    .border_color(cx.theme().border)
+                                                    .child(
+                                                        ListItem::new("create-item")
+                                                            .rounded_md()
+                                                            .input_text_size(self.size)
+                                                            .list_size(self.size)
+                                                            .child(
+                                                                h_flex()
+                                                                    .gap_2()
+                                                                    .child(
+                                                                        Icon::new(IconName::Plus)
+                                                                            .xsmall(),
+                                                                    )
+                                                                    .child(
+                                                                        t!(
+                                                                            "Dropdown.create",
+                                                                            text = text
+                                                                        )
+                                                                        .to_string(),
+                                                                    ),
+                                                            )
+                                                            .on_click(cx.listener(
+                                                                move |this, _, window, cx| {
+                                                                    this.create_item(
+                                                                        text.clone().into(),
+                                                                        window,
+                                                                        cx,
+                                                                    );
+                                                                },
+                                                            )),
+                                                    ),
+                                            )
+                                        })
+                                        .when(self.is_multiple(), |this| {
+                                            this.child(
+                                                h_flex()
+                                                    .justify_between()
+                                                    .p_1()
+                                                    .border_t_1()
+                                                    .border_color(cx.theme().border)
+                                                    .child(
+                                                        Button::new("select-all")
+                                                            .ghost()
+                                                            .xsmall()
+                                                            .label(t!("Dropdown.select_all"))
+                                                            .on_click(cx.listener(
+                                                                |this, _, window, cx| {
+                                                                    this.select_all(window, cx)
+                                                                },
+                                                            )),
+                                                    )
+                                                    .child(
+                                                        Button::new("deselect-all")
+                                                            .ghost()
+                                                            .xsmall()
+                                                            .label(t!("Dropdown.deselect_all"))
+                                                            .on_click(cx.listener(
+                                                                |this, _, window, cx| {
+                                                                    this.deselect_all(window, cx)
+                                                                },
+                                                            )),
+                                                    ),
+                                            )
+                                        }),
                                 )
                                 .on_mouse_down_out(cx.listener(|this, _, window, cx| {
                                     this.escape(&Escape, window, cx);
@@ -743,3 +1396,18 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::combobox_query;
+
+    #[test]
+    fn test_combobox_query_after_confirm() {
+        // Reopen the menu after "Apple" is confirmed, all the items are listed.
+        assert_eq!(combobox_query("Apple", "Apple"), "");
+        // Typing in the combobox.
+        assert_eq!(combobox_query("App", "Apple"), "App");
+        assert_eq!(combobox_query("Ban", ""), "Ban");
+        assert_eq!(combobox_query("", ""), "");
+    }
+}
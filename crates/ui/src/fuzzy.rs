@@ -0,0 +1,206 @@
+use std::cmp::Reverse;
+
+use gpui::SharedString;
+
+const SCORE_MATCH: i32 = 16;
+/// The matched char is the start of a word, e.g.: `b` in `foo_bar`, `fooBar` or `foo bar`.
+const BONUS_BOUNDARY: i32 = 8;
+/// The matched char is right after the previous matched char.
+const BONUS_CONSECUTIVE: i32 = 6;
+/// The penalty of each char skipped between two matched chars.
+const PENALTY_GAP: i32 = 1;
+/// The penalty of each char before the first matched char, up to `MAX_LEADING_PENALTY`.
+const PENALTY_LEADING: i32 = 1;
+const MAX_LEADING_PENALTY: i32 = 3;
+
+/// A fuzzy match of the query in a text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The score of the match, higher is better.
+    pub score: i32,
+    /// The byte offsets of the matched chars in the text, in ascending order.
+    pub positions: Vec<usize>,
+}
+
+/// A fuzzy matcher that matches the chars of the query in order, case insensitive.
+///
+/// The matches at the start of words and the consecutive matches get higher scores,
+/// e.g.: `fb` matches `foo_bar` at `f` and `b`.
+#[derive(Debug, Clone)]
+pub struct FuzzyMatcher {
+    query: Vec<char>,
+}
+
+impl FuzzyMatcher {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.trim().chars().map(lowercase).collect(),
+        }
+    }
+
+    /// Returns true if the query is empty, it matches any text.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// Match the `text`, returns None if not all the chars of the query are found in order.
+    pub fn match_text(&self, text: &str) -> Option<FuzzyMatch> {
+        if self.query.is_empty() {
+            return Some(FuzzyMatch::default());
+        }
+
+        let chars = text.char_indices().collect::<Vec<_>>();
+        let (m, n) = (self.query.len(), chars.len());
+        if m > n {
+            return None;
+        }
+
+        let bonus = (0..n)
+            .map(|j| match j {
+                0 => BONUS_BOUNDARY,
+                _ if is_boundary(chars[j - 1].1, chars[j].1) => BONUS_BOUNDARY,
+                _ => 0,
+            })
+            .collect::<Vec<_>>();
+        let is_match = |i: usize, j: usize| lowercase(chars[j].1) == self.query[i];
+
+        // The best score of matching the query[..=i] with the query[i] at the char `j`.
+        let mut scores = vec![vec![None; n]; m];
+        // The char matched the query[i - 1] for the best score.
+        let mut prev = vec![vec![0; n]; m];
+        for j in 0..n {
+            if is_match(0, j) {
+                let leading = (j as i32 * PENALTY_LEADING).min(MAX_LEADING_PENALTY);
+                scores[0][j] = Some(SCORE_MATCH + bonus[j] - leading);
+            }
+        }
+
+        for i in 1..m {
+            // The best of `scores[i - 1][k] - gap penalty` for the chars k < j - 1.
+            let mut best_gap: Option<(i32, usize)> = None;
+            for j in 1..n {
+                best_gap = best_gap.map(|(score, k)| (score - PENALTY_GAP, k));
+                if j >= 2 {
+                    if let Some(score) = scores[i - 1][j - 2] {
+                        let score = score - PENALTY_GAP;
+                        if !matches!(best_gap, Some((best, _)) if best >= score) {
+                            best_gap = Some((score, j - 2));
+                        }
+                    }
+                }
+
+                if !is_match(i, j) {
+                    continue;
+                }
+
+                let consecutive =
+                    scores[i - 1][j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+                let best = match (consecutive, best_gap) {
+                    (Some(a), Some(b)) => Some(if b.0 > a.0 { b } else { a }),
+                    (a, b) => a.or(b),
+                };
+                if let Some((score, k)) = best {
+                    scores[i][j] = Some(score + SCORE_MATCH + bonus[j]);
+                    prev[i][j] = k;
+                }
+            }
+        }
+
+        let (mut j, score) = scores[m - 1]
+            .iter()
+            .enumerate()
+            .filter_map(|(j, score)| score.map(|score| (j, score)))
+            .fold(None, |best: Option<(usize, i32)>, (j, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((j, score)),
+            })?;
+
+        let mut positions = vec![0; m];
+        for i in (0..m).rev() {
+            positions[i] = chars[j].0;
+            j = prev[i][j];
+        }
+
+        Some(FuzzyMatch { score, positions })
+    }
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Returns true if the char `c` is the start of a word after the char `prev`.
+fn is_boundary(prev: char, c: char) -> bool {
+    (!prev.is_alphanumeric() && c.is_alphanumeric())
+        || (prev.is_lowercase() && c.is_uppercase())
+        || (!prev.is_ascii_digit() && c.is_ascii_digit())
+}
+
+/// Match the `query` with the text of the items, returns the index and the match of the matched items,
+/// ranked by the score, the items with the same score keep the original order.
+///
+/// An empty query matches all the items.
+///
+/// This is designed to be called in the `perform_search` of the [`crate::list::ListDelegate`]:
+///
+/// ```ignore
+/// self.matches = fuzzy::match_items(query, &self.items, |item| item.name.clone());
+/// ```
+pub fn match_items<T>(
+    query: &str,
+    items: &[T],
+    text: impl Fn(&T) -> SharedString,
+) -> Vec<(usize, FuzzyMatch)> {
+    let matcher = FuzzyMatcher::new(query);
+    let mut matches = items
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, item)| matcher.match_text(&text(item)).map(|m| (ix, m)))
+        .collect::<Vec<_>>();
+    matches.sort_by_key(|(_, m)| Reverse(m.score));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(query: &str, text: &str) -> Option<Vec<usize>> {
+        FuzzyMatcher::new(query)
+            .match_text(text)
+            .map(|m| m.positions)
+    }
+
+    #[test]
+    fn test_match_text() {
+        assert_eq!(positions("", "abc"), Some(vec![]));
+        assert_eq!(positions("abc", "abc"), Some(vec![0, 1, 2]));
+        assert_eq!(positions("ABC", "xaxbxc"), Some(vec![1, 3, 5]));
+        assert_eq!(positions("fb", "foo_bar"), Some(vec![0, 4]));
+        assert_eq!(positions("fb", "fooBar"), Some(vec![0, 3]));
+        // Prefer the start of the words.
+        assert_eq!(positions("ds", "dropdown story"), Some(vec![0, 9]));
+        // Prefer the consecutive chars.
+        assert_eq!(positions("bar", "xbxaxr bar"), Some(vec![7, 8, 9]));
+        // The positions are byte offsets.
+        assert_eq!(positions("b", "über"), Some(vec![2]));
+        assert_eq!(positions("xyz", "abc"), None);
+        assert_eq!(positions("cba", "abc"), None);
+    }
+
+    #[test]
+    fn test_match_items() {
+        let items = ["odds", "Dropdown Story", "adds", "list"];
+        let matches = match_items("ds", &items, |item| SharedString::from(item.to_string()));
+        let ixs = matches.iter().map(|(ix, _)| *ix).collect::<Vec<_>>();
+        assert_eq!(ixs[0], 1);
+        assert_eq!(ixs.len(), 3);
+        assert!(!ixs.contains(&3));
+
+        let matches = match_items("", &items, |item| SharedString::from(item.to_string()));
+        assert_eq!(
+            matches.iter().map(|(ix, _)| *ix).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+    }
+}
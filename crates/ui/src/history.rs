@@ -19,6 +19,8 @@ pub struct History<I: HistoryItem> {
     redos: Vec<I>,
     last_changed_at: Instant,
     version: usize,
+    /// Force the next change to start a new version.
+    new_group: bool,
     pub(crate) ignore: bool,
     max_undo: usize,
     group_interval: Option<Duration>,
@@ -36,6 +38,7 @@ where
             ignore: false,
             last_changed_at: Instant::now(),
             version: 0,
+            new_group: false,
             max_undo: 1000,
             group_interval: None,
             unique: false,
@@ -64,14 +67,21 @@ where
     /// Increment the version number if the last change was made more than `GROUP_INTERVAL` milliseconds ago.
     fn inc_version(&mut self) -> usize {
         let t = Instant::now();
-        if Some(self.last_changed_at.elapsed()) > self.group_interval {
+        if self.new_group || Some(self.last_changed_at.elapsed()) > self.group_interval {
             self.version += 1;
+            self.new_group = false;
         }
 
         self.last_changed_at = t;
         self.version
     }
 
+    /// Start a new group, the next change will not be grouped with the previous changes,
+    /// even if it is made within the `group_interval`.
+    pub fn start_new_group(&mut self) {
+        self.new_group = true;
+    }
+
     /// Get the current version number.
     pub fn version(&self) -> usize {
         self.version
@@ -109,6 +119,7 @@ where
         self.redos.clear();
     }
 
+    /// Pop the last changes with the same version, the changes are returned in the reverse order they were pushed.
     pub fn undo(&mut self) -> Option<Vec<I>> {
         if let Some(first_change) = self.undos.pop() {
             let mut changes = vec![first_change.clone()];
@@ -124,13 +135,15 @@ where
                 changes.push(change);
             }
 
-            self.redos.extend(changes.iter().rev().cloned());
+            // Keep the first pushed change on the top of the redo stack.
+            self.redos.extend(changes.iter().cloned());
             Some(changes)
         } else {
             None
         }
     }
 
+    /// Pop the last undone changes with the same version, the changes are returned in the order they were pushed.
     pub fn redo(&mut self) -> Option<Vec<I>> {
         if let Some(first_change) = self.redos.pop() {
             let mut changes = vec![first_change.clone()];
@@ -145,7 +158,7 @@ where
                 let change = self.redos.pop().unwrap();
                 changes.push(change);
             }
-            self.undos.extend(changes.iter().rev().cloned());
+            self.undos.extend(changes.iter().cloned());
             Some(changes)
         } else {
             None
@@ -230,6 +243,43 @@ mod tests {
         assert_eq!(history.undo().is_none(), true);
     }
 
+    #[test]
+    fn test_grouped_history() {
+        let mut history: History<TabIndex> = History::new().group_interval(Duration::from_secs(60));
+        history.push(0.into());
+        history.push(1.into());
+        history.push(2.into());
+
+        let changes = history.undo().unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.tab_index).collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+        assert_eq!(history.undos().len(), 0);
+
+        let changes = history.redo().unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.tab_index).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        let changes = history.undo().unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.tab_index).collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+
+        history.push(3.into());
+        history.start_new_group();
+        history.push(4.into());
+        history.push(5.into());
+        let changes = history.undo().unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.tab_index).collect::<Vec<_>>(),
+            vec![5, 4]
+        );
+    }
+
     #[test]
     fn test_unique_history() {
         let mut history: History<TabIndex> = History::new().max_undo(100).unique();
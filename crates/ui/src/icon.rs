@@ -35,10 +35,12 @@ pub enum IconName {
     EllipsisVertical,
     Eye,
     EyeOff,
+    Filter,
     Frame,
     GalleryVerticalEnd,
     GitHub,
     Globe,
+    GripVertical,
     Heart,
     HeartOff,
     Inbox,
@@ -112,10 +114,12 @@ impl IconName {
             Self::EllipsisVertical => "icons/ellipsis-vertical.svg",
             Self::Eye => "icons/eye.svg",
             Self::EyeOff => "icons/eye-off.svg",
+            Self::Filter => "icons/filter.svg",
             Self::Frame => "icons/frame.svg",
             Self::GalleryVerticalEnd => "icons/gallery-vertical-end.svg",
             Self::GitHub => "icons/github.svg",
             Self::Globe => "icons/globe.svg",
+            Self::GripVertical => "icons/grip-vertical.svg",
             Self::Heart => "icons/heart.svg",
             Self::HeartOff => "icons/heart-off.svg",
             Self::Inbox => "icons/inbox.svg",
@@ -0,0 +1,262 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::prelude::FluentBuilder as _;
+use gpui::{
+    anchored, deferred, div, point, px, App, Context, InteractiveElement as _, IntoElement,
+    ParentElement as _, ScrollHandle, SharedString, StatefulInteractiveElement as _, Styled as _,
+    Task, ViewInputHandler as _, Window,
+};
+
+use crate::{h_flex, list::ListItem, v_flex, ActiveTheme as _, StyledExt as _};
+
+use super::{ShowCompletions, TextInput};
+
+/// An item of the completions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionItem {
+    pub label: SharedString,
+    pub insert_text: Option<SharedString>,
+    pub detail: Option<SharedString>,
+}
+
+impl CompletionItem {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            insert_text: None,
+            detail: None,
+        }
+    }
+
+    /// Set the text to insert when the item is accepted, default is the label.
+    pub fn insert_text(mut self, insert_text: impl Into<SharedString>) -> Self {
+        self.insert_text = Some(insert_text.into());
+        self
+    }
+
+    /// Set the detail text, it will be displayed at the right of the label.
+    pub fn detail(mut self, detail: impl Into<SharedString>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// A provider of the completions for the [`TextInput`].
+pub trait CompletionProvider: 'static {
+    /// Returns the range (UTF-8) of the text to be replaced by the completion at the cursor `offset`,
+    /// or `None` to not show the completions.
+    ///
+    /// The default is the word (alphanumeric or `_`) before the cursor.
+    fn trigger_range(&self, text: &str, offset: usize) -> Option<Range<usize>> {
+        let start = text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(ix, _)| ix)?;
+
+        Some(start..offset)
+    }
+
+    /// Query the completions for the `text` at the cursor `offset` (UTF-8).
+    fn completions(
+        &self,
+        text: &str,
+        offset: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CompletionItem>>>;
+}
+
+pub(super) struct CompletionMenu {
+    /// The range of the text to be replaced by the accepted item.
+    range: Range<usize>,
+    items: Vec<CompletionItem>,
+    selected_ix: usize,
+    scroll_handle: ScrollHandle,
+}
+
+impl TextInput {
+    pub(super) fn show_completions(
+        &mut self,
+        _: &ShowCompletions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_completions(window, cx);
+    }
+
+    /// Query the completions at the cursor, the menu will be shown when the completions are ready.
+    pub(super) fn update_completions(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(provider) = self.completion_provider.clone() else {
+            return;
+        };
+        if !self.selected_range.is_empty()
+            || !self.selections.is_empty()
+            || self.marked_range.is_some()
+        {
+            self.hide_completions(cx);
+            return;
+        }
+
+        let text = self.text();
+        let offset = self.cursor_offset();
+        let Some(range) = provider.trigger_range(&text, offset) else {
+            self.hide_completions(cx);
+            return;
+        };
+
+        // Keep the shown items in sync with the text until the new completions are ready.
+        if let Some(menu) = self.completion_menu.as_mut() {
+            menu.range = range.clone();
+        }
+
+        let completions = provider.completions(&text, offset, window, cx);
+        self._completion_task = cx.spawn_in(window, |this, mut cx| async move {
+            let items = completions.await;
+
+            _ = this.update_in(&mut cx, |this, _, cx| {
+                this.completion_menu = match items {
+                    Ok(items) if !items.is_empty() => Some(CompletionMenu {
+                        range,
+                        items,
+                        selected_ix: 0,
+                        scroll_handle: ScrollHandle::new(),
+                    }),
+                    _ => None,
+                };
+                cx.notify();
+            });
+        });
+    }
+
+    pub(super) fn hide_completions(&mut self, cx: &mut Context<Self>) {
+        self._completion_task = Task::ready(());
+        if self.completion_menu.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Move the selected item of the completion menu by `delta`, returns false if the menu is not shown.
+    pub(super) fn select_completion(&mut self, delta: isize, cx: &mut Context<Self>) -> bool {
+        let Some(menu) = self.completion_menu.as_mut() else {
+            return false;
+        };
+
+        let len = menu.items.len() as isize;
+        menu.selected_ix = (menu.selected_ix as isize + delta).rem_euclid(len) as usize;
+        menu.scroll_handle.scroll_to_item(menu.selected_ix);
+        cx.notify();
+        true
+    }
+
+    /// Accept the selected item of the completion menu, returns false if the menu is not shown.
+    pub(super) fn accept_completion(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(ix) = self.completion_menu.as_ref().map(|menu| menu.selected_ix) else {
+            return false;
+        };
+
+        self.accept_completion_at(ix, window, cx);
+        true
+    }
+
+    fn accept_completion_at(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(menu) = self.completion_menu.take() else {
+            return;
+        };
+        let Some(item) = menu.items.get(ix) else {
+            return;
+        };
+
+        let new_text = item.insert_text.as_ref().unwrap_or(&item.label).clone();
+        let range = menu.range.start.min(self.text.len())..menu.range.end.min(self.text.len());
+
+        // Replace the trigger range as one undo step.
+        self.history.start_new_group();
+        self.replace_text_in_range(Some(self.range_to_utf16(&range)), &new_text, window, cx);
+        self.history.start_new_group();
+        self.hide_completions(cx);
+    }
+
+    pub(super) fn render_completion_menu(
+        &self,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let menu = self.completion_menu.as_ref()?;
+        if !self.focus_handle.is_focused(window) {
+            return None;
+        }
+
+        let (Some(layout), Some(bounds)) = (self.last_layout.as_ref(), self.last_bounds) else {
+            return None;
+        };
+        let line_height = self.last_line_height;
+        let (_, _, Some(pos)) =
+            self.line_and_position_for_offset(menu.range.start, layout, line_height)
+        else {
+            return None;
+        };
+        let position = bounds.origin + pos + point(px(0.), line_height);
+
+        Some(
+            deferred(
+                anchored()
+                    .position(position)
+                    .snap_to_window_with_margin(px(8.))
+                    .child(
+                        v_flex()
+                            .id("completion-menu")
+                            .occlude()
+                            .mt_1()
+                            .p_1()
+                            .gap_y_0p5()
+                            .min_w(px(200.))
+                            .max_w(px(400.))
+                            .max_h(px(240.))
+                            .overflow_y_scroll()
+                            .track_scroll(&menu.scroll_handle)
+                            .popover_style(cx)
+                            .text_color(cx.theme().popover_foreground)
+                            .on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                                this.hide_completions(cx);
+                            }))
+                            .children(menu.items.iter().enumerate().map(|(ix, item)| {
+                                ListItem::new(("completion-item", ix))
+                                    .text_sm()
+                                    .py_0()
+                                    .px_1()
+                                    .rounded_md()
+                                    .selected(ix == menu.selected_ix)
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.accept_completion_at(ix, window, cx);
+                                    }))
+                                    .child(
+                                        h_flex()
+                                            .h(px(26.))
+                                            .w_full()
+                                            .gap_2()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(item.label.clone())
+                                            .when_some(item.detail.clone(), |this, detail| {
+                                                this.child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(cx.theme().muted_foreground)
+                                                        .child(detail),
+                                                )
+                                            }),
+                                    )
+                            })),
+                    ),
+            )
+            .with_priority(1),
+        )
+    }
+}
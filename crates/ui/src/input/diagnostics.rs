@@ -0,0 +1,254 @@
+use std::ops::Range;
+
+use gpui::{
+    anchored, deferred, div, point, px, Context, Hsla, IntoElement, MouseMoveEvent,
+    ParentElement as _, SharedString, Styled as _, Window,
+};
+
+use crate::{ActiveTheme as _, Theme};
+
+use super::TextInput;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+impl DiagnosticSeverity {
+    pub(super) fn color(&self, theme: &Theme) -> Hsla {
+        match self {
+            Self::Error => theme.danger,
+            Self::Warning => theme.warning,
+        }
+    }
+}
+
+/// A diagnostic of the text in the [`TextInput`], displayed as a wavy underline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The range (UTF-8) of the text.
+    pub range: Range<usize>,
+    pub severity: DiagnosticSeverity,
+    pub message: SharedString,
+}
+
+impl Diagnostic {
+    pub fn new(
+        range: Range<usize>,
+        severity: DiagnosticSeverity,
+        message: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            range,
+            severity,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(range: Range<usize>, message: impl Into<SharedString>) -> Self {
+        Self::new(range, DiagnosticSeverity::Error, message)
+    }
+
+    pub fn warning(range: Range<usize>, message: impl Into<SharedString>) -> Self {
+        Self::new(range, DiagnosticSeverity::Warning, message)
+    }
+}
+
+/// Move the diagnostics after the text in `range` is replaced by a text of `new_len`.
+///
+/// The inserted text is not included in the diagnostics at its edges, the diagnostics
+/// overlapping the range are clipped, and removed if they become empty.
+pub(super) fn adjust_diagnostics(
+    diagnostics: &mut Vec<Diagnostic>,
+    range: &Range<usize>,
+    new_len: usize,
+) {
+    diagnostics.retain_mut(|diagnostic| {
+        let start = diagnostic.range.start;
+        let start = if start < range.start {
+            start
+        } else if start >= range.end {
+            start - range.len() + new_len
+        } else {
+            range.start + new_len
+        };
+
+        let end = diagnostic.range.end;
+        let end = if end <= range.start {
+            end
+        } else if end >= range.end {
+            end - range.len() + new_len
+        } else {
+            range.start
+        };
+
+        diagnostic.range = start..end;
+        !diagnostic.range.is_empty()
+    });
+}
+
+impl TextInput {
+    /// Set the diagnostics of the text, the previous diagnostics will be replaced.
+    ///
+    /// The diagnostics are moved with the text when the text is changed.
+    pub fn set_diagnostics(
+        &mut self,
+        diagnostics: Vec<Diagnostic>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let len = self.text.len();
+        self.diagnostics = diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                diagnostic.range = diagnostic.range.start.min(len)..diagnostic.range.end.min(len);
+                (!diagnostic.range.is_empty()).then_some(diagnostic)
+            })
+            .collect();
+        self.diagnostics
+            .sort_by_key(|diagnostic| diagnostic.range.start);
+        self.hovered_diagnostic = None;
+        cx.notify();
+    }
+
+    /// Remove all the diagnostics.
+    pub fn clear_diagnostics(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_diagnostics(vec![], window, cx);
+    }
+
+    /// Returns the diagnostics of the text.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub(super) fn on_mouse_move_for_diagnostics(
+        &mut self,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.diagnostics.is_empty() || event.dragging() {
+            return;
+        }
+
+        let hovered = if self.last_layout.is_some() && self.input_bounds.contains(&event.position) {
+            let offset = self.index_for_mouse_position(event.position, window, cx);
+            self.diagnostics.iter().position(|diagnostic| {
+                diagnostic.range.start <= offset && offset < diagnostic.range.end
+            })
+        } else {
+            None
+        };
+
+        if hovered != self.hovered_diagnostic {
+            self.hovered_diagnostic = hovered;
+            cx.notify();
+        }
+    }
+
+    /// Returns the diagnostic to show the message, the hovered one or the one at the cursor.
+    fn active_diagnostic(&self, window: &Window) -> Option<&Diagnostic> {
+        if let Some(diagnostic) = self
+            .hovered_diagnostic
+            .and_then(|ix| self.diagnostics.get(ix))
+        {
+            return Some(diagnostic);
+        }
+
+        if !self.focus_handle.is_focused(window) || !self.selected_range.is_empty() {
+            return None;
+        }
+
+        let offset = self.cursor_offset();
+        self.diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.range.start <= offset && offset <= diagnostic.range.end)
+    }
+
+    pub(super) fn render_diagnostic_tooltip(
+        &self,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if self.masked || self.completion_menu.is_some() {
+            return None;
+        }
+
+        let diagnostic = self.active_diagnostic(window)?;
+        let (Some(layout), Some(bounds)) = (self.last_layout.as_ref(), self.last_bounds) else {
+            return None;
+        };
+        let line_height = self.last_line_height;
+        let (_, _, Some(pos)) =
+            self.line_and_position_for_offset(diagnostic.range.start, layout, line_height)
+        else {
+            return None;
+        };
+        let position = bounds.origin + pos + point(px(0.), line_height);
+
+        Some(
+            deferred(
+                anchored()
+                    .position(position)
+                    .snap_to_window_with_margin(px(8.))
+                    .child(
+                        div()
+                            .mt_1()
+                            .max_w(px(360.))
+                            .py_0p5()
+                            .px_2()
+                            .border_1()
+                            .border_color(diagnostic.severity.color(cx.theme()))
+                            .rounded(px(6.))
+                            .shadow_md()
+                            .bg(cx.theme().popover)
+                            .text_color(cx.theme().popover_foreground)
+                            .text_sm()
+                            .child(diagnostic.message.clone()),
+                    ),
+            )
+            .with_priority(1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(diagnostics: &[Diagnostic]) -> Vec<Range<usize>> {
+        diagnostics.iter().map(|d| d.range.clone()).collect()
+    }
+
+    #[test]
+    fn test_adjust_diagnostics() {
+        let mut diagnostics = vec![
+            Diagnostic::error(0..5, "a"),
+            Diagnostic::warning(6..11, "b"),
+            Diagnostic::error(12..15, "c"),
+        ];
+
+        // Insert before the diagnostics.
+        adjust_diagnostics(&mut diagnostics, &(0..0), 2);
+        assert_eq!(ranges(&diagnostics), vec![2..7, 8..13, 14..17]);
+
+        // Insert at the end of a diagnostic doesn't extend it.
+        adjust_diagnostics(&mut diagnostics, &(7..7), 1);
+        assert_eq!(ranges(&diagnostics), vec![2..7, 9..14, 15..18]);
+
+        // Replace the text inside a diagnostic.
+        adjust_diagnostics(&mut diagnostics, &(3..5), 4);
+        assert_eq!(ranges(&diagnostics), vec![2..9, 11..16, 17..20]);
+
+        // Replace the text overlapping a diagnostic clips it.
+        adjust_diagnostics(&mut diagnostics, &(10..13), 0);
+        assert_eq!(ranges(&diagnostics), vec![2..9, 10..13, 14..17]);
+
+        // Delete the whole diagnostic removes it.
+        adjust_diagnostics(&mut diagnostics, &(10..13), 0);
+        assert_eq!(ranges(&diagnostics), vec![2..9, 11..14]);
+        assert_eq!(diagnostics[1].message.as_ref(), "c");
+    }
+}
@@ -1,16 +1,21 @@
+use std::ops::Range;
+
 use gpui::{
-    fill, point, px, relative, size, App, Bounds, Corners, Element, ElementId, ElementInputHandler,
-    Entity, GlobalElementId, IntoElement, LayoutId, MouseButton, MouseMoveEvent, PaintQuad, Path,
-    Pixels, Point, Style, TextRun, UnderlineStyle, Window, WrappedLine,
+    combine_highlights, fill, point, px, relative, size, App, Bounds, Corners, Element, ElementId,
+    ElementInputHandler, Entity, Font, GlobalElementId, HighlightStyle, Hsla, IntoElement,
+    LayoutId, MouseButton, MouseMoveEvent, PaintQuad, Path, Pixels, Point, SharedString, Style,
+    TextRun, TextStyle, UnderlineStyle, Window, WrappedLine,
 };
 use smallvec::SmallVec;
 
-use crate::ActiveTheme as _;
+use crate::{ActiveTheme as _, ThemeMode};
 
-use super::TextInput;
+use super::{DiagnosticSeverity, TextInput};
 
 const RIGHT_MARGIN: Pixels = px(5.);
 const BOTTOM_MARGIN: Pixels = px(20.);
+/// The number of rows to layout before and after the visible rows.
+const OVERSCAN_ROWS: usize = 8;
 
 pub(super) struct TextElement {
     input: Entity<TextInput>,
@@ -30,6 +35,10 @@ impl TextElement {
                     input.update(cx, |input, cx| {
                         input.on_drag_move(event, window, cx);
                     });
+                } else {
+                    input.update(cx, |input, cx| {
+                        input.on_mouse_move_for_diagnostics(event, window, cx);
+                    });
                 }
             }
         });
@@ -37,7 +46,7 @@ impl TextElement {
 
     fn layout_cursor(
         &self,
-        lines: &[WrappedLine],
+        layout: &LastLayout,
         line_height: Pixels,
         bounds: &mut Bounds<Pixels>,
         window: &mut Window,
@@ -54,42 +63,33 @@ impl TextElement {
         let mut cursor_start = None;
         let mut cursor_end = None;
 
-        let mut prev_lines_offset = 0;
-        let mut offset_y = px(0.);
-        for line in lines.iter() {
+        for (line_start, line_top, line) in layout.lines_with_offsets(line_height) {
             // break loop if all cursor positions are found
             if cursor_pos.is_some() && cursor_start.is_some() && cursor_end.is_some() {
                 break;
             }
 
-            let line_origin = point(px(0.), offset_y);
+            let line_origin = point(px(0.), line_top);
+            let position = |offset: usize| {
+                let offset = offset.checked_sub(line_start)?;
+                line.position_for_index(offset, line_height)
+            };
             if cursor_pos.is_none() {
-                let offset = cursor_offset.saturating_sub(prev_lines_offset);
-                if let Some(pos) = line.position_for_index(offset, line_height) {
-                    cursor_pos = Some(line_origin + pos);
-                }
+                cursor_pos = position(cursor_offset).map(|pos| line_origin + pos);
             }
             if cursor_start.is_none() {
-                let offset = selected_range.start.saturating_sub(prev_lines_offset);
-                if let Some(pos) = line.position_for_index(offset, line_height) {
-                    cursor_start = Some(line_origin + pos);
-                }
+                cursor_start = position(selected_range.start).map(|pos| line_origin + pos);
             }
             if cursor_end.is_none() {
-                let offset = selected_range.end.saturating_sub(prev_lines_offset);
-                if let Some(pos) = line.position_for_index(offset, line_height) {
-                    cursor_end = Some(line_origin + pos);
-                }
+                cursor_end = position(selected_range.end).map(|pos| line_origin + pos);
             }
-
-            offset_y += line.size(line_height).height;
-            // +1 for skip the last `\n`
-            prev_lines_offset += line.len() + 1;
         }
 
-        if let (Some(cursor_pos), Some(cursor_start), Some(cursor_end)) =
-            (cursor_pos, cursor_start, cursor_end)
-        {
+        if let Some(cursor_pos) = cursor_pos {
+            // The other end of the selection may be out of the laid out rows,
+            // the cursor is always one of the ends.
+            let cursor_start = cursor_start.unwrap_or(cursor_pos);
+            let cursor_end = cursor_end.unwrap_or(cursor_pos);
             let cursor_moved = input.last_cursor_offset != Some(cursor_offset);
             let selection_changed = input.last_selected_range != Some(selected_range.clone());
 
@@ -153,6 +153,9 @@ impl TextElement {
                     cx.theme().caret,
                 ))
             };
+        } else {
+            // The cursor is scrolled out of the laid out rows.
+            bounds.origin = bounds.origin + scroll_offset;
         }
 
         (cursor, scroll_offset)
@@ -160,7 +163,7 @@ impl TextElement {
 
     fn layout_selections(
         &self,
-        lines: &[WrappedLine],
+        layout: &LastLayout,
         line_height: Pixels,
         bounds: &mut Bounds<Pixels>,
         _: &mut Window,
@@ -178,15 +181,17 @@ impl TextElement {
             (selected_range.end, selected_range.start)
         };
 
-        let mut prev_lines_offset = 0;
-        let mut line_corners = vec![];
+        // Only the selection in the laid out rows is displayed.
+        if end_ix < layout.offset || start_ix > layout.end_offset() {
+            return None;
+        }
 
-        let mut offset_y = px(0.);
-        for line in lines.iter() {
+        let mut line_corners = vec![];
+        for (prev_lines_offset, line_top, line) in layout.lines_with_offsets(line_height) {
             let line_size = line.size(line_height);
             let line_wrap_width = line_size.width;
 
-            let line_origin = point(px(0.), offset_y);
+            let line_origin = point(px(0.), line_top);
 
             let line_cursor_start =
                 line.position_for_index(start_ix.saturating_sub(prev_lines_offset), line_height);
@@ -236,10 +241,6 @@ impl TextElement {
             if line_cursor_start.is_some() && line_cursor_end.is_some() {
                 break;
             }
-
-            offset_y += line_size.height;
-            // +1 for skip the last `\n`
-            prev_lines_offset += line.len() + 1;
         }
 
         let mut points = vec![];
@@ -282,16 +283,496 @@ impl TextElement {
 
         builder.build().ok()
     }
+
+    /// Layout the other selections and their cursors when there are multiple cursors.
+    fn layout_multiple_selections(
+        &self,
+        layout: &LastLayout,
+        line_height: Pixels,
+        bounds: &Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (Vec<PaintQuad>, Vec<PaintQuad>) {
+        let input = self.input.read(cx);
+        if input.selections.is_empty() {
+            return (vec![], vec![]);
+        }
+
+        let mut ranges = input.selections.clone();
+        ranges.sort_by_key(|range| range.start);
+
+        let selections = range_bounds(
+            layout,
+            line_height,
+            &ranges,
+            bounds.origin,
+            window.content_mask().bounds,
+        )
+        .into_iter()
+        .map(|bounds| fill(bounds, cx.theme().selection))
+        .collect();
+
+        let mut cursors = vec![];
+        if input.show_cursor(window, cx) {
+            let cursor_height = window.text_style().font_size.to_pixels(window.rem_size()) + px(2.);
+            for range in ranges.iter() {
+                let offset = if input.selection_reversed {
+                    range.start
+                } else {
+                    range.end
+                };
+                let (_, _, Some(pos)) =
+                    input.line_and_position_for_offset(offset, layout, line_height)
+                else {
+                    continue;
+                };
+
+                cursors.push(fill(
+                    Bounds::new(
+                        point(
+                            bounds.left() + pos.x,
+                            bounds.top() + pos.y + ((line_height - cursor_height) / 2.),
+                        ),
+                        size(px(1.), cursor_height),
+                    ),
+                    cx.theme().caret,
+                ));
+            }
+        }
+
+        (selections, cursors)
+    }
+
+    /// Layout the highlights of the find matches, only the visible lines are included.
+    fn layout_search_matches(
+        &self,
+        layout: &LastLayout,
+        line_height: Pixels,
+        bounds: &Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<PaintQuad> {
+        let input = self.input.read(cx);
+        let Some(search) = input.search.as_ref().filter(|search| search.is_visible()) else {
+            return vec![];
+        };
+        if input.masked || search.matches.is_empty() {
+            return vec![];
+        }
+
+        let color = cx.theme().selection.opacity(0.4);
+        range_bounds(
+            layout,
+            line_height,
+            &search.matches,
+            bounds.origin,
+            window.content_mask().bounds,
+        )
+        .into_iter()
+        .map(|bounds| fill(bounds, color))
+        .collect()
+    }
+
+    /// Layout the underlines of the diagnostics, only the visible lines are included.
+    fn layout_diagnostics(
+        &self,
+        layout: &LastLayout,
+        line_height: Pixels,
+        bounds: &Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<(Bounds<Pixels>, Hsla)> {
+        let input = self.input.read(cx);
+        if input.masked || input.diagnostics.is_empty() {
+            return vec![];
+        }
+
+        [DiagnosticSeverity::Warning, DiagnosticSeverity::Error]
+            .into_iter()
+            .flat_map(|severity| {
+                let ranges = input
+                    .diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.severity == severity)
+                    .map(|diagnostic| diagnostic.range.clone())
+                    .collect::<Vec<_>>();
+                let color = severity.color(cx.theme());
+
+                range_bounds(
+                    layout,
+                    line_height,
+                    &ranges,
+                    bounds.origin,
+                    window.content_mask().bounds,
+                )
+                .into_iter()
+                .map(move |bounds| (bounds, color))
+            })
+            .collect()
+    }
+}
+
+/// Returns the bounds of the sorted `ranges` in the laid out lines, split by the wrapped lines.
+///
+/// The lines outside the `visible_bounds` are skipped.
+fn range_bounds(
+    layout: &LastLayout,
+    line_height: Pixels,
+    ranges: &[Range<usize>],
+    origin: Point<Pixels>,
+    visible_bounds: Bounds<Pixels>,
+) -> Vec<Bounds<Pixels>> {
+    let mut result = vec![];
+    let mut range_ix = 0;
+    for (line_start, line_top, line) in layout.lines_with_offsets(line_height) {
+        let line_size = line.size(line_height);
+        let line_end = line_start + line.len();
+        let line_origin = origin + point(px(0.), line_top);
+
+        if line_origin.y > visible_bounds.bottom() {
+            break;
+        }
+
+        // Skip the ranges ending before this line.
+        while range_ix < ranges.len() && ranges[range_ix].end < line_start {
+            range_ix += 1;
+        }
+
+        if line_origin.y + line_size.height >= visible_bounds.top() {
+            for range in ranges[range_ix..]
+                .iter()
+                .take_while(|range| range.start <= line_end)
+            {
+                let start = range.start.max(line_start) - line_start;
+                let end = range.end.min(line_end) - line_start;
+                let (Some(start), Some(end)) = (
+                    line.position_for_index(start, line_height),
+                    line.position_for_index(end, line_height),
+                ) else {
+                    continue;
+                };
+
+                // The range may cross the wrapped lines.
+                let mut y = start.y;
+                while y <= end.y {
+                    let left = if y == start.y { start.x } else { px(0.) };
+                    let right = if y == end.y { end.x } else { line_size.width };
+                    if right > left {
+                        result.push(Bounds::from_corners(
+                            line_origin + point(left, y),
+                            line_origin + point(right, y + line_height),
+                        ));
+                    }
+                    y += line_height;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// The shaped lines of the visible rows (with an overscan) in the last layout.
+///
+/// The other rows are not shaped in the layout, their heights are taken from the
+/// rows shaped before, or estimated as one line.
+#[derive(Clone)]
+pub(super) struct LastLayout {
+    /// The rows of the `lines`.
+    pub(super) rows: Range<usize>,
+    /// The offset of the first line in the text.
+    pub(super) offset: usize,
+    /// The y of the first line, relative to the top of the text.
+    pub(super) top: Pixels,
+    /// The height of the whole text.
+    pub(super) height: Pixels,
+    pub(super) lines: SmallVec<[WrappedLine; 1]>,
+}
+
+impl LastLayout {
+    /// Create a layout of all the lines, e.g.: the lines of the placeholder.
+    fn with_all_lines(lines: SmallVec<[WrappedLine; 1]>, line_height: Pixels) -> Self {
+        Self {
+            rows: 0..lines.len(),
+            offset: 0,
+            top: px(0.),
+            height: lines.iter().fold(px(0.), |height, line| {
+                height + line.size(line_height).height
+            }),
+            lines,
+        }
+    }
+
+    /// Returns the lines with their start offsets in the text and their tops.
+    pub(super) fn lines_with_offsets(
+        &self,
+        line_height: Pixels,
+    ) -> impl Iterator<Item = (usize, Pixels, &WrappedLine)> {
+        let mut offset = self.offset;
+        let mut top = self.top;
+        self.lines.iter().map(move |line| {
+            let item = (offset, top, line);
+            // +1 for the `\n`
+            offset += line.len() + 1;
+            top += line.size(line_height).height;
+            item
+        })
+    }
+
+    /// Returns the end offset of the last line.
+    pub(super) fn end_offset(&self) -> usize {
+        let len = self.lines.iter().map(|line| line.len() + 1).sum::<usize>();
+        (self.offset + len).saturating_sub(1)
+    }
 }
 
 pub(super) struct PrepaintState {
-    lines: SmallVec<[WrappedLine; 1]>,
+    layout: LastLayout,
     cursor: Option<PaintQuad>,
     cursor_scroll_offset: Point<Pixels>,
     selection_path: Option<Path<Pixels>>,
+    /// The other selections and cursors when there are multiple cursors.
+    multiple_selections: Vec<PaintQuad>,
+    multiple_cursors: Vec<PaintQuad>,
+    search_matches: Vec<PaintQuad>,
+    /// The bounds and colors of the diagnostic underlines.
+    diagnostics: Vec<(Bounds<Pixels>, Hsla)>,
+    /// The remaining part of the mask pattern, displayed after the text.
+    mask_placeholder: Option<WrappedLine>,
     bounds: Bounds<Pixels>,
 }
 
+/// The text style and wrap width of the cached line layouts,
+/// the cache must be dropped if any of them is changed.
+#[derive(Clone, PartialEq)]
+pub(super) struct LineLayoutKey {
+    font: Font,
+    color: Hsla,
+    font_size: Pixels,
+    wrap_width: Option<Pixels>,
+    theme_mode: ThemeMode,
+}
+
+fn marked_highlight(style: &TextStyle) -> HighlightStyle {
+    HighlightStyle {
+        underline: Some(UnderlineStyle {
+            color: Some(style.color),
+            thickness: px(1.0),
+            wavy: false,
+        }),
+        ..Default::default()
+    }
+}
+
+/// Layout the visible rows (with an overscan) of the text of the input.
+///
+/// The layout of each line is cached in the input, only the lines changed since
+/// the last layout (and the line with the marked text) are highlighted and shaped again.
+///
+/// The rows out of the visible rows are not shaped, so a large text is not shaped at once.
+/// Without wrapping, the height of each row is the `line_height`; with wrapping,
+/// the rows not shaped yet are estimated as one line.
+#[allow(clippy::too_many_arguments)]
+fn layout_lines(
+    input: &mut TextInput,
+    style: &TextStyle,
+    font_size: Pixels,
+    wrap_width: Option<Pixels>,
+    line_height: Pixels,
+    viewport_height: Pixels,
+    window: &mut Window,
+    cx: &App,
+) -> LastLayout {
+    let key = LineLayoutKey {
+        font: style.font(),
+        color: style.color,
+        font_size,
+        wrap_width,
+        theme_mode: cx.theme().mode,
+    };
+    if input.line_layout_key.as_ref() != Some(&key) {
+        // The highlight colors depend on the theme.
+        if input.line_layout_key.as_ref().map(|key| key.theme_mode) != Some(key.theme_mode) {
+            input.highlight_cache.clear();
+        }
+        input.line_layouts.clear();
+        input.line_layout_key = Some(key);
+    }
+
+    let rows = input.text.lines_len();
+    input.line_layouts.resize(rows);
+    input.highlight_cache.resize(rows);
+
+    let row_height = |input: &TextInput, row: usize| match wrap_width {
+        Some(_) => input
+            .line_layouts
+            .get(row)
+            .map_or(line_height, |line| line.size(line_height).height),
+        None => line_height,
+    };
+    let row_top = |input: &TextInput, row: usize| match wrap_width {
+        Some(_) => (0..row).fold(px(0.), |top, row| top + row_height(input, row)),
+        None => line_height * row as f32,
+    };
+
+    // The top of the viewport in the text.
+    let mut scroll_top = -input.scroll_handle.offset().y;
+
+    // Keep the cursor row in the laid out rows if the cursor is moved,
+    // then `layout_cursor` can scroll to it.
+    let cursor_offset = input.cursor_offset();
+    if input.last_cursor_offset != Some(cursor_offset)
+        || input.last_selected_range.as_ref() != Some(&input.selected_range)
+    {
+        let row = input.text.offset_to_row(cursor_offset);
+        shape_row(input, row, style, font_size, wrap_width, window, cx);
+        let top = row_top(input, row);
+        let bottom = top + row_height(input, row);
+        if top < scroll_top {
+            scroll_top = top;
+        } else if bottom > scroll_top + viewport_height {
+            scroll_top = bottom - viewport_height;
+        }
+    }
+
+    // Find the first visible row.
+    let (mut start, mut top) = match wrap_width {
+        Some(_) => {
+            let mut row = 0;
+            let mut top = px(0.);
+            while row + 1 < rows {
+                let height = row_height(input, row);
+                if top + height > scroll_top {
+                    break;
+                }
+                top += height;
+                row += 1;
+            }
+            (row, top)
+        }
+        None => {
+            let row = ((scroll_top / line_height).max(0.) as usize).min(rows - 1);
+            (row, line_height * row as f32)
+        }
+    };
+    for row in start.saturating_sub(OVERSCAN_ROWS)..start {
+        top -= row_height(input, row);
+    }
+    start = start.saturating_sub(OVERSCAN_ROWS);
+
+    let mut lines = SmallVec::new();
+    let mut end = start;
+    let mut bottom = top;
+    let mut overscan = 0;
+    while end < rows && overscan < OVERSCAN_ROWS {
+        if bottom > scroll_top + viewport_height {
+            overscan += 1;
+        }
+
+        let line = shape_row(input, end, style, font_size, wrap_width, window, cx);
+        bottom += line.size(line_height).height;
+        lines.push(line);
+        end += 1;
+    }
+
+    let height = match wrap_width {
+        Some(_) => (end..rows).fold(bottom, |height, row| height + row_height(input, row)),
+        None => line_height * rows as f32,
+    };
+
+    LastLayout {
+        rows: start..end,
+        offset: input.text.line_start_offset(start),
+        top,
+        height,
+        lines,
+    }
+}
+
+/// Returns the layout of the row, from the cache or shaped again.
+fn shape_row(
+    input: &mut TextInput,
+    row: usize,
+    style: &TextStyle,
+    font_size: Pixels,
+    wrap_width: Option<Pixels>,
+    window: &mut Window,
+    cx: &App,
+) -> WrappedLine {
+    let line_range = input.text.line_start_offset(row)..input.text.line_end_offset(row);
+    let marked_range = input
+        .marked_range
+        .as_ref()
+        .filter(|range| range.start <= line_range.end && range.end >= line_range.start)
+        .map(|range| {
+            range.start.saturating_sub(line_range.start)
+                ..range.end.min(line_range.end) - line_range.start
+        });
+
+    if marked_range.is_none() {
+        if let Some(layout) = input.line_layouts.get(row) {
+            return layout.clone();
+        }
+    }
+
+    let line = input.text.slice(line_range);
+    let highlights = match input.highlighter.clone() {
+        Some(highlighter) => input
+            .highlight_cache
+            .get_or_insert_with(row, || highlighter.highlight_line(&line, cx))
+            .clone(),
+        None => vec![],
+    };
+    let runs = match marked_range.clone() {
+        Some(marked_range) => text_runs_for_highlights(
+            line.len(),
+            style,
+            combine_highlights(highlights, [(marked_range, marked_highlight(style))]),
+        ),
+        None => text_runs_for_highlights(line.len(), style, highlights),
+    };
+
+    let layout = window
+        .text_system()
+        .shape_text(line.into(), font_size, &runs, wrap_width, None)
+        .unwrap()
+        .swap_remove(0);
+
+    // The marked text is changing frequently, so don't cache it.
+    if marked_range.is_none() {
+        input.line_layouts.insert(row, layout.clone());
+    }
+    layout
+}
+
+/// Split the text into [`TextRun`]s by the highlights, the text not covered by
+/// highlights will use the default `style`.
+fn text_runs_for_highlights(
+    len: usize,
+    style: &TextStyle,
+    highlights: impl IntoIterator<Item = (Range<usize>, HighlightStyle)>,
+) -> Vec<TextRun> {
+    let mut runs = vec![];
+    let mut ix = 0;
+    for (range, highlight) in highlights {
+        let range = range.start.max(ix)..range.end.min(len);
+        if range.is_empty() {
+            continue;
+        }
+
+        if ix < range.start {
+            runs.push(style.to_run(range.start - ix));
+        }
+        runs.push(style.clone().highlight(highlight).to_run(range.len()));
+        ix = range.end;
+    }
+    if ix < len {
+        runs.push(style.to_run(len - ix));
+    }
+
+    runs
+}
+
 impl IntoElement for TextElement {
     type Element = Self;
 
@@ -365,56 +846,15 @@ impl Element for TextElement {
         let multi_line = self.input.read(cx).is_multi_line();
         let line_height = window.line_height();
         let input = self.input.read(cx);
-        let text = input.text.clone();
-        let placeholder = input.placeholder.clone();
-        let style = window.text_style();
+        let is_empty = input.text.is_empty();
+        let masked = input.masked;
+        let mut style = window.text_style();
         let mut bounds = bounds;
 
-        let (display_text, text_color) = if text.is_empty() {
-            (placeholder, cx.theme().muted_foreground)
-        } else if input.masked {
-            (
-                "*".repeat(text.chars().count()).into(),
-                cx.theme().foreground,
-            )
-        } else {
-            (text, cx.theme().foreground)
-        };
-
-        let run = TextRun {
-            len: display_text.len(),
-            font: style.font(),
-            color: text_color,
-            background_color: None,
-            underline: None,
-            strikethrough: None,
-        };
-
-        let runs = if let Some(marked_range) = input.marked_range.as_ref() {
-            vec![
-                TextRun {
-                    len: marked_range.start,
-                    ..run.clone()
-                },
-                TextRun {
-                    len: marked_range.end - marked_range.start,
-                    underline: Some(UnderlineStyle {
-                        color: Some(run.color),
-                        thickness: px(1.0),
-                        wavy: false,
-                    }),
-                    ..run.clone()
-                },
-                TextRun {
-                    len: display_text.len() - marked_range.end,
-                    ..run.clone()
-                },
-            ]
-            .into_iter()
-            .filter(|run| run.len > 0)
-            .collect()
+        style.color = if is_empty {
+            cx.theme().muted_foreground
         } else {
-            vec![run]
+            cx.theme().foreground
         };
 
         let font_size = style.font_size.to_pixels(window.rem_size());
@@ -424,10 +864,48 @@ impl Element for TextElement {
             None
         };
 
-        let lines = window
-            .text_system()
-            .shape_text(display_text, font_size, &runs, wrap_width, None)
-            .unwrap();
+        // The remaining part of the mask pattern is displayed as the placeholder.
+        let mask_placeholder = input
+            .mask_pattern
+            .as_ref()
+            .filter(|_| !masked)
+            .map(|mask| mask.placeholder(&input.text.to_string()))
+            .filter(|placeholder| !placeholder.is_empty());
+
+        let layout = if is_empty || masked {
+            let display_text: SharedString = if is_empty {
+                match mask_placeholder.clone() {
+                    Some(placeholder) if input.placeholder.is_empty() => placeholder.into(),
+                    _ => input.placeholder.clone(),
+                }
+            } else {
+                "*".repeat(input.text.chars().count()).into()
+            };
+            let highlights = input
+                .marked_range
+                .clone()
+                .map(|range| (range, marked_highlight(&style)));
+            let runs = text_runs_for_highlights(display_text.len(), &style, highlights);
+
+            let lines = window
+                .text_system()
+                .shape_text(display_text, font_size, &runs, wrap_width, None)
+                .unwrap();
+            LastLayout::with_all_lines(lines, line_height)
+        } else {
+            self.input.update(cx, |input, cx| {
+                layout_lines(
+                    input,
+                    &style,
+                    font_size,
+                    wrap_width,
+                    line_height,
+                    bounds.size.height,
+                    window,
+                    cx,
+                )
+            })
+        };
 
         // `position_for_index` for example
         //
@@ -461,16 +939,39 @@ impl Element for TextElement {
         // Calculate the scroll offset to keep the cursor in view
 
         let (cursor, cursor_scroll_offset) =
-            self.layout_cursor(&lines, line_height, &mut bounds, window, cx);
-
-        let selection_path = self.layout_selections(&lines, line_height, &mut bounds, window, cx);
+            self.layout_cursor(&layout, line_height, &mut bounds, window, cx);
+
+        let selection_path = self.layout_selections(&layout, line_height, &mut bounds, window, cx);
+        let (multiple_selections, multiple_cursors) =
+            self.layout_multiple_selections(&layout, line_height, &bounds, window, cx);
+        let search_matches = self.layout_search_matches(&layout, line_height, &bounds, window, cx);
+        let mask_placeholder = mask_placeholder.filter(|_| !is_empty).map(|placeholder| {
+            let mut style = style.clone();
+            style.color = cx.theme().muted_foreground;
+            let run = style.to_run(placeholder.len());
+            window
+                .text_system()
+                .shape_text(placeholder.into(), font_size, &[run], None, None)
+                .unwrap()
+                .swap_remove(0)
+        });
+        let diagnostics = if is_empty {
+            vec![]
+        } else {
+            self.layout_diagnostics(&layout, line_height, &bounds, window, cx)
+        };
 
         PrepaintState {
             bounds,
-            lines,
+            layout,
             cursor,
             cursor_scroll_offset,
             selection_path,
+            multiple_selections,
+            multiple_cursors,
+            search_matches,
+            diagnostics,
+            mask_placeholder,
         }
     }
 
@@ -494,44 +995,84 @@ impl Element for TextElement {
             cx,
         );
 
+        // Paint find matches
+        for quad in prepaint.search_matches.drain(..) {
+            window.paint_quad(quad);
+        }
+
         // Paint selections
         if let Some(path) = prepaint.selection_path.take() {
             window.paint_path(path, cx.theme().selection);
         }
+        for quad in prepaint.multiple_selections.drain(..) {
+            window.paint_quad(quad);
+        }
 
         // Paint multi line text
         let line_height = window.line_height();
         let origin = bounds.origin;
 
-        let mut offset_y = px(0.);
-        for line in prepaint.lines.iter() {
-            let p = point(origin.x, origin.y + offset_y);
+        let visible_bounds = window.content_mask().bounds;
+        for (_, line_top, line) in prepaint.layout.lines_with_offsets(line_height) {
+            let p = point(origin.x, origin.y + line_top);
+            let line_height_total = line.size(line_height).height;
+
+            // Only paint the visible lines.
+            if p.y + line_height_total < visible_bounds.top() {
+                continue;
+            }
+            if p.y > visible_bounds.bottom() {
+                break;
+            }
+
             _ = line.paint(p, line_height, window, cx);
-            offset_y += line.size(line_height).height;
+        }
+
+        // The mask is only for the single line input.
+        if let Some(placeholder) = prepaint.mask_placeholder.take() {
+            let x = prepaint
+                .layout
+                .lines
+                .last()
+                .map(|line| line.width())
+                .unwrap_or_default();
+            _ = placeholder.paint(point(origin.x + x, origin.y), line_height, window, cx);
+        }
+
+        // Paint diagnostic underlines
+        for (bounds, color) in prepaint.diagnostics.drain(..) {
+            window.paint_underline(
+                point(bounds.left(), bounds.bottom() - px(4.)),
+                bounds.size.width,
+                &UnderlineStyle {
+                    color: Some(color),
+                    thickness: px(1.),
+                    wavy: true,
+                },
+            );
         }
 
         if focused {
             if let Some(cursor) = prepaint.cursor.take() {
                 window.paint_quad(cursor);
             }
+            for cursor in prepaint.multiple_cursors.drain(..) {
+                window.paint_quad(cursor);
+            }
         }
 
+        // Only the laid out lines are measured, the others are not shaped.
         let width = prepaint
+            .layout
             .lines
             .iter()
             .map(|l| l.width())
             .max()
             .unwrap_or_default();
-        let height = prepaint
-            .lines
-            .iter()
-            .map(|l| l.size(line_height).height.0)
-            .sum::<f32>();
-
-        let scroll_size = size(width, px(height));
+        let scroll_size = size(width, prepaint.layout.height);
 
         self.input.update(cx, |input, _cx| {
-            input.last_layout = Some(prepaint.lines.clone());
+            input.last_layout = Some(prepaint.layout.clone());
             input.last_bounds = Some(bounds);
             input.last_cursor_offset = Some(input.cursor_offset());
             input.last_line_height = line_height;
@@ -0,0 +1,284 @@
+use std::ops::Range;
+
+use gpui::{App, FontStyle, FontWeight, HighlightStyle, Hsla};
+
+use crate::ActiveTheme as _;
+
+/// A styled run of a line, the range is the UTF-8 byte range relative to the line start.
+pub type HighlightRun = (Range<usize>, HighlightStyle);
+
+/// A syntax highlighter for the [`super::TextInput`].
+///
+/// The TextInput will call `highlight_line` for each line of the text (split by `\n`),
+/// the results are cached per line and only the lines touched by a change will be highlighted again.
+pub trait Highlighter: 'static {
+    /// Return the styled runs for the given line.
+    ///
+    /// The runs must be sorted by the range start and must not overlap,
+    /// the text not covered by any run will use the default text style.
+    fn highlight_line(&self, line: &str, cx: &App) -> Vec<HighlightRun>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Comment,
+    Key,
+    String,
+    Number,
+    Keyword,
+    Section,
+    Punctuation,
+}
+
+/// A built-in [`Highlighter`] for JSON and TOML style config text.
+///
+/// It highlights keys, strings, numbers, `true`/`false`/`null`, comments (`#` and `//`),
+/// TOML `[section]` headers and punctuation, without any external grammar.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigHighlighter;
+
+impl ConfigHighlighter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn style(kind: TokenKind, cx: &App) -> HighlightStyle {
+        let is_dark = cx.theme().mode.is_dark();
+        let scale = |light: Hsla, dark: Hsla| if is_dark { dark } else { light };
+
+        match kind {
+            TokenKind::Comment => HighlightStyle {
+                color: Some(cx.theme().muted_foreground),
+                font_style: Some(FontStyle::Italic),
+                ..Default::default()
+            },
+            TokenKind::Key => HighlightStyle {
+                color: Some(scale(crate::blue_600(), crate::blue_400())),
+                ..Default::default()
+            },
+            TokenKind::String => HighlightStyle {
+                color: Some(scale(crate::green_700(), crate::green_400())),
+                ..Default::default()
+            },
+            TokenKind::Number => HighlightStyle {
+                color: Some(scale(crate::orange_600(), crate::orange_400())),
+                ..Default::default()
+            },
+            TokenKind::Keyword => HighlightStyle {
+                color: Some(scale(crate::purple_600(), crate::purple_400())),
+                ..Default::default()
+            },
+            TokenKind::Section => HighlightStyle {
+                color: Some(scale(crate::pink_600(), crate::pink_400())),
+                font_weight: Some(FontWeight::SEMIBOLD),
+                ..Default::default()
+            },
+            TokenKind::Punctuation => HighlightStyle {
+                color: Some(cx.theme().muted_foreground),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Highlighter for ConfigHighlighter {
+    fn highlight_line(&self, line: &str, cx: &App) -> Vec<HighlightRun> {
+        tokenize(line)
+            .into_iter()
+            .map(|(range, kind)| (range, Self::style(kind, cx)))
+            .collect()
+    }
+}
+
+fn is_section_header(line: &str) -> bool {
+    let trimmed = line.trim();
+    let inner = trimmed
+        .strip_prefix("[[")
+        .and_then(|s| s.strip_suffix("]]"))
+        .or_else(|| trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')));
+
+    match inner {
+        Some(inner) => {
+            !inner.trim().is_empty()
+                && inner
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '))
+        }
+        None => false,
+    }
+}
+
+/// Returns the byte offset of the next non-whitespace char from `offset`.
+fn skip_whitespace(line: &str, offset: usize) -> usize {
+    line[offset..]
+        .find(|c: char| !c.is_whitespace())
+        .map(|ix| offset + ix)
+        .unwrap_or(line.len())
+}
+
+fn tokenize(line: &str) -> Vec<(Range<usize>, TokenKind)> {
+    let mut tokens = vec![];
+
+    if is_section_header(line) {
+        let start = skip_whitespace(line, 0);
+        let end = line.trim_end().len();
+        tokens.push((start..end, TokenKind::Section));
+        return tokens;
+    }
+
+    let bytes = line.as_bytes();
+    let mut ix = 0;
+    while ix < line.len() {
+        let c = line[ix..].chars().next().unwrap_or_default();
+
+        if c.is_whitespace() {
+            ix += c.len_utf8();
+            continue;
+        }
+
+        if c == '#' || line[ix..].starts_with("//") {
+            tokens.push((ix..line.len(), TokenKind::Comment));
+            break;
+        }
+
+        if c == '"' || c == '\'' {
+            let start = ix;
+            ix += 1;
+            let mut escaped = false;
+            while ix < line.len() {
+                let ch = line[ix..].chars().next().unwrap_or_default();
+                ix += ch.len_utf8();
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' && c == '"' {
+                    escaped = true;
+                } else if ch == c {
+                    break;
+                }
+            }
+
+            let next = skip_whitespace(line, ix);
+            let kind = match bytes.get(next) {
+                Some(b':') | Some(b'=') => TokenKind::Key,
+                _ => TokenKind::String,
+            };
+            tokens.push((start..ix, kind));
+            continue;
+        }
+
+        if c.is_ascii_digit()
+            || (matches!(c, '-' | '+') && bytes.get(ix + 1).map_or(false, u8::is_ascii_digit))
+        {
+            let start = ix;
+            ix += 1;
+            while ix < line.len()
+                && (bytes[ix].is_ascii_alphanumeric()
+                    || matches!(bytes[ix], b'.' | b'_' | b'-' | b'+' | b':'))
+            {
+                ix += 1;
+            }
+            tokens.push((start..ix, TokenKind::Number));
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let start = ix;
+            while ix < line.len() {
+                let ch = line[ix..].chars().next().unwrap_or_default();
+                if !(ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.')) {
+                    break;
+                }
+                ix += ch.len_utf8();
+            }
+
+            let word = &line[start..ix];
+            let next = skip_whitespace(line, ix);
+            if matches!(word, "true" | "false" | "null" | "inf" | "nan") {
+                tokens.push((start..ix, TokenKind::Keyword));
+            } else if bytes.get(next) == Some(&b'=') {
+                tokens.push((start..ix, TokenKind::Key));
+            }
+            continue;
+        }
+
+        if matches!(c, '{' | '}' | '[' | ']' | ':' | ',' | '=') {
+            tokens.push((ix..ix + 1, TokenKind::Punctuation));
+        }
+
+        ix += c.len_utf8();
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &str) -> Vec<(&str, TokenKind)> {
+        tokenize(line)
+            .into_iter()
+            .map(|(range, kind)| (&line[range], kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_json() {
+        assert_eq!(
+            kinds(r#"  "name": "GPUI \"Component\"", "count": -12.5e3,"#),
+            vec![
+                (r#""name""#, TokenKind::Key),
+                (":", TokenKind::Punctuation),
+                (r#""GPUI \"Component\"""#, TokenKind::String),
+                (",", TokenKind::Punctuation),
+                (r#""count""#, TokenKind::Key),
+                (":", TokenKind::Punctuation),
+                ("-12.5e3", TokenKind::Number),
+                (",", TokenKind::Punctuation),
+            ]
+        );
+
+        assert_eq!(
+            kinds(r#"[true, null] // done"#),
+            vec![
+                ("[", TokenKind::Punctuation),
+                ("true", TokenKind::Keyword),
+                (",", TokenKind::Punctuation),
+                ("null", TokenKind::Keyword),
+                ("]", TokenKind::Punctuation),
+                ("// done", TokenKind::Comment),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_toml() {
+        assert_eq!(
+            kinds("[dependencies.gpui]"),
+            vec![("[dependencies.gpui]", TokenKind::Section)]
+        );
+        assert_eq!(kinds("  [[bin]]  "), vec![("[[bin]]", TokenKind::Section)]);
+        assert_eq!(
+            kinds("edition = '2021' # comment"),
+            vec![
+                ("edition", TokenKind::Key),
+                ("=", TokenKind::Punctuation),
+                ("'2021'", TokenKind::String),
+                ("# comment", TokenKind::Comment),
+            ]
+        );
+        assert_eq!(
+            kinds("date = 1979-05-27T07:32:00Z"),
+            vec![
+                ("date", TokenKind::Key),
+                ("=", TokenKind::Punctuation),
+                ("1979-05-27T07:32:00Z", TokenKind::Number),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unterminated_string() {
+        assert_eq!(kinds(r#""你好"#), vec![(r#""你好"#, TokenKind::String)]);
+    }
+}
@@ -3,11 +3,9 @@
 //! Based on the `Input` example from the `gpui` crate.
 //! https://github.com/zed-industries/zed/blob/main/crates/gpui/examples/input.rs
 
-use smallvec::SmallVec;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ops::Range;
 use std::rc::Rc;
-use unicode_segmentation::*;
 
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
@@ -15,7 +13,7 @@ use gpui::{
     Context, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement,
     KeyBinding, KeyDownEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
     ParentElement as _, Pixels, Point, Rems, Render, ScrollHandle, ScrollWheelEvent, SharedString,
-    Styled as _, UTF16Selection, ViewInputHandler, Window, WrappedLine,
+    Styled as _, Task, UTF16Selection, ViewInputHandler, Window, WrappedLine,
 };
 
 // TODO:
@@ -24,7 +22,14 @@ use gpui::{
 
 use super::blink_cursor::BlinkCursor;
 use super::change::Change;
-use super::element::TextElement;
+use super::completion::{CompletionMenu, CompletionProvider};
+use super::diagnostics::{adjust_diagnostics, Diagnostic};
+use super::element::{LastLayout, LineLayoutKey, TextElement};
+use super::highlighter::{HighlightRun, Highlighter};
+use super::line_cache::LineCache;
+use super::mask::MaskPattern;
+use super::rope::Rope;
+use super::search::{SearchState, SEARCH_CONTEXT};
 use super::{number_input, ClearButton};
 
 use crate::history::History;
@@ -69,6 +74,33 @@ actions!(
         MoveToStart,
         MoveToEnd,
         TextChanged,
+        Find,
+        FindAndReplace,
+        FindNext,
+        FindPrevious,
+        CloseFind,
+        ReplaceAll,
+        AddCursorAbove,
+        AddCursorBelow,
+        SelectNextOccurrence,
+        ShowCompletions,
+        Escape,
+        MoveToPreviousWord,
+        MoveToNextWord,
+        MoveToPreviousSubword,
+        MoveToNextSubword,
+        MoveToPreviousParagraph,
+        MoveToNextParagraph,
+        SelectToPreviousWord,
+        SelectToNextWord,
+        SelectToPreviousSubword,
+        SelectToNextSubword,
+        SelectToPreviousParagraph,
+        SelectToNextParagraph,
+        DeleteToPreviousWordStart,
+        DeleteToNextWordEnd,
+        DeleteToPreviousSubwordStart,
+        DeleteToNextSubwordEnd,
     ]
 );
 
@@ -153,6 +185,114 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("ctrl-z", Undo, Some(CONTEXT)),
         #[cfg(not(target_os = "macos"))]
         KeyBinding::new("ctrl-y", Redo, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-f", Find, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-f", Find, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-h", FindAndReplace, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-f", FindAndReplace, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-h", FindAndReplace, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-g", FindNext, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-shift-g", FindPrevious, Some(CONTEXT)),
+        KeyBinding::new("f3", FindNext, Some(CONTEXT)),
+        KeyBinding::new("shift-f3", FindPrevious, Some(CONTEXT)),
+        KeyBinding::new("escape", CloseFind, Some(SEARCH_CONTEXT)),
+        KeyBinding::new("shift-enter", FindPrevious, Some(SEARCH_CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-enter", ReplaceAll, Some(SEARCH_CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-alt-enter", ReplaceAll, Some(SEARCH_CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-up", AddCursorAbove, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-down", AddCursorBelow, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-d", SelectNextOccurrence, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-alt-up", AddCursorAbove, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-alt-down", AddCursorBelow, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-d", SelectNextOccurrence, Some(CONTEXT)),
+        KeyBinding::new("ctrl-space", ShowCompletions, Some(CONTEXT)),
+        KeyBinding::new("escape", Escape, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-left", MoveToPreviousWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-right", MoveToNextWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-shift-left", SelectToPreviousWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-shift-right", SelectToNextWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("ctrl-alt-left", MoveToPreviousSubword, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("ctrl-alt-right", MoveToNextSubword, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new(
+            "ctrl-alt-shift-left",
+            SelectToPreviousSubword,
+            Some(CONTEXT),
+        ),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("ctrl-alt-shift-right", SelectToNextSubword, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-up", MoveToPreviousParagraph, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-down", MoveToNextParagraph, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-shift-up", SelectToPreviousParagraph, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-shift-down", SelectToNextParagraph, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-backspace", DeleteToPreviousWordStart, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-delete", DeleteToNextWordEnd, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new(
+            "ctrl-alt-backspace",
+            DeleteToPreviousSubwordStart,
+            Some(CONTEXT),
+        ),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("ctrl-alt-delete", DeleteToNextSubwordEnd, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-left", MoveToPreviousWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-right", MoveToNextWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-left", SelectToPreviousWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-right", SelectToNextWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-left", MoveToPreviousSubword, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-right", MoveToNextSubword, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-shift-left", SelectToPreviousSubword, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-shift-right", SelectToNextSubword, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-up", MoveToPreviousParagraph, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-down", MoveToNextParagraph, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-up", SelectToPreviousParagraph, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-down", SelectToNextParagraph, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-backspace", DeleteToPreviousWordStart, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-delete", DeleteToNextWordEnd, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-backspace", DeleteToPreviousSubwordStart, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-delete", DeleteToNextSubwordEnd, Some(CONTEXT)),
     ]);
 
     number_input::init(cx);
@@ -160,7 +300,9 @@ pub fn init(cx: &mut App) {
 
 pub struct TextInput {
     pub(super) focus_handle: FocusHandle,
-    pub(super) text: SharedString,
+    pub(super) text: Rope,
+    /// The text as a string, built on demand and dropped when the text is changed.
+    text_snapshot: RefCell<Option<SharedString>>,
     multi_line: bool,
     pub(super) history: History<Change>,
     pub(super) blink_cursor: Entity<BlinkCursor>,
@@ -176,8 +318,15 @@ pub struct TextInput {
     /// Range for save the selected word, use to keep word range when drag move.
     pub(super) selected_word_range: Option<Range<usize>>,
     pub(super) selection_reversed: bool,
+    /// The other selections (UTF-8 ranges) besides the `selected_range` when there are multiple cursors.
+    ///
+    /// They share the `selection_reversed` with the `selected_range`.
+    pub(super) selections: Vec<Range<usize>>,
+    /// The mouse down position of the column selection (Alt + drag).
+    column_selection_anchor: Option<Point<Pixels>>,
     pub(super) marked_range: Option<Range<usize>>,
-    pub(super) last_layout: Option<SmallVec<[WrappedLine; 1]>>,
+    /// The laid out lines of the visible rows in the last paint.
+    pub(super) last_layout: Option<LastLayout>,
     pub(super) last_cursor_offset: Option<usize>,
     /// The line_height of text layout, this will change will InputElement painted.
     pub(super) last_line_height: Pixels,
@@ -194,6 +343,7 @@ pub struct TextInput {
     pub(super) size: Size,
     pub(super) rows: usize,
     pattern: Option<regex::Regex>,
+    pub(super) mask_pattern: Option<MaskPattern>,
     validate: Option<Box<dyn Fn(&str) -> bool + 'static>>,
     pub(crate) scroll_handle: ScrollHandle,
     scrollbar_state: Rc<Cell<ScrollbarState>>,
@@ -201,6 +351,21 @@ pub struct TextInput {
     pub(crate) scroll_size: gpui::Size<Pixels>,
     /// To remember the horizontal column (x-coordinate) of the cursor position.
     preferred_x_offset: Option<Pixels>,
+    pub(super) highlighter: Option<Rc<dyn Highlighter>>,
+    /// The highlighted runs of each line.
+    pub(super) highlight_cache: LineCache<Vec<HighlightRun>>,
+    /// The shaped layout of each line, only the changed lines will be shaped again.
+    pub(super) line_layouts: LineCache<WrappedLine>,
+    /// The text style and wrap width used by `line_layouts`.
+    pub(super) line_layout_key: Option<LineLayoutKey>,
+    /// The find/replace bar state, created when the bar is opened the first time.
+    pub(super) search: Option<SearchState>,
+    pub(super) completion_provider: Option<Rc<dyn CompletionProvider>>,
+    pub(super) completion_menu: Option<CompletionMenu>,
+    pub(super) _completion_task: Task<()>,
+    pub(super) diagnostics: Vec<Diagnostic>,
+    /// The index of the diagnostic under the mouse.
+    pub(super) hovered_diagnostic: Option<usize>,
 }
 
 impl EventEmitter<InputEvent> for TextInput {}
@@ -212,7 +377,8 @@ impl TextInput {
         let history = History::new().group_interval(std::time::Duration::from_secs(1));
         let input = Self {
             focus_handle: focus_handle.clone(),
-            text: "".into(),
+            text: Rope::new(),
+            text_snapshot: RefCell::new(None),
             multi_line: false,
             blink_cursor,
             history,
@@ -220,6 +386,8 @@ impl TextInput {
             selected_range: 0..0,
             selected_word_range: None,
             selection_reversed: false,
+            selections: vec![],
+            column_selection_anchor: None,
             marked_range: None,
             input_bounds: Bounds::default(),
             is_selecting: false,
@@ -232,6 +400,7 @@ impl TextInput {
             suffix: None,
             size: Size::Medium,
             pattern: None,
+            mask_pattern: None,
             validate: None,
             rows: 2,
             last_layout: None,
@@ -243,6 +412,16 @@ impl TextInput {
             scrollbar_state: Rc::new(Cell::new(ScrollbarState::default())),
             scroll_size: gpui::size(px(0.), px(0.)),
             preferred_x_offset: None,
+            highlighter: None,
+            highlight_cache: LineCache::default(),
+            line_layouts: LineCache::default(),
+            line_layout_key: None,
+            search: None,
+            completion_provider: None,
+            completion_menu: None,
+            _completion_task: Task::ready(()),
+            diagnostics: vec![],
+            hovered_diagnostic: None,
         };
 
         // Observe the blink cursor to repaint the view when it changes.
@@ -275,13 +454,13 @@ impl TextInput {
 
     /// Called after moving the cursor. Updates preferred_x_offset if we know where the cursor now is.
     fn update_preferred_x_offset(&mut self, _cx: &mut Context<Self>) {
-        if let (Some(lines), Some(bounds)) = (&self.last_layout, &self.last_bounds) {
+        if let (Some(layout), Some(bounds)) = (&self.last_layout, &self.last_bounds) {
             let offset = self.cursor_offset();
             let line_height = self.last_line_height;
 
             // Find which line and sub-line the cursor is on and its position
             let (_line_index, _sub_line_index, cursor_pos) =
-                self.line_and_position_for_offset(offset, lines, line_height);
+                self.line_and_position_for_offset(offset, layout, line_height);
 
             if let Some(pos) = cursor_pos {
                 // Adjust by scroll offset
@@ -291,25 +470,27 @@ impl TextInput {
         }
     }
 
-    /// Find which line and sub-line the given offset belongs to, along with the position within that sub-line.
-    fn line_and_position_for_offset(
+    /// Find which line (the index in the laid out lines) and sub-line the given offset belongs to,
+    /// along with the position within that sub-line.
+    ///
+    /// The position is None if the offset is out of the laid out lines.
+    pub(super) fn line_and_position_for_offset(
         &self,
         offset: usize,
-        lines: &[WrappedLine],
+        layout: &LastLayout,
         line_height: Pixels,
     ) -> (usize, usize, Option<Point<Pixels>>) {
-        let mut prev_lines_offset = 0;
-        let mut y_offset = px(0.);
-        for (line_index, line) in lines.iter().enumerate() {
-            let local_offset = offset.saturating_sub(prev_lines_offset);
+        for (line_index, (line_start, line_top, line)) in
+            layout.lines_with_offsets(line_height).enumerate()
+        {
+            let Some(local_offset) = offset.checked_sub(line_start) else {
+                break;
+            };
             if let Some(pos) = line.position_for_index(local_offset, line_height) {
                 let sub_line_index = (pos.y.0 / line_height.0) as usize;
-                let adjusted_pos = point(pos.x, pos.y + y_offset);
+                let adjusted_pos = point(pos.x, pos.y + line_top);
                 return (line_index, sub_line_index, Some(adjusted_pos));
             }
-
-            y_offset += line.size(line_height).height;
-            prev_lines_offset += line.len() + 1;
         }
         (0, 0, None)
     }
@@ -321,18 +502,21 @@ impl TextInput {
             return;
         }
 
-        let (Some(lines), Some(bounds)) = (&self.last_layout, &self.last_bounds) else {
+        let (Some(layout), Some(bounds)) = (&self.last_layout, &self.last_bounds) else {
             return;
         };
 
         let offset = self.cursor_offset();
         let line_height = self.last_line_height;
         let (current_line_index, current_sub_line, current_pos) =
-            self.line_and_position_for_offset(offset, lines, line_height);
+            self.line_and_position_for_offset(offset, layout, line_height);
 
         let Some(current_pos) = current_pos else {
+            // The cursor is out of the laid out rows.
+            self.move_vertical_by_row(direction, cx);
             return;
         };
+        let lines = &layout.lines;
 
         let current_x = self
             .preferred_x_offset
@@ -345,8 +529,13 @@ impl TextInput {
 
         // Handle moving above the first line
         if direction == -1 && new_line_index == 0 && new_sub_line < 0 {
-            // Move cursor to the beginning of the text
-            self.move_to(0, window, cx);
+            if layout.rows.start > 0 {
+                // The previous row is not laid out.
+                self.move_vertical_by_row(direction, cx);
+            } else {
+                // Move cursor to the beginning of the text
+                self.move_to(0, window, cx);
+            }
             return;
         }
 
@@ -363,6 +552,10 @@ impl TextInput {
                 if new_line_index < lines.len() - 1 {
                     new_line_index += 1;
                     new_sub_line = 0;
+                } else if layout.rows.end < self.text.lines_len() {
+                    // The next row is not laid out.
+                    self.move_vertical_by_row(direction, cx);
+                    return;
                 } else {
                     new_sub_line = max_sub_line;
                 }
@@ -386,16 +579,45 @@ impl TextInput {
             Err(i) => i,
         };
 
-        let mut prev_lines_offset = 0;
-        for (i, l) in lines.iter().enumerate() {
-            if i == new_line_index {
-                break;
-            }
-            prev_lines_offset += l.len() + 1;
-        }
+        let prev_lines_offset = layout
+            .lines_with_offsets(line_height)
+            .nth(new_line_index)
+            .map_or(layout.offset, |(line_start, _, _)| line_start);
 
         let new_offset = (prev_lines_offset + new_local_index).min(self.text.len());
         self.selected_range = new_offset..new_offset;
+        self.selections.clear();
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+
+    /// Move the cursor to the same column of the previous or next row,
+    /// used when the target row is out of the laid out rows.
+    fn move_vertical_by_row(&mut self, direction: i32, cx: &mut Context<Self>) {
+        let offset = self.cursor_offset();
+        let row = self.text.offset_to_row(offset);
+        let column = offset - self.text.line_start_offset(row);
+        let new_row = if direction < 0 {
+            row.checked_sub(1)
+        } else {
+            Some(row + 1).filter(|row| *row < self.text.lines_len())
+        };
+        let Some(new_row) = new_row else {
+            return;
+        };
+
+        let line_start = self.text.line_start_offset(new_row);
+        let line = self
+            .text
+            .slice(line_start..self.text.line_end_offset(new_row));
+        let mut column = column.min(line.len());
+        while !line.is_char_boundary(column) {
+            column -= 1;
+        }
+
+        let new_offset = line_start + column;
+        self.selected_range = new_offset..new_offset;
+        self.selections.clear();
         self.pause_blink_cursor(cx);
         cx.notify();
     }
@@ -445,7 +667,7 @@ impl TextInput {
         cx: &mut Context<Self>,
     ) {
         let text: SharedString = text.into();
-        let range = 0..self.text.chars().map(|c| c.len_utf16()).sum();
+        let range = 0..self.text.len_utf16();
         self.replace_text_in_range(Some(range), &text, window, cx);
     }
 
@@ -549,12 +771,73 @@ impl TextInput {
         self.pattern = Some(pattern);
     }
 
+    /// Set the format mask of the input field, for example `(999) 999-9999`, only for single line input.
+    ///
+    /// The literals of the mask are inserted automatically when typing,
+    /// and the [`InputEvent::Change`] will emit the raw value without the literals.
+    pub fn mask_pattern(mut self, pattern: impl Into<MaskPattern>) -> Self {
+        self.mask_pattern = Some(pattern.into());
+        self
+    }
+
+    /// Set the format mask of the input field with reference, `None` to remove it.
+    ///
+    /// The current value will be formatted by the new mask.
+    pub fn set_mask_pattern(
+        &mut self,
+        pattern: Option<impl Into<MaskPattern>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let value = self.raw_value();
+        self.mask_pattern = pattern.map(Into::into);
+        self.set_text(value, window, cx);
+    }
+
     /// Set the validation function of the input field.
     pub fn validate(mut self, f: impl Fn(&str) -> bool + 'static) -> Self {
         self.validate = Some(Box::new(f));
         self
     }
 
+    /// Set the syntax highlighter of the input field.
+    pub fn highlighter(mut self, highlighter: impl Highlighter) -> Self {
+        self.highlighter = Some(Rc::new(highlighter));
+        self.highlight_cache.clear();
+        self.line_layouts.clear();
+        self
+    }
+
+    /// Set the syntax highlighter of the input field with reference, `None` to remove it.
+    pub fn set_highlighter(
+        &mut self,
+        highlighter: Option<Rc<dyn Highlighter>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.highlighter = highlighter;
+        self.highlight_cache.clear();
+        self.line_layouts.clear();
+        cx.notify();
+    }
+
+    /// Set the completion provider of the input field.
+    pub fn completion_provider(mut self, provider: impl CompletionProvider) -> Self {
+        self.completion_provider = Some(Rc::new(provider));
+        self
+    }
+
+    /// Set the completion provider of the input field with reference, `None` to remove it.
+    pub fn set_completion_provider(
+        &mut self,
+        provider: Option<Rc<dyn CompletionProvider>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.completion_provider = provider;
+        self.hide_completions(cx);
+    }
+
     /// Set true to show indicator at the input right.
     pub fn set_loading(&mut self, loading: bool, _: &mut Window, cx: &mut Context<Self>) {
         self.loading = loading;
@@ -562,8 +845,22 @@ impl TextInput {
     }
 
     /// Return the text of the input field.
+    ///
+    /// The string is built once after each change, so calling this repeatedly is cheap.
     pub fn text(&self) -> SharedString {
-        self.text.clone()
+        self.text_snapshot
+            .borrow_mut()
+            .get_or_insert_with(|| self.text.to_string().into())
+            .clone()
+    }
+
+    /// Returns the raw value without the literals of the mask pattern,
+    /// same as the [`TextInput::text`] if there is no mask pattern.
+    pub fn raw_value(&self) -> SharedString {
+        match self.mask_pattern.as_ref() {
+            Some(mask) => mask.unformat(&self.text()).into(),
+            None => self.text(),
+        }
     }
 
     pub fn disabled(&self) -> bool {
@@ -594,7 +891,11 @@ impl TextInput {
     }
 
     fn up(&mut self, _: &Up, window: &mut Window, cx: &mut Context<Self>) {
+        if self.select_completion(-1, cx) {
+            return;
+        }
         if self.is_single_line() {
+            cx.propagate();
             return;
         }
         self.pause_blink_cursor(cx);
@@ -602,7 +903,11 @@ impl TextInput {
     }
 
     fn down(&mut self, _: &Down, window: &mut Window, cx: &mut Context<Self>) {
+        if self.select_completion(1, cx) {
+            return;
+        }
         if self.is_single_line() {
+            cx.propagate();
             return;
         }
         self.pause_blink_cursor(cx);
@@ -619,6 +924,7 @@ impl TextInput {
 
     fn select_up(&mut self, _: &SelectUp, window: &mut Window, cx: &mut Context<Self>) {
         if self.is_single_line() {
+            cx.propagate();
             return;
         }
         let offset = self.start_of_line(window, cx).saturating_sub(1);
@@ -627,13 +933,19 @@ impl TextInput {
 
     fn select_down(&mut self, _: &SelectDown, window: &mut Window, cx: &mut Context<Self>) {
         if self.is_single_line() {
+            cx.propagate();
             return;
         }
         let offset = (self.end_of_line(window, cx) + 1).min(self.text.len());
         self.select_to(self.next_boundary(offset), window, cx);
     }
 
-    fn select_all(&mut self, _: &SelectAll, window: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_all(
+        &mut self,
+        _: &SelectAll,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         self.move_to(0, window, cx);
         self.select_to(self.text.len(), window, cx)
     }
@@ -689,69 +1001,48 @@ impl TextInput {
     }
 
     /// Get start of line
-    fn start_of_line(&mut self, window: &mut Window, cx: &mut Context<Self>) -> usize {
+    fn start_of_line(&mut self, _: &mut Window, _: &mut Context<Self>) -> usize {
         if self.is_single_line() {
             return 0;
         }
 
-        let offset = self.previous_boundary(self.cursor_offset());
-        let line = self
-            .text_for_range(self.range_to_utf16(&(0..offset + 1)), &mut None, window, cx)
-            .unwrap_or_default()
-            .rfind('\n')
-            .map(|i| i + 1)
-            .unwrap_or(0);
-        line
+        let row = self.text.offset_to_row(self.cursor_offset());
+        self.text.line_start_offset(row)
     }
 
     /// Get end of line
-    fn end_of_line(&mut self, window: &mut Window, cx: &mut Context<Self>) -> usize {
+    fn end_of_line(&mut self, _: &mut Window, _: &mut Context<Self>) -> usize {
         if self.is_single_line() {
             return self.text.len();
         }
 
-        let offset = self.next_boundary(self.cursor_offset());
-        // ignore if offset is "\n"
-        if self
-            .text_for_range(
-                self.range_to_utf16(&(offset - 1..offset)),
-                &mut None,
-                window,
-                cx,
-            )
-            .unwrap_or_default()
-            .eq("\n")
-        {
-            return offset;
-        }
-
-        let line = self
-            .text_for_range(
-                self.range_to_utf16(&(offset..self.text.len())),
-                &mut None,
-                window,
-                cx,
-            )
-            .unwrap_or_default()
-            .find('\n')
-            .map(|i| i + offset)
-            .unwrap_or(self.text.len());
-        line
+        let row = self.text.offset_to_row(self.cursor_offset());
+        self.text.line_end_offset(row)
     }
 
     fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
-        if self.selected_range.is_empty() {
-            self.select_to(self.previous_boundary(self.cursor_offset()), window, cx)
-        }
-        self.replace_text_in_range(None, "", window, cx);
-        self.pause_blink_cursor(cx);
+        self.delete_to(Self::previous_boundary, window, cx);
     }
 
     fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
-        if self.selected_range.is_empty() {
-            self.select_to(self.next_boundary(self.cursor_offset()), window, cx)
+        self.delete_to(Self::next_boundary, window, cx);
+    }
+
+    /// Delete the selected text, or the text from the cursor to the `target` offset of the cursor if nothing is selected.
+    pub(super) fn delete_to(
+        &mut self,
+        target: impl Fn(&Self, usize) -> usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.selections.is_empty() {
+            self.delete_in_selections(target, window, cx);
+        } else {
+            if self.selected_range.is_empty() {
+                self.select_to(target(self, self.cursor_offset()), window, cx)
+            }
+            self.replace_text_in_range(None, "", window, cx);
         }
-        self.replace_text_in_range(None, "", window, cx);
         self.pause_blink_cursor(cx);
     }
 
@@ -788,21 +1079,44 @@ impl TextInput {
     }
 
     fn enter(&mut self, _: &Enter, window: &mut Window, cx: &mut Context<Self>) {
+        if self.accept_completion(window, cx) {
+            return;
+        }
+
         if self.is_multi_line() {
             let is_eof = self.selected_range.end == self.text.len();
+            let has_multiple_cursors = !self.selections.is_empty();
             self.replace_text_in_range(None, "\n", window, cx);
 
             // Move cursor to the start of the next line
-            let mut new_offset = self.next_boundary(self.cursor_offset()) - 1;
-            if is_eof {
-                new_offset += 1;
+            if !has_multiple_cursors {
+                let mut new_offset = self.next_boundary(self.cursor_offset()) - 1;
+                if is_eof {
+                    new_offset += 1;
+                }
+                self.move_to(new_offset, window, cx);
             }
-            self.move_to(new_offset, window, cx);
         }
 
         cx.emit(InputEvent::PressEnter);
     }
 
+    fn escape(&mut self, _: &Escape, _: &mut Window, cx: &mut Context<Self>) {
+        if self.completion_menu.is_some() {
+            self.hide_completions(cx);
+            return;
+        }
+
+        if !self.selections.is_empty() {
+            self.selections.clear();
+            cx.notify();
+            return;
+        }
+
+        // Propagate the event to the parent view, for example to the Modal to support ESC to close.
+        cx.propagate();
+    }
+
     fn clean(&mut self, _: &ClickEvent, window: &mut Window, cx: &mut Context<Self>) {
         self.replace_text("", window, cx);
     }
@@ -815,6 +1129,13 @@ impl TextInput {
     ) {
         self.is_selecting = true;
         let offset = self.index_for_mouse_position(event.position, window, cx);
+        // Alt + drag to select columns
+        if self.is_multi_line() && event.modifiers.alt {
+            self.column_selection_anchor = Some(event.position);
+            self.move_to(offset, window, cx);
+            return;
+        }
+
         // Double click to select word
         if event.button == MouseButton::Left && event.click_count == 2 {
             self.select_word(offset, window, cx);
@@ -831,6 +1152,7 @@ impl TextInput {
     fn on_mouse_up(&mut self, _: &MouseUpEvent, _window: &mut Window, _cx: &mut Context<Self>) {
         self.is_selecting = false;
         self.selected_word_range = None;
+        self.column_selection_anchor = None;
     }
 
     fn on_scroll_wheel(
@@ -861,21 +1183,32 @@ impl TextInput {
         window.show_character_palette();
     }
 
+    /// Returns the selected text, the text of multiple selections is joined by `\n`.
+    fn selected_text(&self) -> String {
+        let (selections, _) = self.all_selections();
+        selections
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| self.text.slice(range))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     fn copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
-        if self.selected_range.is_empty() {
+        let selected_text = self.selected_text();
+        if selected_text.is_empty() {
             return;
         }
 
-        let selected_text = self.text[self.selected_range.clone()].to_string();
         cx.write_to_clipboard(ClipboardItem::new_string(selected_text));
     }
 
     fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
-        if self.selected_range.is_empty() {
+        let selected_text = self.selected_text();
+        if selected_text.is_empty() {
             return;
         }
 
-        let selected_text = self.text[self.selected_range.clone()].to_string();
         cx.write_to_clipboard(ClipboardItem::new_string(selected_text));
         self.replace_text_in_range(None, "", window, cx);
     }
@@ -887,6 +1220,21 @@ impl TextInput {
                 new_text = new_text.replace('\n', "");
             }
 
+            // Paste one line to each cursor, if the lines count matches the cursors count.
+            if !self.selections.is_empty() && self.marked_range.is_none() {
+                let (selections, primary_ix) = self.all_selections();
+                let lines = new_text.split('\n').collect::<Vec<_>>();
+                if lines.len() == selections.len() {
+                    let edits = selections
+                        .into_iter()
+                        .zip(lines)
+                        .map(|(range, line)| (range, line.to_string()))
+                        .collect();
+                    self.replace_selections(edits, primary_ix, window, cx);
+                    return;
+                }
+            }
+
             self.replace_text_in_range(None, &new_text, window, cx);
         }
     }
@@ -943,8 +1291,10 @@ impl TextInput {
     /// The offset is the UTF-8 offset.
     ///
     /// Ensure the offset use self.next_boundary or self.previous_boundary to get the correct offset.
-    fn move_to(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn move_to(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
         self.selected_range = offset..offset;
+        self.selections.clear();
+        self.hide_completions(cx);
         self.pause_blink_cursor(cx);
         self.update_preferred_x_offset(cx);
         cx.notify()
@@ -1057,7 +1407,7 @@ impl TextInput {
             return 0;
         }
 
-        let (Some(bounds), Some(lines)) = (self.last_bounds.as_ref(), self.last_layout.as_ref())
+        let (Some(bounds), Some(layout)) = (self.last_bounds.as_ref(), self.last_layout.as_ref())
         else {
             return 0;
         };
@@ -1076,10 +1426,10 @@ impl TextInput {
         // - included the scroll offset.
         let inner_position = position - bounds.origin;
 
-        let mut index = 0;
-        let mut y_offset = px(0.);
+        let mut index = layout.offset;
+        let mut y_offset = layout.top;
 
-        for line in lines.iter() {
+        for line in layout.lines.iter() {
             let line_origin = self.line_origin_with_y_offset(&mut y_offset, &line, line_height);
             let pos = inner_position - line_origin;
             let closest_index = self.closest_index_for_x(line, pos.x);
@@ -1116,11 +1466,8 @@ impl TextInput {
             index += 1;
         }
 
-        if index > self.text.len() {
-            self.text.len()
-        } else {
-            index
-        }
+        // Only the laid out rows can be hit.
+        index.min(layout.end_offset()).min(self.text.len())
     }
 
     /// Returns a y offsetted point for the line origin.
@@ -1149,7 +1496,8 @@ impl TextInput {
     /// The offset is the UTF-8 offset.
     ///
     /// Ensure the offset use self.next_boundary or self.previous_boundary to get the correct offset.
-    fn select_to(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_to(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
+        self.selections.clear();
         if self.selection_reversed {
             self.selected_range.start = offset
         } else {
@@ -1179,40 +1527,9 @@ impl TextInput {
     /// Select the word at the given offset.
     ///
     /// The offset is the UTF-8 offset.
-    fn select_word(&mut self, offset: usize, window: &mut Window, cx: &mut Context<Self>) {
-        fn is_word(c: char) -> bool {
-            c.is_alphanumeric() || matches!(c, '_')
-        }
-
-        let mut start = self.offset_to_utf16(offset);
-        let mut end = start;
-        let prev_text = self
-            .text_for_range(0..start, &mut None, window, cx)
-            .unwrap_or_default();
-        let next_text = self
-            .text_for_range(end..self.text.len(), &mut None, window, cx)
-            .unwrap_or_default();
-
-        let prev_chars = prev_text.chars().rev().peekable();
-        let next_chars = next_text.chars().peekable();
-
-        for (_, c) in prev_chars.enumerate() {
-            if !is_word(c) {
-                break;
-            }
-
-            start -= c.len_utf16();
-        }
-
-        for (_, c) in next_chars.enumerate() {
-            if !is_word(c) {
-                break;
-            }
-
-            end += c.len_utf16();
-        }
-
-        self.selected_range = self.range_from_utf16(&(start..end));
+    fn select_word(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
+        self.selected_range = self.word_range(offset);
+        self.selections.clear();
         self.selected_word_range = Some(self.selected_range.clone());
         cx.notify()
     }
@@ -1220,40 +1537,19 @@ impl TextInput {
     fn unselect(&mut self, _: &mut Window, cx: &mut Context<Self>) {
         let offset = self.next_boundary(self.cursor_offset());
         self.selected_range = offset..offset;
+        self.selections.clear();
         cx.notify()
     }
 
     fn offset_from_utf16(&self, offset: usize) -> usize {
-        let mut utf8_offset = 0;
-        let mut utf16_count = 0;
-
-        for ch in self.text.chars() {
-            if utf16_count >= offset {
-                break;
-            }
-            utf16_count += ch.len_utf16();
-            utf8_offset += ch.len_utf8();
-        }
-
-        utf8_offset
+        self.text.offset_from_utf16(offset)
     }
 
     fn offset_to_utf16(&self, offset: usize) -> usize {
-        let mut utf16_offset = 0;
-        let mut utf8_count = 0;
-
-        for ch in self.text.chars() {
-            if utf8_count >= offset {
-                break;
-            }
-            utf8_count += ch.len_utf8();
-            utf16_offset += ch.len_utf16();
-        }
-
-        utf16_offset
+        self.text.offset_to_utf16(offset)
     }
 
-    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+    pub(super) fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
         self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
     }
 
@@ -1262,18 +1558,11 @@ impl TextInput {
     }
 
     fn previous_boundary(&self, offset: usize) -> usize {
-        self.text
-            .grapheme_indices(true)
-            .rev()
-            .find_map(|(idx, _)| (idx < offset).then_some(idx))
-            .unwrap_or(0)
+        self.text.previous_boundary(offset)
     }
 
     fn next_boundary(&self, offset: usize) -> usize {
-        self.text
-            .grapheme_indices(true)
-            .find_map(|(idx, _)| (idx > offset).then_some(idx))
-            .unwrap_or(self.text.len())
+        self.text.next_boundary(offset)
     }
 
     /// Returns the true to let InputElement to render cursor, when Input is focused and current BlinkCursor is visible.
@@ -1290,13 +1579,14 @@ impl TextInput {
 
     fn on_blur(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.unselect(window, cx);
+        self.hide_completions(cx);
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.stop(cx);
         });
         cx.emit(InputEvent::Blur);
     }
 
-    fn pause_blink_cursor(&mut self, cx: &mut Context<Self>) {
+    pub(super) fn pause_blink_cursor(&mut self, cx: &mut Context<Self>) {
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.pause(cx);
         });
@@ -1333,26 +1623,317 @@ impl TextInput {
             return;
         }
 
+        if let Some(anchor) = self.column_selection_anchor {
+            self.select_columns(anchor, event.position, window, cx);
+            return;
+        }
+
         let offset = self.index_for_mouse_position(event.position, window, cx);
         self.select_to(offset, window, cx);
     }
 
-    fn is_valid_input(&self, new_text: &str) -> bool {
-        if new_text.is_empty() {
+    /// Check if the text is valid after applying the `edits`.
+    ///
+    /// The edits must be sorted by the range start and must not overlap.
+    fn is_valid_input(&self, edits: &[(Range<usize>, &str)]) -> bool {
+        if self.validate.is_none() && self.pattern.is_none() {
+            return true;
+        }
+
+        let mut pending_text = String::new();
+        let mut offset = 0;
+        for (range, new_text) in edits {
+            pending_text.push_str(&self.text.slice(offset..range.start));
+            pending_text.push_str(new_text);
+            offset = range.end;
+        }
+        pending_text.push_str(&self.text.slice(offset..self.text.len()));
+        if pending_text.is_empty() {
             return true;
         }
 
         if let Some(validate) = &self.validate {
-            if !validate(new_text) {
+            if !validate(&pending_text) {
                 return false;
             }
         }
 
         self.pattern
             .as_ref()
-            .map(|p| p.is_match(new_text))
+            .map(|p| p.is_match(&pending_text))
             .unwrap_or(true)
     }
+
+    /// Returns all the selections (the `selected_range` included) sorted by the start,
+    /// with the overlapping selections merged, and the index of the `selected_range` in them.
+    fn all_selections(&self) -> (Vec<Range<usize>>, usize) {
+        let mut ranges = self.selections.clone();
+        ranges.push(self.selected_range.clone());
+        ranges.sort_by_key(|range| (range.start, range.end));
+
+        let mut selections: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        let mut primary_ix = 0;
+        for range in ranges {
+            let is_primary = range == self.selected_range;
+            match selections.last_mut() {
+                Some(last) if range.start < last.end || range == *last => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => selections.push(range),
+            }
+            if is_primary {
+                primary_ix = selections.len() - 1;
+            }
+        }
+
+        (selections, primary_ix)
+    }
+
+    /// Replace the text of each selection, the `edits` must be sorted and not overlapping.
+    ///
+    /// The cursors are moved to the end of the each new text,
+    /// the cursor of `edits[primary_ix]` will be the `selected_range`.
+    fn replace_selections(
+        &mut self,
+        edits: Vec<(Range<usize>, String)>,
+        primary_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.disabled || edits.is_empty() {
+            return;
+        }
+
+        let pending_edits = edits
+            .iter()
+            .map(|(range, new_text)| (range.clone(), new_text.as_str()))
+            .collect::<Vec<_>>();
+        if !self.is_valid_input(&pending_edits) {
+            return;
+        }
+
+        // Apply the edits from the end, to keep the ranges of the previous edits unchanged.
+        //
+        // All the changes are pushed to the history in the same group, to undo them at once.
+        for (range, new_text) in edits.iter().rev() {
+            self.push_history(range, new_text, window, cx);
+            self.apply_text_change(range, new_text);
+        }
+
+        let mut delta = 0isize;
+        let mut cursors = Vec::with_capacity(edits.len());
+        for (range, new_text) in &edits {
+            let offset = (range.start as isize + delta) as usize + new_text.len();
+            cursors.push(offset..offset);
+            delta += new_text.len() as isize - range.len() as isize;
+        }
+
+        self.selected_range = cursors.remove(primary_ix.min(cursors.len() - 1));
+        self.selections = cursors;
+        self.marked_range.take();
+        self.update_preferred_x_offset(cx);
+        self.update_search_matches(cx);
+        cx.emit(InputEvent::Change(self.raw_value()));
+        cx.notify();
+    }
+
+    /// Delete the text of all the selections, the empty selections are
+    /// extended to the `target` offset of their cursors first.
+    fn delete_in_selections(
+        &mut self,
+        target: impl Fn(&Self, usize) -> usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (selections, primary_ix) = self.all_selections();
+
+        let mut edits: Vec<(Range<usize>, String)> = Vec::with_capacity(selections.len());
+        for range in selections {
+            let mut range = if !range.is_empty() {
+                range
+            } else {
+                let offset = target(self, range.start);
+                offset.min(range.start)..offset.max(range.start)
+            };
+            // Avoid overlapping with the previous extended selection.
+            if let Some((last, _)) = edits.last() {
+                range.start = range.start.max(last.end);
+            }
+            edits.push((range, String::new()));
+        }
+
+        self.replace_selections(edits, primary_ix, window, cx);
+    }
+
+    /// Select the columns in the rectangle from `anchor` to `position` (window coordinates), one selection per visual line.
+    fn select_columns(
+        &mut self,
+        anchor: Point<Pixels>,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bounds) = self.last_bounds else {
+            return;
+        };
+
+        let line_height = self.last_line_height;
+        let row_for = |y: Pixels| ((y - bounds.origin.y) / line_height).max(0.) as usize;
+        let anchor_row = row_for(anchor.y);
+        let row = row_for(position.y);
+
+        let mut ranges = vec![];
+        for row in anchor_row.min(row)..=anchor_row.max(row) {
+            let y = bounds.origin.y + line_height * (row as f32 + 0.5);
+            let start = self.index_for_mouse_position(point(anchor.x, y), window, cx);
+            let end = self.index_for_mouse_position(point(position.x, y), window, cx);
+            ranges.push(start.min(end)..start.max(end));
+        }
+
+        // The selection under the mouse is the primary selection.
+        let primary = if row >= anchor_row {
+            ranges.pop()
+        } else {
+            Some(ranges.remove(0))
+        };
+        let Some(primary) = primary else {
+            return;
+        };
+
+        self.selected_range = primary;
+        self.selection_reversed = position.x < anchor.x;
+        self.selections = ranges;
+        cx.notify();
+    }
+
+    fn add_cursor_above(
+        &mut self,
+        _: &AddCursorAbove,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.add_cursor_vertically(-1., window, cx);
+    }
+
+    fn add_cursor_below(
+        &mut self,
+        _: &AddCursorBelow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.add_cursor_vertically(1., window, cx);
+    }
+
+    /// Add a cursor on the visual line above (`direction` = -1) the first
+    /// selection or below (`direction` = 1) the last selection.
+    fn add_cursor_vertically(
+        &mut self,
+        direction: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(layout), Some(bounds)) = (self.last_layout.as_ref(), self.last_bounds) else {
+            return;
+        };
+
+        let (selections, _) = self.all_selections();
+        let range = if direction < 0. {
+            selections.first()
+        } else {
+            selections.last()
+        };
+        let Some(range) = range else {
+            return;
+        };
+        let offset = if self.selection_reversed {
+            range.start
+        } else {
+            range.end
+        };
+
+        let line_height = self.last_line_height;
+        let (_, _, Some(pos)) = self.line_and_position_for_offset(offset, layout, line_height)
+        else {
+            return;
+        };
+
+        let y = pos.y + line_height * direction;
+        if y < px(0.) || y >= self.scroll_size.height {
+            return;
+        }
+
+        let position = bounds.origin + point(pos.x, y + line_height / 2.);
+        let new_offset = self.index_for_mouse_position(position, window, cx);
+        if selections
+            .iter()
+            .any(|range| range.start <= new_offset && new_offset <= range.end)
+        {
+            return;
+        }
+
+        self.selections.push(self.selected_range.clone());
+        self.selected_range = new_offset..new_offset;
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+
+    /// Select the word under the cursor, or add the next occurrence of the selected text as a new selection.
+    fn select_next_occurrence(
+        &mut self,
+        _: &SelectNextOccurrence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_range.is_empty() {
+            self.select_word(self.cursor_offset(), window, cx);
+            self.selected_word_range = None;
+            return;
+        }
+
+        let query = self.text.slice(self.selected_range.clone());
+        let text = self.text();
+        let (selections, _) = self.all_selections();
+
+        // Search after the primary selection, and wrap around to the start.
+        let start = self.selected_range.end;
+        let next_range = text[start..]
+            .match_indices(&query)
+            .map(|(ix, _)| start + ix)
+            .chain(
+                text.match_indices(&query)
+                    .map(|(ix, _)| ix)
+                    .take_while(|ix| *ix < start),
+            )
+            .map(|ix| ix..ix + query.len())
+            .find(|range| {
+                !selections
+                    .iter()
+                    .any(|selection| selection.start < range.end && range.start < selection.end)
+            });
+
+        if let Some(range) = next_range {
+            self.selections.push(self.selected_range.clone());
+            self.selected_range = range;
+            self.selection_reversed = false;
+            cx.notify();
+        }
+    }
+
+    /// Replace the text in the `range` with `new_text`, and invalidate the line caches.
+    fn apply_text_change(&mut self, range: &Range<usize>, new_text: &str) {
+        let row = self.text.offset_to_row(range.start);
+        let removed_rows = self.text.offset_to_row(range.end) - row;
+        let inserted_rows = new_text.matches('\n').count();
+        self.highlight_cache
+            .invalidate(row, removed_rows, inserted_rows);
+        self.line_layouts
+            .invalidate(row, removed_rows, inserted_rows);
+        adjust_diagnostics(&mut self.diagnostics, range, new_text.len());
+        self.hovered_diagnostic = None;
+
+        self.text.replace(range.clone(), new_text);
+        self.text_snapshot.take();
+    }
 }
 
 impl Sizable for TextInput {
@@ -1372,7 +1953,7 @@ impl ViewInputHandler for TextInput {
     ) -> Option<String> {
         let range = self.range_from_utf16(&range_utf16);
         adjusted_range.replace(self.range_to_utf16(&range));
-        Some(self.text[range].to_string())
+        Some(self.text.slice(range))
     }
 
     fn selected_text_range(
@@ -1412,24 +1993,55 @@ impl ViewInputHandler for TextInput {
             return;
         }
 
+        // Insert the text to all the cursors.
+        if range_utf16.is_none() && self.marked_range.is_none() && !self.selections.is_empty() {
+            let (selections, primary_ix) = self.all_selections();
+            let edits = selections
+                .into_iter()
+                .map(|range| (range, new_text.to_string()))
+                .collect();
+            self.replace_selections(edits, primary_ix, window, cx);
+            return;
+        }
+
         let range = range_utf16
             .as_ref()
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
+        self.selections.clear();
+
+        // Format the text by the mask, the literals are inserted automatically.
+        let (range, new_text, cursor) = match self.mask_pattern.as_ref() {
+            Some(mask) => mask.edit(&self.text(), &range, new_text),
+            None => (
+                range.clone(),
+                new_text.to_string(),
+                range.start + new_text.len(),
+            ),
+        };
+        let new_text = new_text.as_str();
+        if self.mask_pattern.is_some() && range.is_empty() && new_text.is_empty() {
+            self.selected_range = cursor..cursor;
+            cx.notify();
+            return;
+        }
 
-        let pending_text: SharedString =
-            (self.text[0..range.start].to_owned() + new_text + &self.text[range.end..]).into();
-        if !self.is_valid_input(&pending_text) {
+        if !self.is_valid_input(&[(range.clone(), new_text)]) {
             return;
         }
 
         self.push_history(&range, new_text, window, cx);
-        self.text = pending_text;
-        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
+        self.apply_text_change(&range, new_text);
+        self.selected_range = cursor..cursor;
         self.marked_range.take();
         self.update_preferred_x_offset(cx);
-        cx.emit(InputEvent::Change(self.text.clone()));
+        self.update_search_matches(cx);
+        cx.emit(InputEvent::Change(self.raw_value()));
+        // Query the completions when typing, or update the shown completions.
+        if !self.history.ignore && (!new_text.is_empty() || self.completion_menu.is_some()) {
+            self.update_completions(window, cx);
+        }
         cx.notify();
     }
 
@@ -1445,26 +2057,33 @@ impl ViewInputHandler for TextInput {
             return;
         }
 
+        // The masked text can't be composed, it must be formatted.
+        if self.mask_pattern.is_some() {
+            self.replace_text_in_range(range_utf16, new_text, window, cx);
+            return;
+        }
+
         let range = range_utf16
             .as_ref()
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
-        let pending_text: SharedString =
-            (self.text[0..range.start].to_owned() + new_text + &self.text[range.end..]).into();
-        if !self.is_valid_input(&pending_text) {
+        if !self.is_valid_input(&[(range.clone(), new_text)]) {
             return;
         }
+        // The IME composition only works on the primary cursor.
+        self.selections.clear();
 
         self.push_history(&range, new_text, window, cx);
-        self.text = pending_text;
+        self.apply_text_change(&range, new_text);
         self.marked_range = Some(range.start..range.start + new_text.len());
         self.selected_range = new_selected_range_utf16
             .as_ref()
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .map(|new_range| new_range.start + range.start..new_range.end + range.end)
             .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
-        cx.emit(InputEvent::Change(self.text.clone()));
+        self.update_search_matches(cx);
+        cx.emit(InputEvent::Change(self.raw_value()));
         cx.notify();
     }
 
@@ -1478,28 +2097,29 @@ impl ViewInputHandler for TextInput {
         _cx: &mut Context<Self>,
     ) -> Option<Bounds<Pixels>> {
         let line_height = self.last_line_height;
-        let lines = self.last_layout.as_ref()?;
+        let layout = self.last_layout.as_ref()?;
         let range = self.range_from_utf16(&range_utf16);
 
         let mut start_origin = None;
         let mut end_origin = None;
-        let mut y_offset = px(0.);
-        let mut index_offset = 0;
 
-        for line in lines.iter() {
-            if let Some(p) = line.position_for_index(range.start - index_offset, line_height) {
-                start_origin = Some(p + point(px(0.), y_offset));
+        for (line_start, line_top, line) in layout.lines_with_offsets(line_height) {
+            let (Some(start), Some(end)) = (
+                range.start.checked_sub(line_start),
+                range.end.checked_sub(line_start),
+            ) else {
+                break;
+            };
+            if let Some(p) = line.position_for_index(start, line_height) {
+                start_origin = Some(p + point(px(0.), line_top));
             }
-            if let Some(p) = line.position_for_index(range.end - index_offset, line_height) {
-                end_origin = Some(p + point(px(0.), y_offset));
+            if let Some(p) = line.position_for_index(end, line_height) {
+                end_origin = Some(p + point(px(0.), line_top));
             }
 
-            y_offset += line.size(line_height).height;
             if start_origin.is_some() && end_origin.is_some() {
                 break;
             }
-
-            index_offset += line.len();
         }
 
         Some(Bounds::from_corners(
@@ -1534,16 +2154,43 @@ impl Render for TextInput {
                     .on_action(cx.listener(Self::delete_to_beginning_of_line))
                     .on_action(cx.listener(Self::delete_to_end_of_line))
                     .on_action(cx.listener(Self::enter))
+                    .on_action(cx.listener(Self::delete_to_previous_word_start))
+                    .on_action(cx.listener(Self::delete_to_next_word_end))
+                    .on_action(cx.listener(Self::delete_to_previous_subword_start))
+                    .on_action(cx.listener(Self::delete_to_next_subword_end))
             })
             .on_action(cx.listener(Self::left))
             .on_action(cx.listener(Self::right))
             .on_action(cx.listener(Self::select_left))
             .on_action(cx.listener(Self::select_right))
+            .on_action(cx.listener(Self::move_to_previous_word))
+            .on_action(cx.listener(Self::move_to_next_word))
+            .on_action(cx.listener(Self::move_to_previous_subword))
+            .on_action(cx.listener(Self::move_to_next_subword))
+            .on_action(cx.listener(Self::select_to_previous_word))
+            .on_action(cx.listener(Self::select_to_next_word))
+            .on_action(cx.listener(Self::select_to_previous_subword))
+            .on_action(cx.listener(Self::select_to_next_subword))
+            .on_action(cx.listener(Self::up))
+            .on_action(cx.listener(Self::down))
+            .on_action(cx.listener(Self::escape))
+            .when(self.completion_provider.is_some(), |this| {
+                this.on_action(cx.listener(Self::show_completions))
+            })
             .when(self.multi_line, |this| {
-                this.on_action(cx.listener(Self::up))
-                    .on_action(cx.listener(Self::down))
-                    .on_action(cx.listener(Self::select_up))
+                this.on_action(cx.listener(Self::select_up))
                     .on_action(cx.listener(Self::select_down))
+                    .on_action(cx.listener(Self::move_to_previous_paragraph))
+                    .on_action(cx.listener(Self::move_to_next_paragraph))
+                    .on_action(cx.listener(Self::select_to_previous_paragraph))
+                    .on_action(cx.listener(Self::select_to_next_paragraph))
+                    .on_action(cx.listener(Self::find))
+                    .on_action(cx.listener(Self::find_and_replace))
+                    .on_action(cx.listener(Self::find_next))
+                    .on_action(cx.listener(Self::find_previous))
+                    .on_action(cx.listener(Self::add_cursor_above))
+                    .on_action(cx.listener(Self::add_cursor_below))
+                    .on_action(cx.listener(Self::select_next_occurrence))
             })
             .on_action(cx.listener(Self::select_all))
             .on_action(cx.listener(Self::select_to_start_of_line))
@@ -1629,5 +2276,10 @@ impl Render for TextInput {
                     this
                 }
             })
+            .when(self.is_multi_line(), |this| {
+                this.relative().children(self.render_search_bar(cx))
+            })
+            .children(self.render_completion_menu(window, cx))
+            .children(self.render_diagnostic_tooltip(window, cx))
     }
 }
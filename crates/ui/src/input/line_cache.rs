@@ -0,0 +1,104 @@
+/// A cache of per-line items (e.g. highlights, layouts) indexed by row.
+///
+/// When the text is changed, only the items of the changed rows are invalidated,
+/// the rows after the change are shifted to keep the cache in sync with the text.
+pub(super) struct LineCache<T> {
+    lines: Vec<Option<T>>,
+}
+
+impl<T> Default for LineCache<T> {
+    fn default() -> Self {
+        Self { lines: vec![] }
+    }
+}
+
+impl<T> LineCache<T> {
+    /// Drop all the cached items.
+    pub(super) fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Resize the cache to the given rows count, the new rows are not cached.
+    pub(super) fn resize(&mut self, rows: usize) {
+        self.lines.resize_with(rows, || None);
+    }
+
+    /// Invalidate the rows affected by a change.
+    ///
+    /// - `row`: the row where the change starts.
+    /// - `removed_rows`: the number of `\n` removed by the change.
+    /// - `inserted_rows`: the number of `\n` inserted by the change.
+    pub(super) fn invalidate(&mut self, row: usize, removed_rows: usize, inserted_rows: usize) {
+        if self.lines.is_empty() {
+            return;
+        }
+
+        let end_row = (row + removed_rows + 1).min(self.lines.len());
+        let row = row.min(end_row);
+        self.lines.splice(
+            row..end_row,
+            std::iter::repeat_with(|| None).take(inserted_rows + 1),
+        );
+    }
+
+    pub(super) fn get(&self, row: usize) -> Option<&T> {
+        self.lines.get(row).and_then(|item| item.as_ref())
+    }
+
+    pub(super) fn insert(&mut self, row: usize, item: T) {
+        if row >= self.lines.len() {
+            self.resize(row + 1);
+        }
+        self.lines[row] = Some(item);
+    }
+
+    pub(super) fn get_or_insert_with(&mut self, row: usize, f: impl FnOnce() -> T) -> &T {
+        if row >= self.lines.len() {
+            self.resize(row + 1);
+        }
+        self.lines[row].get_or_insert_with(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached_rows(cache: &LineCache<()>) -> Vec<bool> {
+        cache.lines.iter().map(|l| l.is_some()).collect()
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let mut cache = LineCache::default();
+        for row in 0..3 {
+            cache.insert(row, ());
+        }
+
+        // "a\nb\nc": replace "b" with "x\ny", row 1 is split into 2 rows.
+        cache.invalidate(1, 0, 1);
+        assert_eq!(cached_rows(&cache), vec![true, false, false, true]);
+
+        // "a\nx\ny\nc": remove "\nx\n", rows 0..=2 are merged into 1 row.
+        let mut cache = LineCache::default();
+        for row in 0..4 {
+            cache.insert(row, ());
+        }
+        cache.invalidate(0, 2, 0);
+        assert_eq!(cached_rows(&cache), vec![false, true]);
+
+        let mut cache = LineCache::default();
+        cache.invalidate(0, 0, 0);
+        assert_eq!(cached_rows(&cache), vec![]);
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut cache = LineCache::default();
+        assert_eq!(cache.get(2), None);
+        assert_eq!(*cache.get_or_insert_with(2, || 1), 1);
+        assert_eq!(*cache.get_or_insert_with(2, || 2), 1);
+        assert_eq!(cache.get(2), Some(&1));
+        assert_eq!(cache.get(0), None);
+    }
+}
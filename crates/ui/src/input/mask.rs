@@ -0,0 +1,274 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaskToken {
+    /// `9`, a digit.
+    Digit,
+    /// `A`, a letter.
+    Letter,
+    /// `*`, a letter or digit.
+    Alphanumeric,
+    Literal(char),
+}
+
+impl MaskToken {
+    fn is_slot(&self) -> bool {
+        !matches!(self, Self::Literal(_))
+    }
+
+    fn accept(&self, c: char) -> bool {
+        match self {
+            Self::Digit => c.is_ascii_digit(),
+            Self::Letter => c.is_alphabetic(),
+            Self::Alphanumeric => c.is_alphanumeric(),
+            Self::Literal(_) => false,
+        }
+    }
+}
+
+/// A format mask of the [`super::TextInput`], for example `(999) 999-9999`, `99/99/9999` or `AAA-0000`.
+///
+/// - `9` or `0` is a digit.
+/// - `A` is a letter.
+/// - `*` is a letter or digit.
+/// - `\` escapes the next character to a literal.
+/// - The other characters are literals, they are inserted automatically when typing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskPattern {
+    pattern: SharedString,
+    tokens: Vec<MaskToken>,
+    placeholder_char: char,
+}
+
+impl From<&str> for MaskPattern {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl MaskPattern {
+    pub fn new(pattern: impl Into<SharedString>) -> Self {
+        let pattern: SharedString = pattern.into();
+        let mut tokens = vec![];
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                '9' | '0' => MaskToken::Digit,
+                'A' => MaskToken::Letter,
+                '*' => MaskToken::Alphanumeric,
+                '\\' => match chars.next() {
+                    Some(c) => MaskToken::Literal(c),
+                    None => break,
+                },
+                c => MaskToken::Literal(c),
+            });
+        }
+
+        Self {
+            pattern,
+            tokens,
+            placeholder_char: '_',
+        }
+    }
+
+    /// Set the character to display the empty slots in the placeholder, default is `_`.
+    pub fn placeholder_char(mut self, placeholder_char: char) -> Self {
+        self.placeholder_char = placeholder_char;
+        self
+    }
+
+    /// Returns the mask pattern.
+    pub fn pattern(&self) -> &SharedString {
+        &self.pattern
+    }
+
+    /// Format the raw value by the mask, the characters not accepted by the slots are skipped.
+    pub fn format(&self, raw: &str) -> String {
+        self.format_chars(raw.chars()).0
+    }
+
+    /// Returns the raw value of the formatted `text`, the literals are removed.
+    pub fn unformat(&self, text: &str) -> String {
+        text.chars()
+            .zip(self.tokens.iter())
+            .filter(|(_, token)| token.is_slot())
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Returns true if all the slots are filled in the formatted `text`.
+    pub fn is_complete(&self, text: &str) -> bool {
+        text.chars().count() == self.tokens.len()
+    }
+
+    /// Returns the part of the mask after the formatted `text`, to display as the placeholder.
+    pub(super) fn placeholder(&self, text: &str) -> String {
+        self.tokens
+            .iter()
+            .skip(text.chars().count())
+            .map(|token| match token {
+                MaskToken::Literal(c) => *c,
+                _ => self.placeholder_char,
+            })
+            .collect()
+    }
+
+    /// Format the chars, returns the formatted text, and the (start, end) offsets of the filled slots.
+    ///
+    /// The literals before a slot are only included when the slot is filled.
+    fn format_chars(&self, chars: impl Iterator<Item = char>) -> (String, Vec<(usize, usize)>) {
+        let mut chars = chars.peekable();
+        let mut text = String::new();
+        let mut slots = vec![];
+        let mut literals = String::new();
+        for token in self.tokens.iter() {
+            match token {
+                MaskToken::Literal(c) => literals.push(*c),
+                token => {
+                    // Skip the chars not accepted by the slot, and the typed literals.
+                    while chars.next_if(|c| !token.accept(*c)).is_some() {}
+                    let Some(c) = chars.next() else {
+                        return (text, slots);
+                    };
+
+                    text.push_str(&literals);
+                    literals.clear();
+                    slots.push((text.len(), text.len() + c.len_utf8()));
+                    text.push(c);
+                }
+            }
+        }
+
+        // All the slots are filled, the trailing literals are included.
+        if !slots.is_empty() {
+            text.push_str(&literals);
+        }
+
+        (text, slots)
+    }
+
+    /// Returns the index of the raw value at the `offset` of the formatted `text`.
+    fn raw_index(&self, text: &str, offset: usize) -> usize {
+        text.char_indices()
+            .take_while(|(ix, _)| *ix < offset)
+            .zip(self.tokens.iter())
+            .filter(|(_, token)| token.is_slot())
+            .count()
+    }
+
+    /// Replace the `range` of the formatted `text` with the `new_text`.
+    ///
+    /// Returns the minimal changed range of the `text`, the new text of that range, and the new cursor offset.
+    pub(super) fn edit(
+        &self,
+        text: &str,
+        range: &Range<usize>,
+        new_text: &str,
+    ) -> (Range<usize>, String, usize) {
+        let start = self.raw_index(text, range.start);
+        let end = self.raw_index(text, range.end);
+
+        // Only the literals are deleted, just move the cursor before them.
+        if new_text.is_empty() && start == end {
+            return (range.start..range.start, String::new(), range.start);
+        }
+
+        let raw = self.unformat(text).chars().collect::<Vec<_>>();
+        let prefix = raw[..start].iter().copied().chain(new_text.chars());
+        let (formatted, slots) =
+            self.format_chars(prefix.clone().chain(raw[end..].iter().copied()));
+
+        // The cursor is placed after the last slot filled by the prefix and the inserted text.
+        let filled = self.format_chars(prefix).1.len();
+        let cursor = match filled {
+            0 => slots.first().map_or(0, |(start, _)| *start),
+            n => slots[n - 1].1,
+        };
+
+        let prefix_len = text
+            .char_indices()
+            .zip(formatted.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(text.len().min(formatted.len()), |((ix, _), _)| ix);
+        let suffix_len = text[prefix_len..]
+            .chars()
+            .rev()
+            .zip(formatted[prefix_len..].chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum::<usize>();
+
+        (
+            prefix_len..text.len() - suffix_len,
+            formatted[prefix_len..formatted.len() - suffix_len].to_string(),
+            cursor,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply the edit to the `text`, returns the new text and the cursor.
+    fn edit(
+        mask: &MaskPattern,
+        text: &str,
+        range: Range<usize>,
+        new_text: &str,
+    ) -> (String, usize) {
+        let (range, new_text, cursor) = mask.edit(text, &range, new_text);
+        let mut text = text.to_string();
+        text.replace_range(range, &new_text);
+        (text, cursor)
+    }
+
+    #[test]
+    fn test_format() {
+        let mask = MaskPattern::new("(999) 999-9999");
+        assert_eq!(mask.format(""), "");
+        assert_eq!(mask.format("1"), "(1");
+        assert_eq!(mask.format("123"), "(123");
+        assert_eq!(mask.format("1234"), "(123) 4");
+        assert_eq!(mask.format("12a3-4"), "(123) 4");
+        assert_eq!(mask.format("(555) 123-4567"), "(555) 123-4567");
+        assert_eq!(mask.format("555123456789"), "(555) 123-4567");
+        assert_eq!(mask.unformat("(555) 123-4"), "5551234");
+        assert_eq!(mask.placeholder("(555) 1"), "__-____");
+        assert!(!mask.is_complete("(555) 123-456"));
+        assert!(mask.is_complete("(555) 123-4567"));
+
+        let mask = MaskPattern::new("AAA-0000");
+        assert_eq!(mask.format("abc1234"), "abc-1234");
+        assert_eq!(mask.format("1abc"), "abc");
+
+        let mask = MaskPattern::new("\\99%");
+        assert_eq!(mask.format("5"), "95%");
+    }
+
+    #[test]
+    fn test_edit() {
+        let mask = MaskPattern::new("(999) 999-9999");
+
+        // Typing inserts the literals.
+        assert_eq!(edit(&mask, "", 0..0, "5"), ("(5".into(), 2));
+        assert_eq!(edit(&mask, "(555", 4..4, "1"), ("(555) 1".into(), 7));
+        // Invalid chars are ignored.
+        assert_eq!(edit(&mask, "(555", 4..4, "a"), ("(555".into(), 4));
+        // Insert in the middle shifts the following chars.
+        assert_eq!(edit(&mask, "(555) 12", 1..1, "9"), ("(955) 512".into(), 2));
+        // Deleting a literal moves the cursor before it.
+        assert_eq!(edit(&mask, "(555) 12", 5..6, ""), ("(555) 12".into(), 5));
+        // Deleting a digit before a literal.
+        assert_eq!(edit(&mask, "(555) 1", 6..7, ""), ("(555".into(), 4));
+        assert_eq!(edit(&mask, "(555) 12", 3..4, ""), ("(551) 2".into(), 3));
+        assert_eq!(edit(&mask, "(5", 1..2, ""), ("".into(), 0));
+        // Paste a formatted value.
+        assert_eq!(
+            edit(&mask, "", 0..0, "(555) 123-4567"),
+            ("(555) 123-4567".into(), 14)
+        );
+    }
+}
@@ -1,12 +1,26 @@
 mod blink_cursor;
 mod change;
 mod clear_button;
+mod completion;
+mod diagnostics;
 mod element;
+mod highlighter;
 mod input;
+mod line_cache;
+mod mask;
+mod movement;
 mod number_input;
 mod otp_input;
+mod rope;
+mod search;
+mod tag_input;
 
 pub(crate) use clear_button::*;
+pub use completion::{CompletionItem, CompletionProvider};
+pub use diagnostics::{Diagnostic, DiagnosticSeverity};
+pub use highlighter::{ConfigHighlighter, HighlightRun, Highlighter};
 pub use input::*;
-pub use number_input::{NumberInput, NumberInputEvent, StepAction};
+pub use mask::MaskPattern;
+pub use number_input::{NumberFormat, NumberInput, NumberInputEvent, StepAction};
 pub use otp_input::*;
+pub use tag_input::{TagInput, TagInputEvent};
@@ -0,0 +1,424 @@
+use std::ops::Range;
+
+use gpui::{Context, Window};
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use super::{
+    DeleteToNextSubwordEnd, DeleteToNextWordEnd, DeleteToPreviousSubwordStart,
+    DeleteToPreviousWordStart, MoveToNextParagraph, MoveToNextSubword, MoveToNextWord,
+    MoveToPreviousParagraph, MoveToPreviousSubword, MoveToPreviousWord, SelectToNextParagraph,
+    SelectToNextSubword, SelectToNextWord, SelectToPreviousParagraph, SelectToPreviousSubword,
+    SelectToPreviousWord, TextInput,
+};
+
+fn is_word(text: &str) -> bool {
+    text.chars().any(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Split a word into the subwords of the camelCase and snake_case, the `_` are not included.
+fn subword_ranges(word: &str, offset: usize, ranges: &mut Vec<Range<usize>>) {
+    let chars = word.char_indices().collect::<Vec<_>>();
+    let mut start = None;
+    for (i, &(ix, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if let Some(start) = start.take() {
+                ranges.push(offset + start..offset + ix);
+            }
+            continue;
+        }
+
+        let Some(subword_start) = start else {
+            start = Some(ix);
+            continue;
+        };
+
+        let prev = chars[i - 1].1;
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        // fooBar, foo2Bar, HTMLParser
+        let is_boundary = c.is_uppercase()
+            && (prev.is_lowercase()
+                || prev.is_numeric()
+                || (prev.is_uppercase() && next.is_some_and(|c| c.is_lowercase())));
+        if is_boundary {
+            ranges.push(offset + subword_start..offset + ix);
+            start = Some(ix);
+        }
+    }
+
+    if let Some(start) = start {
+        ranges.push(offset + start..offset + word.len());
+    }
+}
+
+/// Returns the ranges of the words (or subwords) in the `line`, the whitespaces are skipped.
+///
+/// The words are split by the Unicode word boundaries (UAX #29).
+fn word_ranges(line: &str, subword: bool) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    for (start, word) in line.split_word_bound_indices() {
+        if word.chars().all(char::is_whitespace) {
+            continue;
+        }
+
+        if subword {
+            subword_ranges(word, start, &mut ranges);
+        } else {
+            ranges.push(start..start + word.len());
+        }
+    }
+
+    ranges
+}
+
+/// Returns the start of the word before the `offset` in the `line`.
+pub(super) fn previous_word_start(line: &str, offset: usize, subword: bool) -> usize {
+    word_ranges(line, subword)
+        .into_iter()
+        .rev()
+        .find(|range| range.start < offset)
+        .map_or(0, |range| range.start)
+}
+
+/// Returns the end of the word after the `offset` in the `line`.
+pub(super) fn next_word_end(line: &str, offset: usize, subword: bool) -> usize {
+    word_ranges(line, subword)
+        .into_iter()
+        .find(|range| range.end > offset)
+        .map_or(line.len(), |range| range.end)
+}
+
+/// Returns the range of the word at the `offset` in the `line`.
+///
+/// If the `offset` is at the end of a word, the word is preferred over the following whitespaces or punctuations.
+pub(super) fn word_range(line: &str, offset: usize) -> Range<usize> {
+    let mut prev = None;
+    for (start, word) in line.split_word_bound_indices() {
+        let range = start..start + word.len();
+        if range.contains(&offset) {
+            return match prev {
+                Some((prev, true)) if start == offset && !is_word(word) => prev,
+                _ => range,
+            };
+        }
+        prev = Some((range, is_word(word)));
+    }
+
+    match prev {
+        Some((range, true)) => range,
+        _ => offset..offset,
+    }
+}
+
+impl TextInput {
+    /// Returns the line range and the text of the line at the `offset`.
+    fn line_at(&self, offset: usize) -> (Range<usize>, String) {
+        let row = self.text.offset_to_row(offset);
+        let range = self.text.line_start_offset(row)..self.text.line_end_offset(row);
+        let line = self.text.slice(range.clone());
+        (range, line)
+    }
+
+    /// Returns the start of the previous word (or subword), the line break is a word.
+    pub(super) fn previous_word_start(&self, offset: usize, subword: bool) -> usize {
+        // The masked text is one word, to not leak the word boundaries of the password.
+        if self.masked {
+            return 0;
+        }
+
+        let (range, line) = self.line_at(offset);
+        if offset == range.start {
+            return self.previous_boundary(offset);
+        }
+
+        range.start + previous_word_start(&line, offset - range.start, subword)
+    }
+
+    /// Returns the end of the next word (or subword), the line break is a word.
+    pub(super) fn next_word_end(&self, offset: usize, subword: bool) -> usize {
+        if self.masked {
+            return self.text.len();
+        }
+
+        let (range, line) = self.line_at(offset);
+        if offset == range.end {
+            return self.next_boundary(offset);
+        }
+
+        range.start + next_word_end(&line, offset - range.start, subword)
+    }
+
+    /// Returns the range of the word at the `offset`.
+    pub(super) fn word_range(&self, offset: usize) -> Range<usize> {
+        if self.masked {
+            return 0..self.text.len();
+        }
+
+        let (range, line) = self.line_at(offset);
+        let word = word_range(&line, offset - range.start);
+        range.start + word.start..range.start + word.end
+    }
+
+    fn is_blank_row(&self, row: usize) -> bool {
+        let range = self.text.line_start_offset(row)..self.text.line_end_offset(row);
+        self.text.slice(range).trim().is_empty()
+    }
+
+    /// Returns the start of the blank line before the paragraph at the `offset`, or the start of the text.
+    fn previous_paragraph_start(&self, offset: usize) -> usize {
+        let row = self.text.offset_to_row(offset);
+        let mut found_non_blank = false;
+        for row in (0..=row).rev() {
+            let blank = self.is_blank_row(row);
+            if found_non_blank && blank {
+                return self.text.line_start_offset(row);
+            }
+            found_non_blank |= !blank;
+        }
+
+        0
+    }
+
+    /// Returns the end of the blank line after the paragraph at the `offset`, or the end of the text.
+    fn next_paragraph_end(&self, offset: usize) -> usize {
+        let row = self.text.offset_to_row(offset);
+        let mut found_non_blank = false;
+        for row in row..self.text.lines_len() {
+            let blank = self.is_blank_row(row);
+            if found_non_blank && blank {
+                return self.text.line_end_offset(row);
+            }
+            found_non_blank |= !blank;
+        }
+
+        self.text.len()
+    }
+
+    pub(super) fn move_to_previous_word(
+        &mut self,
+        _: &MoveToPreviousWord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pause_blink_cursor(cx);
+        let offset = self.previous_word_start(self.cursor_offset(), false);
+        self.move_to(offset, window, cx);
+    }
+
+    pub(super) fn move_to_next_word(
+        &mut self,
+        _: &MoveToNextWord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pause_blink_cursor(cx);
+        let offset = self.next_word_end(self.cursor_offset(), false);
+        self.move_to(offset, window, cx);
+    }
+
+    pub(super) fn move_to_previous_subword(
+        &mut self,
+        _: &MoveToPreviousSubword,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pause_blink_cursor(cx);
+        let offset = self.previous_word_start(self.cursor_offset(), true);
+        self.move_to(offset, window, cx);
+    }
+
+    pub(super) fn move_to_next_subword(
+        &mut self,
+        _: &MoveToNextSubword,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pause_blink_cursor(cx);
+        let offset = self.next_word_end(self.cursor_offset(), true);
+        self.move_to(offset, window, cx);
+    }
+
+    pub(super) fn move_to_previous_paragraph(
+        &mut self,
+        _: &MoveToPreviousParagraph,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pause_blink_cursor(cx);
+        let offset = self.previous_paragraph_start(self.cursor_offset());
+        self.move_to(offset, window, cx);
+    }
+
+    pub(super) fn move_to_next_paragraph(
+        &mut self,
+        _: &MoveToNextParagraph,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pause_blink_cursor(cx);
+        let offset = self.next_paragraph_end(self.cursor_offset());
+        self.move_to(offset, window, cx);
+    }
+
+    pub(super) fn select_to_previous_word(
+        &mut self,
+        _: &SelectToPreviousWord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let offset = self.previous_word_start(self.cursor_offset(), false);
+        self.select_to(offset, window, cx);
+    }
+
+    pub(super) fn select_to_next_word(
+        &mut self,
+        _: &SelectToNextWord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let offset = self.next_word_end(self.cursor_offset(), false);
+        self.select_to(offset, window, cx);
+    }
+
+    pub(super) fn select_to_previous_subword(
+        &mut self,
+        _: &SelectToPreviousSubword,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let offset = self.previous_word_start(self.cursor_offset(), true);
+        self.select_to(offset, window, cx);
+    }
+
+    pub(super) fn select_to_next_subword(
+        &mut self,
+        _: &SelectToNextSubword,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let offset = self.next_word_end(self.cursor_offset(), true);
+        self.select_to(offset, window, cx);
+    }
+
+    pub(super) fn select_to_previous_paragraph(
+        &mut self,
+        _: &SelectToPreviousParagraph,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let offset = self.previous_paragraph_start(self.cursor_offset());
+        self.select_to(offset, window, cx);
+    }
+
+    pub(super) fn select_to_next_paragraph(
+        &mut self,
+        _: &SelectToNextParagraph,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let offset = self.next_paragraph_end(self.cursor_offset());
+        self.select_to(offset, window, cx);
+    }
+
+    pub(super) fn delete_to_previous_word_start(
+        &mut self,
+        _: &DeleteToPreviousWordStart,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.delete_to(
+            |this, offset| this.previous_word_start(offset, false),
+            window,
+            cx,
+        );
+    }
+
+    pub(super) fn delete_to_next_word_end(
+        &mut self,
+        _: &DeleteToNextWordEnd,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.delete_to(|this, offset| this.next_word_end(offset, false), window, cx);
+    }
+
+    pub(super) fn delete_to_previous_subword_start(
+        &mut self,
+        _: &DeleteToPreviousSubwordStart,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.delete_to(
+            |this, offset| this.previous_word_start(offset, true),
+            window,
+            cx,
+        );
+    }
+
+    pub(super) fn delete_to_next_subword_end(
+        &mut self,
+        _: &DeleteToNextSubwordEnd,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.delete_to(|this, offset| this.next_word_end(offset, true), window, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_movement() {
+        let line = "hello world, héllo wörld";
+        assert_eq!(previous_word_start(line, line.len(), false), 20);
+        assert_eq!(previous_word_start(line, 20, false), 13);
+        assert_eq!(previous_word_start(line, 13, false), 11);
+        assert_eq!(previous_word_start(line, 8, false), 6);
+        assert_eq!(previous_word_start(line, 0, false), 0);
+
+        assert_eq!(next_word_end(line, 0, false), 5);
+        assert_eq!(next_word_end(line, 5, false), 11);
+        assert_eq!(next_word_end(line, 11, false), 12);
+        assert_eq!(next_word_end(line, 12, false), 19);
+        assert_eq!(next_word_end(line, 20, false), line.len());
+        assert_eq!(next_word_end(line, line.len(), false), line.len());
+    }
+
+    #[test]
+    fn test_subword_movement() {
+        let line = "fooBarBaz snake_case HTMLParser";
+        assert_eq!(next_word_end(line, 0, true), 3);
+        assert_eq!(next_word_end(line, 3, true), 6);
+        assert_eq!(next_word_end(line, 6, true), 9);
+        assert_eq!(next_word_end(line, 9, true), 15);
+        assert_eq!(next_word_end(line, 15, true), 20);
+        assert_eq!(next_word_end(line, 20, true), 25);
+        assert_eq!(next_word_end(line, 25, true), 31);
+
+        assert_eq!(previous_word_start(line, 31, true), 25);
+        assert_eq!(previous_word_start(line, 25, true), 21);
+        assert_eq!(previous_word_start(line, 21, true), 16);
+        assert_eq!(previous_word_start(line, 16, true), 10);
+        assert_eq!(previous_word_start(line, 10, true), 6);
+
+        // The whole words are used without subword.
+        assert_eq!(next_word_end(line, 0, false), 9);
+        assert_eq!(previous_word_start(line, 20, false), 10);
+    }
+
+    #[test]
+    fn test_word_range() {
+        let line = "foo  bar(baz)";
+        assert_eq!(word_range(line, 0), 0..3);
+        assert_eq!(word_range(line, 1), 0..3);
+        assert_eq!(word_range(line, 3), 0..3);
+        assert_eq!(word_range(line, 4), 3..5);
+        assert_eq!(word_range(line, 5), 5..8);
+        assert_eq!(word_range(line, 8), 5..8);
+        assert_eq!(word_range(line, 9), 9..12);
+        assert_eq!(word_range(line, 12), 9..12);
+        assert_eq!(word_range(line, 13), 13..13);
+        assert_eq!(word_range("", 0), 0..0);
+    }
+}
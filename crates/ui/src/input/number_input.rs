@@ -1,7 +1,8 @@
 use gpui::{
-    actions, px, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, KeyBinding, ParentElement, Pixels, Render, SharedString,
-    Styled, Subscription, Window,
+    actions, div, px, App, AppContext as _, ClickEvent, Context, DragMoveEvent, Empty, Entity,
+    EntityId, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
+    MouseButton, MouseDownEvent, ParentElement, Pixels, Render, ScrollWheelEvent, SharedString,
+    StatefulInteractiveElement as _, Styled, Subscription, Window,
 };
 use regex::Regex;
 
@@ -13,42 +14,190 @@ use crate::{
     ActiveTheme, IconName, Sizable, Size, StyleSized, StyledExt,
 };
 
-actions!(number_input, [Increment, Decrement]);
+actions!(
+    number_input,
+    [Increment, Decrement, LargeIncrement, LargeDecrement]
+);
 
 const KEY_CONTENT: &str = "NumberInput";
 
+/// The pixels of the mouse movement to scrub one step.
+const SCRUB_PIXELS_PER_STEP: Pixels = px(4.);
+
 pub fn init(cx: &mut App) {
     cx.bind_keys(vec![
         KeyBinding::new("up", Increment, Some(KEY_CONTENT)),
         KeyBinding::new("down", Decrement, Some(KEY_CONTENT)),
+        KeyBinding::new("shift-up", LargeIncrement, Some(KEY_CONTENT)),
+        KeyBinding::new("shift-down", LargeDecrement, Some(KEY_CONTENT)),
     ]);
 }
 
+/// The decimal and group separators to format the number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    /// The separator of the thousands, `None` to disable grouping.
+    pub group_separator: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: Some(','),
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Returns the number format of the locale, for example `en`, `de-DE` or `fr`.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale.split(['-', '_']).next().unwrap_or_default();
+        match language {
+            "de" | "es" | "it" | "pt" | "nl" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl"
+            | "sr" | "vi" => Self {
+                decimal_separator: ',',
+                group_separator: Some('.'),
+            },
+            "fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "hu" | "bg"
+            | "et" | "lt" | "lv" => Self {
+                decimal_separator: ',',
+                group_separator: Some('\u{a0}'),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Format the `value` with the `precision` decimals, `None` to use the shortest representation.
+    pub fn format(&self, value: f64, precision: Option<usize>) -> String {
+        // Avoid the `-0`.
+        let value = if value == 0. { 0. } else { value };
+        let text = match precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        };
+
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(text) => ("-", text),
+            None => ("", text.as_str()),
+        };
+        let (integer, fraction) = match text.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text, None),
+        };
+
+        let mut result = String::from(sign);
+        for (ix, c) in integer.chars().enumerate() {
+            if ix > 0 && (integer.len() - ix) % 3 == 0 {
+                if let Some(group_separator) = self.group_separator {
+                    result.push(group_separator);
+                }
+            }
+            result.push(c);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        result
+    }
+
+    /// Parse the formatted text, returns `None` if it is not a number.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let text = text
+            .trim()
+            .chars()
+            .filter(|c| Some(*c) != self.group_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect::<String>();
+
+        text.parse::<f64>().ok().filter(|value| value.is_finite())
+    }
+
+    /// Returns the pattern to accept the typing text.
+    fn pattern(&self) -> Regex {
+        let group_separator = self
+            .group_separator
+            .map(|c| regex::escape(&c.to_string()))
+            .unwrap_or_default();
+        let decimal_separator = regex::escape(&self.decimal_separator.to_string());
+
+        Regex::new(&format!(
+            r"^-?[\d{}]*({}\d*)?$",
+            group_separator, decimal_separator
+        ))
+        .unwrap()
+    }
+}
+
+/// Returns the number of decimals of the `value`, for example 2 for `0.25`.
+fn decimals(value: f64) -> usize {
+    value
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+fn round(value: f64, precision: usize) -> f64 {
+    let factor = 10f64.powi(precision as i32);
+    (value * factor).round() / factor
+}
+
+#[derive(Clone)]
+struct DragScrub(EntityId);
+
+impl Render for DragScrub {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
 pub struct NumberInput {
     input: Entity<TextInput>,
     size: Size,
+    value: Option<f64>,
+    min: f64,
+    max: f64,
+    step: f64,
+    /// The multiplier of the step when the shift key is pressed.
+    large_step_multiplier: f64,
+    precision: Option<usize>,
+    format: NumberFormat,
+    label: Option<SharedString>,
+    /// The mouse x position and the value when scrubbing on the label is started.
+    scrub_start: Option<(Pixels, f64)>,
+    scroll_delta: Pixels,
     _subscriptions: Vec<Subscription>,
     _synced_size: bool,
 }
 
 impl NumberInput {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        // Default pattern for the number input.
-        let pattern = Regex::new(r"^-?(\d+)?\.?(\d+)?$").unwrap();
+        let format = NumberFormat::for_locale(&crate::locale());
 
         let input = cx.new(|cx| {
             TextInput::new(window, cx)
-                .pattern(pattern)
+                .pattern(format.pattern())
                 .appearance(false)
         });
 
-        let _subscriptions = vec![cx.subscribe(&input, |_, _, event: &InputEvent, cx| {
-            cx.emit(NumberInputEvent::Input(event.clone()));
-        })];
+        let _subscriptions = vec![cx.subscribe_in(&input, window, Self::on_input_event)];
 
         Self {
             input,
             size: Size::default(),
+            value: None,
+            min: f64::MIN,
+            max: f64::MAX,
+            step: 1.,
+            large_step_multiplier: 10.,
+            precision: None,
+            format,
+            label: None,
+            scrub_start: None,
+            scroll_delta: px(0.),
             _synced_size: false,
             _subscriptions,
         }
@@ -65,6 +214,50 @@ impl NumberInput {
         self
     }
 
+    /// Set the minimum value, default is `f64::MIN`.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum value, default is `f64::MAX`.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set the step of the increment and decrement, default is `1.0`.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the multiplier of the step when the shift key is pressed, default is `10.0`.
+    pub fn large_step_multiplier(mut self, multiplier: f64) -> Self {
+        self.large_step_multiplier = multiplier;
+        self
+    }
+
+    /// Set the number of decimals, default is the decimals of the step.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Set the number format, default is the format of the current locale.
+    pub fn format(mut self, format: NumberFormat, _: &mut Window, cx: &mut Context<Self>) -> Self {
+        self.format = format;
+        self.input
+            .update(cx, |input, _| input.set_pattern(format.pattern()));
+        self
+    }
+
+    /// Set the label at the left of the input, drag on it to scrub the value.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
     pub fn set_size(&mut self, size: Size, window: &mut Window, cx: &mut Context<Self>) {
         self.size = size;
         self.sync_size_to_input_if_needed(window, cx);
@@ -86,14 +279,16 @@ impl NumberInput {
         self
     }
 
-    pub fn set_value(
-        &self,
-        text: impl Into<SharedString>,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        self.input
-            .update(cx, |input, cx| input.set_text(text, window, cx))
+    /// Returns the value, `None` if the input is empty.
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Set the value, it will be clamped and rounded to the precision.
+    pub fn set_value(&mut self, value: f64, window: &mut Window, cx: &mut Context<Self>) {
+        let value = self.normalize(value);
+        self.value = Some(value);
+        self.update_text(window, cx);
     }
 
     pub fn set_disabled(&self, disabled: bool, window: &mut Window, cx: &mut Context<Self>) {
@@ -109,22 +304,183 @@ impl NumberInput {
         self.on_action_decrement(&Decrement, window, cx);
     }
 
+    fn effective_precision(&self) -> usize {
+        self.precision.unwrap_or_else(|| decimals(self.step))
+    }
+
+    /// Clamp the value to the range, and round it to the precision.
+    fn normalize(&self, value: f64) -> f64 {
+        round(value, self.effective_precision()).clamp(self.min, self.max)
+    }
+
+    fn update_text(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = match self.value {
+            Some(value) => self.format.format(value, Some(self.effective_precision())),
+            None => String::new(),
+        };
+        self.input
+            .update(cx, |input, cx| input.set_text(text, window, cx));
+    }
+
+    /// Update the value and emit the [`NumberInputEvent::ValueChanged`] if it is changed.
+    fn update_value(&mut self, value: f64, cx: &mut Context<Self>) {
+        if self.value != Some(value) {
+            self.value = Some(value);
+            cx.emit(NumberInputEvent::ValueChanged(value));
+        }
+    }
+
+    /// Clamp and format the typing text.
+    fn commit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.input.read(cx).text();
+        match self.format.parse(&text) {
+            Some(value) => self.update_value(self.normalize(value), cx),
+            // Restore the last value for the invalid text.
+            None if !text.trim().is_empty() => {}
+            None => self.value = None,
+        }
+        self.update_text(window, cx);
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<TextInput>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                // Keep the typing text as is, it is clamped and formatted when committed.
+                if let Some(value) = self.format.parse(text) {
+                    self.update_value(self.normalize(value), cx);
+                }
+            }
+            InputEvent::PressEnter | InputEvent::Blur => self.commit(window, cx),
+            InputEvent::Focus => {}
+        }
+
+        cx.emit(NumberInputEvent::Input(event.clone()));
+    }
+
     fn on_action_increment(&mut self, _: &Increment, window: &mut Window, cx: &mut Context<Self>) {
-        self.on_step(StepAction::Increment, window, cx);
+        self.on_step(StepAction::Increment, false, window, cx);
     }
 
     fn on_action_decrement(&mut self, _: &Decrement, window: &mut Window, cx: &mut Context<Self>) {
-        self.on_step(StepAction::Decrement, window, cx);
+        self.on_step(StepAction::Decrement, false, window, cx);
     }
 
-    fn on_step(&mut self, action: StepAction, _: &mut Window, cx: &mut Context<Self>) {
+    fn on_action_large_increment(
+        &mut self,
+        _: &LargeIncrement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.on_step(StepAction::Increment, true, window, cx);
+    }
+
+    fn on_action_large_decrement(
+        &mut self,
+        _: &LargeDecrement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.on_step(StepAction::Decrement, true, window, cx);
+    }
+
+    fn step_value(&self, large: bool) -> f64 {
+        if large {
+            self.step * self.large_step_multiplier
+        } else {
+            self.step
+        }
+    }
+
+    fn on_step(
+        &mut self,
+        action: StepAction,
+        large: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         if self.input.read(cx).disabled {
             return;
         }
 
+        let current = self
+            .format
+            .parse(&self.input.read(cx).text())
+            .or(self.value)
+            .unwrap_or(self.min.max(0.));
+        let step = self.step_value(large);
+        let value = match action {
+            StepAction::Increment => current + step,
+            StepAction::Decrement => current - step,
+        };
+
+        self.update_value(self.normalize(value), cx);
+        self.update_text(window, cx);
         cx.emit(NumberInputEvent::Step(action));
     }
 
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Only step by the scroll wheel when focused, to not hijack the scrolling of the page.
+        if !self.input.focus_handle(cx).is_focused(window) {
+            return;
+        }
+        cx.stop_propagation();
+
+        let line_height = window.line_height();
+        self.scroll_delta += event.delta.pixel_delta(line_height).y;
+        while self.scroll_delta.abs() >= line_height {
+            let action = if self.scroll_delta > px(0.) {
+                self.scroll_delta -= line_height;
+                StepAction::Increment
+            } else {
+                self.scroll_delta += line_height;
+                StepAction::Decrement
+            };
+            self.on_step(action, event.modifiers.shift, window, cx);
+        }
+    }
+
+    fn on_scrub_start(&mut self, event: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let value = self
+            .format
+            .parse(&self.input.read(cx).text())
+            .or(self.value)
+            .unwrap_or(self.min.max(0.));
+        self.scrub_start = Some((event.position.x, value));
+    }
+
+    fn on_scrub_move(
+        &mut self,
+        event: &DragMoveEvent<DragScrub>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if event.drag(cx).0 != cx.entity_id() || self.input.read(cx).disabled {
+            return;
+        }
+        let Some((start_x, start_value)) = self.scrub_start else {
+            return;
+        };
+
+        let steps = ((event.event.position.x - start_x) / SCRUB_PIXELS_PER_STEP).trunc() as f64;
+        let value = start_value + steps * self.step_value(event.event.modifiers.shift);
+        let value = self.normalize(value);
+        if self.value != Some(value) {
+            self.update_value(value, cx);
+            self.update_text(window, cx);
+        }
+    }
+
     fn sync_size_to_input_if_needed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if !self._synced_size {
             self.input
@@ -147,7 +503,9 @@ pub enum StepAction {
 
 pub enum NumberInputEvent {
     Input(InputEvent),
+    /// Emitted after the value is stepped by the buttons, keys, or scroll wheel.
     Step(StepAction),
+    ValueChanged(f64),
 }
 
 impl EventEmitter<NumberInputEvent> for NumberInput {}
@@ -160,6 +518,7 @@ impl Sizable for NumberInput {
 impl Render for NumberInput {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let focused = self.input.focus_handle(cx).is_focused(window);
+        let entity_id = cx.entity_id();
 
         // Sync size to input at first.
         self.sync_size_to_input_if_needed(window, cx);
@@ -173,6 +532,9 @@ impl Render for NumberInput {
             .key_context(KEY_CONTENT)
             .on_action(cx.listener(Self::on_action_increment))
             .on_action(cx.listener(Self::on_action_decrement))
+            .on_action(cx.listener(Self::on_action_large_increment))
+            .on_action(cx.listener(Self::on_action_large_decrement))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
             .flex_1()
             .input_size(self.size)
             .bg(cx.theme().background)
@@ -180,14 +542,30 @@ impl Render for NumberInput {
             .border_1()
             .rounded_md()
             .when(focused, |this| this.outline(cx))
+            .when_some(self.label.clone(), |this, label| {
+                this.child(
+                    div()
+                        .id("label")
+                        .pl_2()
+                        .text_color(cx.theme().muted_foreground)
+                        .cursor_col_resize()
+                        .on_mouse_down(MouseButton::Left, cx.listener(Self::on_scrub_start))
+                        .on_drag(DragScrub(entity_id), |drag, _, _, cx| {
+                            cx.stop_propagation();
+                            cx.new(|_| drag.clone())
+                        })
+                        .on_drag_move(cx.listener(Self::on_scrub_move))
+                        .child(label),
+                )
+            })
             .child(
                 Button::new("minus")
                     .ghost()
                     .with_size(btn_size)
                     .ml(BUTTON_OFFSET)
                     .icon(IconName::Minus)
-                    .on_click(cx.listener(|this, _, window, cx| {
-                        this.on_step(StepAction::Decrement, window, cx)
+                    .on_click(cx.listener(|this, event: &ClickEvent, window, cx| {
+                        this.on_step(StepAction::Decrement, event.up.modifiers.shift, window, cx)
                     })),
             )
             .child(self.input.clone())
@@ -197,9 +575,49 @@ impl Render for NumberInput {
                     .with_size(btn_size)
                     .mr(BUTTON_OFFSET)
                     .icon(IconName::Plus)
-                    .on_click(cx.listener(|this, _, window, cx| {
-                        this.on_step(StepAction::Increment, window, cx)
+                    .on_click(cx.listener(|this, event: &ClickEvent, window, cx| {
+                        this.on_step(StepAction::Increment, event.up.modifiers.shift, window, cx)
                     })),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_format() {
+        let format = NumberFormat::for_locale("en");
+        assert_eq!(format.format(1234567.5, None), "1,234,567.5");
+        assert_eq!(format.format(-1234.5, Some(2)), "-1,234.50");
+        assert_eq!(format.format(123., None), "123");
+        assert_eq!(format.format(-0., Some(1)), "0.0");
+        assert_eq!(format.parse("1,234,567.5"), Some(1234567.5));
+        assert_eq!(format.parse("-12"), Some(-12.));
+        assert_eq!(format.parse("-"), None);
+        assert_eq!(format.parse(""), None);
+
+        let format = NumberFormat::for_locale("de-DE");
+        assert_eq!(format.format(1234567.5, None), "1.234.567,5");
+        assert_eq!(format.parse("1.234,5"), Some(1234.5));
+
+        let format = NumberFormat::for_locale("fr");
+        assert_eq!(format.format(1234.5, None), "1\u{a0}234,5");
+        assert_eq!(format.parse("1\u{a0}234,5"), Some(1234.5));
+
+        let format = NumberFormat {
+            decimal_separator: '.',
+            group_separator: None,
+        };
+        assert_eq!(format.format(1234567., None), "1234567");
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(decimals(1.), 0);
+        assert_eq!(decimals(0.25), 2);
+        assert_eq!(round(0.1 + 0.2, 1), 0.3);
+        assert_eq!(round(1.005, 0), 1.);
+    }
+}
@@ -0,0 +1,518 @@
+use std::{borrow::Cow, fmt, ops::Range};
+
+use unicode_segmentation::UnicodeSegmentation as _;
+
+const MAX_CHUNK_LEN: usize = 2048;
+const MIN_CHUNK_LEN: usize = MAX_CHUNK_LEN / 4;
+
+#[derive(Debug, Clone, Default)]
+struct Chunk {
+    text: String,
+    len_utf16: usize,
+    /// The number of `\n` in the chunk.
+    lines: usize,
+}
+
+impl Chunk {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            len_utf16: text.encode_utf16().count(),
+            lines: text.bytes().filter(|b| *b == b'\n').count(),
+        }
+    }
+}
+
+/// A chunked text buffer used by the [`super::TextInput`] to store the text.
+///
+/// The text is split into small chunks, so an edit only needs to rebuild the chunks it touches,
+/// and each chunk keeps the count of UTF-16 code units and lines to find offsets and rows
+/// without scanning the whole text.
+///
+/// All offsets are UTF-8 offsets unless the method name says otherwise.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Rope {
+    chunks: Vec<Chunk>,
+    len: usize,
+    len_utf16: usize,
+    lines: usize,
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        let mut rope = Self {
+            chunks: build_chunks(text),
+            ..Default::default()
+        };
+        rope.update_summary();
+        rope
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.chunks.iter() {
+            f.write_str(&chunk.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Split the text into chunks, the chunks are split at char boundaries.
+fn build_chunks(text: &str) -> Vec<Chunk> {
+    let mut chunks = vec![];
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut split = if rest.len() > MAX_CHUNK_LEN && rest.len() < MAX_CHUNK_LEN + MIN_CHUNK_LEN
+        {
+            // Avoid to leave a tiny chunk at the end.
+            rest.len() / 2
+        } else {
+            rest.len().min(MAX_CHUNK_LEN)
+        };
+        while !rest.is_char_boundary(split) {
+            split -= 1;
+        }
+
+        chunks.push(Chunk::new(&rest[..split]));
+        rest = &rest[split..];
+    }
+
+    chunks
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the length of the text in UTF-8 bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the length of the text in UTF-16 code units.
+    #[inline]
+    pub fn len_utf16(&self) -> usize {
+        self.len_utf16
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of lines, an empty text has 1 line.
+    #[inline]
+    pub fn lines_len(&self) -> usize {
+        self.lines + 1
+    }
+
+    pub fn chars(&self) -> impl DoubleEndedIterator<Item = char> + '_ {
+        self.chunks.iter().flat_map(|chunk| chunk.text.chars())
+    }
+
+    /// Returns an iterator over the lines (split by `\n`) of the text.
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            chunks: self.chunks.iter(),
+            rest: "",
+            finished: false,
+        }
+    }
+
+    fn update_summary(&mut self) {
+        self.len = self.chunks.iter().map(|c| c.text.len()).sum();
+        self.len_utf16 = self.chunks.iter().map(|c| c.len_utf16).sum();
+        self.lines = self.chunks.iter().map(|c| c.lines).sum();
+    }
+
+    /// Returns the chunk index and the offset in that chunk for the given offset.
+    ///
+    /// If the offset is at the boundary of two chunks, the former chunk is returned.
+    fn chunk_at(&self, offset: usize) -> (usize, usize) {
+        let mut start = 0;
+        for (ix, chunk) in self.chunks.iter().enumerate() {
+            if offset <= start + chunk.text.len() {
+                return (ix, offset - start);
+            }
+            start += chunk.text.len();
+        }
+
+        let last_ix = self.chunks.len().saturating_sub(1);
+        (last_ix, self.chunks.last().map_or(0, |c| c.text.len()))
+    }
+
+    fn clip_range(&self, range: Range<usize>) -> Range<usize> {
+        let end = range.end.min(self.len);
+        range.start.min(end)..end
+    }
+
+    /// Returns the text in the given range.
+    pub fn slice(&self, range: Range<usize>) -> String {
+        let range = self.clip_range(range);
+        let mut text = String::with_capacity(range.len());
+        if range.is_empty() {
+            return text;
+        }
+
+        let mut start = 0;
+        for chunk in self.chunks.iter() {
+            let end = start + chunk.text.len();
+            if end > range.start && start < range.end {
+                let local_start = range.start.saturating_sub(start);
+                let local_end = (range.end - start).min(chunk.text.len());
+                text.push_str(&chunk.text[local_start..local_end]);
+            }
+            if end >= range.end {
+                break;
+            }
+            start = end;
+        }
+
+        text
+    }
+
+    /// Replace the text in the given range with the new text.
+    pub fn replace(&mut self, range: Range<usize>, new_text: &str) {
+        let range = self.clip_range(range);
+        if self.chunks.is_empty() {
+            self.chunks = build_chunks(new_text);
+            self.update_summary();
+            return;
+        }
+
+        let (mut start_ix, start_offset) = self.chunk_at(range.start);
+        let (mut end_ix, end_offset) = self.chunk_at(range.end);
+
+        let mut text = String::with_capacity(
+            start_offset + new_text.len() + self.chunks[end_ix].text.len() - end_offset,
+        );
+        text.push_str(&self.chunks[start_ix].text[..start_offset]);
+        text.push_str(new_text);
+        text.push_str(&self.chunks[end_ix].text[end_offset..]);
+
+        // Merge the small chunk with the neighbor to keep the chunks count low.
+        if text.len() < MIN_CHUNK_LEN {
+            if start_ix > 0 {
+                start_ix -= 1;
+                text.insert_str(0, &self.chunks[start_ix].text);
+            } else if end_ix + 1 < self.chunks.len() {
+                end_ix += 1;
+                text.push_str(&self.chunks[end_ix].text);
+            }
+        }
+
+        self.chunks.splice(start_ix..=end_ix, build_chunks(&text));
+        self.update_summary();
+    }
+
+    /// Returns the offset of the `n`th (0-based) `\n`.
+    fn newline_offset(&self, n: usize) -> Option<usize> {
+        let mut remaining = n;
+        let mut start = 0;
+        for chunk in self.chunks.iter() {
+            if remaining < chunk.lines {
+                return chunk
+                    .text
+                    .bytes()
+                    .enumerate()
+                    .filter(|(_, b)| *b == b'\n')
+                    .nth(remaining)
+                    .map(|(ix, _)| start + ix);
+            }
+
+            remaining -= chunk.lines;
+            start += chunk.text.len();
+        }
+
+        None
+    }
+
+    /// Returns the row (0-based line index) of the given offset.
+    pub fn offset_to_row(&self, offset: usize) -> usize {
+        let mut row = 0;
+        let mut start = 0;
+        for chunk in self.chunks.iter() {
+            let end = start + chunk.text.len();
+            if offset <= end {
+                row += chunk.text.as_bytes()[..offset - start]
+                    .iter()
+                    .filter(|b| **b == b'\n')
+                    .count();
+                return row;
+            }
+
+            row += chunk.lines;
+            start = end;
+        }
+
+        row
+    }
+
+    /// Returns the start offset of the given row.
+    pub fn line_start_offset(&self, row: usize) -> usize {
+        if row == 0 {
+            return 0;
+        }
+
+        self.newline_offset(row - 1)
+            .map(|ix| ix + 1)
+            .unwrap_or(self.len)
+    }
+
+    /// Returns the end offset of the given row, excluding the `\n`.
+    pub fn line_end_offset(&self, row: usize) -> usize {
+        self.newline_offset(row).unwrap_or(self.len)
+    }
+
+    /// Convert the UTF-8 offset to UTF-16 offset.
+    pub fn offset_to_utf16(&self, offset: usize) -> usize {
+        let mut utf16_offset = 0;
+        let mut start = 0;
+        for chunk in self.chunks.iter() {
+            let end = start + chunk.text.len();
+            if offset < end {
+                let mut utf8_count = start;
+                for ch in chunk.text.chars() {
+                    if utf8_count >= offset {
+                        break;
+                    }
+                    utf8_count += ch.len_utf8();
+                    utf16_offset += ch.len_utf16();
+                }
+                return utf16_offset;
+            }
+
+            utf16_offset += chunk.len_utf16;
+            start = end;
+        }
+
+        utf16_offset
+    }
+
+    /// Convert the UTF-16 offset to UTF-8 offset.
+    pub fn offset_from_utf16(&self, offset: usize) -> usize {
+        let mut utf8_offset = 0;
+        let mut start = 0;
+        for chunk in self.chunks.iter() {
+            let end = start + chunk.len_utf16;
+            if offset < end {
+                let mut utf16_count = start;
+                for ch in chunk.text.chars() {
+                    if utf16_count >= offset {
+                        break;
+                    }
+                    utf16_count += ch.len_utf16();
+                    utf8_offset += ch.len_utf8();
+                }
+                return utf8_offset;
+            }
+
+            utf8_offset += chunk.text.len();
+            start = end;
+        }
+
+        utf8_offset
+    }
+
+    /// Returns the previous grapheme boundary of the given offset.
+    ///
+    /// Only the current and previous lines are scanned, so this is not slow on large text.
+    pub fn previous_boundary(&self, offset: usize) -> usize {
+        let offset = offset.min(self.len);
+        if offset == 0 {
+            return 0;
+        }
+
+        let row = self.offset_to_row(offset);
+        // Include the previous line to handle `\r\n` as one grapheme.
+        let start = self.line_start_offset(row.saturating_sub(1));
+        let end = self.line_end_offset(row);
+        self.slice(start..end)
+            .grapheme_indices(true)
+            .rev()
+            .find_map(|(ix, _)| (start + ix < offset).then_some(start + ix))
+            .unwrap_or(start)
+    }
+
+    /// Returns the next grapheme boundary of the given offset.
+    ///
+    /// Only the current and next lines are scanned, so this is not slow on large text.
+    pub fn next_boundary(&self, offset: usize) -> usize {
+        let row = self.offset_to_row(offset);
+        let start = self.line_start_offset(row);
+        // Include the next line to handle `\r\n` as one grapheme.
+        let end = self.line_end_offset((row + 1).min(self.lines));
+        self.slice(start..end)
+            .grapheme_indices(true)
+            .find_map(|(ix, _)| (start + ix > offset).then_some(start + ix))
+            .unwrap_or(end)
+    }
+}
+
+/// An iterator over the lines of a [`Rope`].
+///
+/// The line is borrowed if it is in a single chunk, otherwise it's copied.
+pub(crate) struct Lines<'a> {
+    chunks: std::slice::Iter<'a, Chunk>,
+    rest: &'a str,
+    finished: bool,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut line: Option<String> = None;
+        let part = loop {
+            if let Some(ix) = self.rest.find('\n') {
+                let part = &self.rest[..ix];
+                self.rest = &self.rest[ix + 1..];
+                break part;
+            }
+
+            match self.chunks.next() {
+                Some(chunk) => {
+                    if !self.rest.is_empty() {
+                        line.get_or_insert_with(String::new).push_str(self.rest);
+                    }
+                    self.rest = &chunk.text;
+                }
+                None => {
+                    self.finished = true;
+                    break std::mem::take(&mut self.rest);
+                }
+            }
+        };
+
+        Some(match line {
+            Some(mut line) => {
+                line.push_str(part);
+                Cow::Owned(line)
+            }
+            None => Cow::Borrowed(part),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn large_text() -> String {
+        (0..500)
+            .map(|i| format!("Line {} 你好世界 💝\r\n", i))
+            .collect::<String>()
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut rope = Rope::from("Hello world");
+        rope.replace(6..11, "世界");
+        assert_eq!(rope.to_string(), "Hello 世界");
+        assert_eq!(rope.len(), "Hello 世界".len());
+        assert_eq!(rope.len_utf16(), 8);
+
+        rope.replace(0..rope.len(), "");
+        assert!(rope.is_empty());
+        assert_eq!(rope.lines_len(), 1);
+
+        rope.replace(0..0, "a\nb");
+        assert_eq!(rope.to_string(), "a\nb");
+        assert_eq!(rope.lines_len(), 2);
+
+        let text = large_text();
+        let mut rope = Rope::from(text.as_str());
+        assert!(rope.chunks.len() > 1);
+        assert_eq!(rope.to_string(), text);
+
+        let mut expected = text.clone();
+        let char_boundary = |mut ix: usize| {
+            while !expected.is_char_boundary(ix) {
+                ix -= 1;
+            }
+            ix
+        };
+        let range = char_boundary(1000)..char_boundary(MAX_CHUNK_LEN * 3 + 1);
+        expected.replace_range(range.clone(), "Replaced\n");
+        rope.replace(range.clone(), "Replaced\n");
+        assert_eq!(rope.to_string(), expected);
+        assert_eq!(rope.len(), expected.len());
+        assert_eq!(rope.lines_len(), expected.split('\n').count());
+        assert_eq!(rope.slice(range.start..range.start + 9), "Replaced\n");
+    }
+
+    #[test]
+    fn test_lines() {
+        let rope = Rope::from("");
+        assert_eq!(rope.lines().collect::<Vec<_>>(), vec![""]);
+
+        let rope = Rope::from("a\n\nb\n");
+        assert_eq!(rope.lines().collect::<Vec<_>>(), vec!["a", "", "b", ""]);
+
+        let text = large_text();
+        let rope = Rope::from(text.as_str());
+        assert_eq!(
+            rope.lines().collect::<Vec<_>>(),
+            text.split('\n').collect::<Vec<_>>()
+        );
+        assert_eq!(rope.lines_len(), text.split('\n').count());
+    }
+
+    #[test]
+    fn test_rows() {
+        let text = large_text();
+        let rope = Rope::from(text.as_str());
+
+        let mut offset = 0;
+        for (row, line) in text.split('\n').enumerate() {
+            assert_eq!(rope.line_start_offset(row), offset);
+            assert_eq!(rope.line_end_offset(row), offset + line.len());
+            assert_eq!(rope.offset_to_row(offset), row);
+            assert_eq!(rope.offset_to_row(offset + line.len()), row);
+            assert_eq!(
+                rope.slice(rope.line_start_offset(row)..rope.line_end_offset(row)),
+                line
+            );
+            offset += line.len() + 1;
+        }
+        assert_eq!(rope.line_start_offset(10000), rope.len());
+    }
+
+    #[test]
+    fn test_utf16() {
+        let text = large_text();
+        let rope = Rope::from(text.as_str());
+
+        let mut utf16_offset = 0;
+        for (offset, ch) in text.char_indices() {
+            assert_eq!(rope.offset_to_utf16(offset), utf16_offset);
+            assert_eq!(rope.offset_from_utf16(utf16_offset), offset);
+            utf16_offset += ch.len_utf16();
+        }
+        assert_eq!(rope.offset_to_utf16(text.len()), rope.len_utf16());
+        assert_eq!(rope.offset_from_utf16(rope.len_utf16()), text.len());
+    }
+
+    #[test]
+    fn test_boundary() {
+        let rope = Rope::from("ab\r\n💝c");
+        assert_eq!(rope.next_boundary(0), 1);
+        assert_eq!(rope.next_boundary(2), 4);
+        assert_eq!(rope.next_boundary(4), 8);
+        assert_eq!(rope.next_boundary(8), 9);
+        assert_eq!(rope.next_boundary(9), 9);
+
+        assert_eq!(rope.previous_boundary(9), 8);
+        assert_eq!(rope.previous_boundary(8), 4);
+        assert_eq!(rope.previous_boundary(4), 2);
+        assert_eq!(rope.previous_boundary(1), 0);
+        assert_eq!(rope.previous_boundary(0), 0);
+    }
+}
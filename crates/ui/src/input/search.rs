@@ -0,0 +1,653 @@
+use std::ops::Range;
+
+use gpui::prelude::FluentBuilder as _;
+use gpui::{
+    div, px, AppContext as _, ClickEvent, Context, Entity, InteractiveElement as _, IntoElement,
+    MouseButton, ParentElement as _, Styled as _, ViewInputHandler as _, Window,
+};
+use regex::{Regex, RegexBuilder};
+use rust_i18n::t;
+
+use crate::button::{Button, ButtonVariants as _};
+use crate::{
+    h_flex, v_flex, ActiveTheme as _, Disableable as _, IconName, Selectable as _, Sizable as _,
+};
+
+use super::{
+    CloseFind, Find, FindAndReplace, FindNext, FindPrevious, InputEvent, ReplaceAll, TextInput,
+};
+
+pub(super) const SEARCH_CONTEXT: &str = "InputSearch";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct SearchOptions {
+    pub(super) case_sensitive: bool,
+    pub(super) whole_word: bool,
+    pub(super) regex: bool,
+}
+
+/// A compiled search query.
+#[derive(Debug, Clone)]
+pub(super) struct SearchQuery {
+    regex: Regex,
+    is_regex: bool,
+}
+
+impl SearchQuery {
+    /// Compile the `query` with the `options`.
+    ///
+    /// Returns `None` if the query is empty or is an invalid regular expression.
+    pub(super) fn new(query: &str, options: SearchOptions) -> Option<Self> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let mut pattern = if options.regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        if options.whole_word {
+            pattern = format!(r"\b(?:{})\b", pattern);
+        }
+
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!options.case_sensitive)
+            .multi_line(true)
+            .build()
+            .ok()?;
+
+        Some(Self {
+            regex,
+            is_regex: options.regex,
+        })
+    }
+
+    /// Returns the UTF-8 ranges of all the matches in the `text`, empty matches are ignored.
+    pub(super) fn find_matches(&self, text: &str) -> Vec<Range<usize>> {
+        self.regex
+            .find_iter(text)
+            .map(|m| m.range())
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+
+    /// Returns the text to replace the match at `range` with.
+    ///
+    /// In regex mode, the `$1`, `${name}` in the `replacement` are expanded by the captures of the match.
+    pub(super) fn replacement_for(
+        &self,
+        text: &str,
+        range: &Range<usize>,
+        replacement: &str,
+    ) -> String {
+        if self.is_regex {
+            if let Some(captures) = self.regex.captures_at(text, range.start) {
+                if captures.get(0).map(|m| m.range()).as_ref() == Some(range) {
+                    let mut dst = String::new();
+                    captures.expand(replacement, &mut dst);
+                    return dst;
+                }
+            }
+        }
+
+        replacement.to_string()
+    }
+
+    /// Replace all the `matches` in the `text`.
+    ///
+    /// Returns the replaced range (from the first match start to the last match end) and the new text of it.
+    pub(super) fn replace_all(
+        &self,
+        text: &str,
+        matches: &[Range<usize>],
+        replacement: &str,
+    ) -> Option<(Range<usize>, String)> {
+        let range = matches.first()?.start..matches.last()?.end;
+
+        let mut new_text = String::new();
+        let mut offset = range.start;
+        for m in matches {
+            new_text.push_str(&text[offset..m.start]);
+            new_text.push_str(&self.replacement_for(text, m, replacement));
+            offset = m.end;
+        }
+
+        Some((range, new_text))
+    }
+}
+
+/// The state of the find/replace bar of the multi-line [`TextInput`].
+pub(super) struct SearchState {
+    query_input: Entity<TextInput>,
+    replace_input: Entity<TextInput>,
+    visible: bool,
+    show_replace: bool,
+    options: SearchOptions,
+    query: Option<SearchQuery>,
+    /// The UTF-8 ranges of the matches, sorted by the start.
+    pub(super) matches: Vec<Range<usize>>,
+    active_match: Option<usize>,
+}
+
+impl SearchState {
+    fn new(window: &mut Window, cx: &mut Context<TextInput>) -> Self {
+        let query_input = cx.new(|cx| {
+            TextInput::new(window, cx)
+                .placeholder(t!("Input.Find"))
+                .small()
+        });
+        let replace_input = cx.new(|cx| {
+            TextInput::new(window, cx)
+                .placeholder(t!("Input.Replace"))
+                .small()
+        });
+
+        cx.subscribe_in(&query_input, window, TextInput::on_search_query_event)
+            .detach();
+        cx.subscribe_in(&replace_input, window, TextInput::on_search_replace_event)
+            .detach();
+
+        Self {
+            query_input,
+            replace_input,
+            visible: false,
+            show_replace: false,
+            options: SearchOptions::default(),
+            query: None,
+            matches: vec![],
+            active_match: None,
+        }
+    }
+
+    /// Returns true if the find bar is open.
+    pub(super) fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl TextInput {
+    pub(super) fn find(&mut self, _: &Find, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_search(false, window, cx);
+    }
+
+    pub(super) fn find_and_replace(
+        &mut self,
+        _: &FindAndReplace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_search(true, window, cx);
+    }
+
+    pub(super) fn find_next(&mut self, _: &FindNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_adjacent_match(true, cx);
+    }
+
+    pub(super) fn find_previous(
+        &mut self,
+        _: &FindPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_adjacent_match(false, cx);
+    }
+
+    fn close_find(&mut self, _: &CloseFind, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+
+        search.visible = false;
+        search.matches.clear();
+        search.active_match = None;
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn replace_all(&mut self, _: &ReplaceAll, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_ref().filter(|search| search.visible) else {
+            return;
+        };
+        let Some(query) = search.query.as_ref() else {
+            return;
+        };
+
+        let text = self.text();
+        let replacement = search.replace_input.read(cx).text();
+        let Some((range, new_text)) = query.replace_all(&text, &search.matches, &replacement)
+        else {
+            return;
+        };
+
+        // Replace all the matches with one change, so it can be undone at once.
+        self.replace_text_in_range(Some(self.range_to_utf16(&range)), &new_text, window, cx);
+    }
+
+    /// Replace the current selected match, and select the next match.
+    fn replace_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_ref().filter(|search| search.visible) else {
+            return;
+        };
+        let Some(query) = search.query.as_ref() else {
+            return;
+        };
+
+        if search.matches.contains(&self.selected_range) {
+            let text = self.text();
+            let replacement = search.replace_input.read(cx).text();
+            let range = self.selected_range.clone();
+            let new_text = query.replacement_for(&text, &range, &replacement);
+            self.replace_text_in_range(Some(self.range_to_utf16(&range)), &new_text, window, cx);
+        }
+
+        self.select_adjacent_match(true, cx);
+    }
+
+    fn open_search(&mut self, show_replace: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_single_line() {
+            return;
+        }
+
+        let search = self
+            .search
+            .get_or_insert_with(|| SearchState::new(window, cx));
+        search.visible = true;
+        search.show_replace = show_replace;
+
+        // Use the selected text as the query, if it is in one line.
+        let query_input = search.query_input.clone();
+        if !self.selected_range.is_empty() {
+            let selected_text = self.text.slice(self.selected_range.clone());
+            if !selected_text.contains('\n') {
+                query_input.update(cx, |input, cx| {
+                    input.set_text(selected_text, window, cx);
+                });
+            }
+        }
+        query_input.update(cx, |input, cx| {
+            input.select_all(&super::SelectAll, window, cx);
+            input.focus(window, cx);
+        });
+
+        self.update_search_matches(cx);
+        cx.notify();
+    }
+
+    fn toggle_search_option(&mut self, f: impl FnOnce(&mut SearchOptions), cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+
+        f(&mut search.options);
+        let query = search.query_input.read(cx).text();
+        search.query = SearchQuery::new(&query, search.options);
+        self.update_search_matches(cx);
+        self.select_match_from(self.selected_range.start, cx);
+    }
+
+    fn on_search_query_event(
+        &mut self,
+        _: &Entity<TextInput>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.query = SearchQuery::new(text, search.options);
+                }
+                self.update_search_matches(cx);
+                self.select_match_from(self.selected_range.start, cx);
+            }
+            InputEvent::PressEnter => self.find_next(&FindNext, window, cx),
+            _ => {}
+        }
+    }
+
+    fn on_search_replace_event(
+        &mut self,
+        _: &Entity<TextInput>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::PressEnter = event {
+            self.replace_next(window, cx);
+        }
+    }
+
+    /// Find the matches of the current query again, this must be called after the text changed.
+    pub(super) fn update_search_matches(&mut self, cx: &mut Context<Self>) {
+        if !self.search.as_ref().is_some_and(|search| search.visible) {
+            return;
+        }
+        let text = self.text();
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+
+        search.matches = match search.query.as_ref() {
+            Some(query) => query.find_matches(&text),
+            None => vec![],
+        };
+        search.active_match = search
+            .matches
+            .iter()
+            .position(|range| *range == self.selected_range);
+        cx.notify();
+    }
+
+    /// Select the first match starts at or after the `offset`.
+    fn select_match_from(&mut self, offset: usize, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_ref() else {
+            return;
+        };
+        if search.matches.is_empty() {
+            return;
+        }
+
+        let ix = search
+            .matches
+            .iter()
+            .position(|range| range.start >= offset)
+            .unwrap_or(0);
+        self.select_match(ix, cx);
+    }
+
+    /// Select the next (or previous) match of the selection, wrapping around the text.
+    fn select_adjacent_match(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_ref().filter(|search| search.visible) else {
+            return;
+        };
+        let matches = &search.matches;
+        if matches.is_empty() {
+            return;
+        }
+
+        let ix = if forward {
+            matches
+                .iter()
+                .position(|range| range.start >= self.selected_range.end)
+                .unwrap_or(0)
+        } else {
+            matches
+                .iter()
+                .rposition(|range| range.end <= self.selected_range.start)
+                .unwrap_or(matches.len() - 1)
+        };
+        self.select_match(ix, cx);
+    }
+
+    fn select_match(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let Some(range) = search.matches.get(ix).cloned() else {
+            return;
+        };
+
+        search.active_match = Some(ix);
+        self.selected_range = range;
+        self.selections.clear();
+        self.selection_reversed = false;
+        cx.notify();
+    }
+
+    pub(super) fn render_search_bar(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let search = self.search.as_ref().filter(|search| search.visible)?;
+        let options = search.options;
+        let show_replace = search.show_replace;
+        let has_matches = !search.matches.is_empty();
+
+        let status = match search.active_match {
+            _ if search.query.is_none() => "".to_string(),
+            _ if !has_matches => t!("Input.No results").to_string(),
+            Some(ix) => format!("{}/{}", ix + 1, search.matches.len()),
+            None => format!("{}", search.matches.len()),
+        };
+
+        Some(
+            v_flex()
+                .id("search-bar")
+                .key_context(SEARCH_CONTEXT)
+                .on_action(cx.listener(Self::close_find))
+                .on_action(cx.listener(Self::replace_all))
+                .absolute()
+                .top_1()
+                .right_3()
+                .gap_1()
+                .p_1()
+                .bg(cx.theme().popover)
+                .text_color(cx.theme().popover_foreground)
+                .border_1()
+                .border_color(cx.theme().border)
+                .rounded(px(cx.theme().radius))
+                .shadow_md()
+                .cursor_default()
+                .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                .on_scroll_wheel(|_, _, cx| cx.stop_propagation())
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Button::new("toggle-replace")
+                                .ghost()
+                                .xsmall()
+                                .icon(if show_replace {
+                                    IconName::ChevronDown
+                                } else {
+                                    IconName::ChevronRight
+                                })
+                                .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                                    let show_replace = this
+                                        .search
+                                        .as_ref()
+                                        .map_or(false, |search| search.show_replace);
+                                    this.open_search(!show_replace, window, cx);
+                                })),
+                        )
+                        .child(div().w(px(180.)).child(search.query_input.clone()))
+                        .child(
+                            Button::new("case-sensitive")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::ALargeSmall)
+                                .selected(options.case_sensitive)
+                                .tooltip(t!("Input.Match Case"))
+                                .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                    this.toggle_search_option(
+                                        |options| options.case_sensitive = !options.case_sensitive,
+                                        cx,
+                                    )
+                                })),
+                        )
+                        .child(
+                            Button::new("whole-word")
+                                .ghost()
+                                .xsmall()
+                                .label("ab")
+                                .selected(options.whole_word)
+                                .tooltip(t!("Input.Match Whole Word"))
+                                .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                    this.toggle_search_option(
+                                        |options| options.whole_word = !options.whole_word,
+                                        cx,
+                                    )
+                                })),
+                        )
+                        .child(
+                            Button::new("regex")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::Asterisk)
+                                .selected(options.regex)
+                                .tooltip(t!("Input.Use Regular Expression"))
+                                .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                    this.toggle_search_option(
+                                        |options| options.regex = !options.regex,
+                                        cx,
+                                    )
+                                })),
+                        )
+                        .child(
+                            div()
+                                .min_w(px(60.))
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(status),
+                        )
+                        .child(
+                            Button::new("previous-match")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::ArrowUp)
+                                .disabled(!has_matches)
+                                .tooltip(t!("Input.Previous Match"))
+                                .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                                    this.find_previous(&FindPrevious, window, cx)
+                                })),
+                        )
+                        .child(
+                            Button::new("next-match")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::ArrowDown)
+                                .disabled(!has_matches)
+                                .tooltip(t!("Input.Next Match"))
+                                .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                                    this.find_next(&FindNext, window, cx)
+                                })),
+                        )
+                        .child(
+                            Button::new("close-find")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::Close)
+                                .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                                    this.close_find(&CloseFind, window, cx)
+                                })),
+                        ),
+                )
+                .when(show_replace, |this| {
+                    this.child(
+                        h_flex()
+                            .gap_1()
+                            // Align with the query input, after the toggle button.
+                            .pl_6()
+                            .child(div().w(px(180.)).child(search.replace_input.clone()))
+                            .child(
+                                Button::new("replace")
+                                    .ghost()
+                                    .xsmall()
+                                    .label(t!("Input.Replace"))
+                                    .disabled(!has_matches || self.disabled)
+                                    .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                                        this.replace_next(window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("replace-all")
+                                    .ghost()
+                                    .xsmall()
+                                    .label(t!("Input.Replace All"))
+                                    .disabled(!has_matches || self.disabled)
+                                    .on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                                        this.replace_all(&ReplaceAll, window, cx)
+                                    })),
+                            ),
+                    )
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches<'a>(text: &'a str, query: &str, options: SearchOptions) -> Vec<&'a str> {
+        SearchQuery::new(query, options)
+            .map(|query| query.find_matches(text))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let text = "Hello hello HELLO, hello_world\nhello.";
+        let options = SearchOptions::default();
+        assert_eq!(
+            matches(text, "hello", options),
+            vec!["Hello", "hello", "HELLO", "hello", "hello"]
+        );
+        assert_eq!(matches(text, "", options), Vec::<&str>::new());
+        assert_eq!(matches(text, "o.", options), vec!["o."]);
+
+        let options = SearchOptions {
+            case_sensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            matches(text, "hello", options),
+            vec!["hello", "hello", "hello"]
+        );
+
+        let options = SearchOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            matches(text, "hello", options),
+            vec!["Hello", "hello", "HELLO", "hello"]
+        );
+
+        let options = SearchOptions {
+            regex: true,
+            ..Default::default()
+        };
+        assert_eq!(matches(text, "^h\\w+", options), vec!["Hello", "hello"]);
+        assert_eq!(
+            matches(text, "o.", options),
+            vec!["o ", "o ", "O,", "o_", "or", "o."]
+        );
+        // Invalid regex and empty matches
+        assert_eq!(matches(text, "(", options), Vec::<&str>::new());
+        assert_eq!(matches(text, "x*", options), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let text = "foo = 1, bar = 2";
+        let query = SearchQuery::new("foo|bar", SearchOptions::default()).unwrap();
+        let matches = query.find_matches(text);
+        assert_eq!(matches, Vec::<Range<usize>>::new());
+
+        let options = SearchOptions {
+            regex: true,
+            ..Default::default()
+        };
+        let query = SearchQuery::new(r"(\w+) = (\d)", options).unwrap();
+        let matches = query.find_matches(text);
+        assert_eq!(
+            query.replace_all(text, &matches, "$2 = ${1}"),
+            Some((0..16, "1 = foo, 2 = bar".to_string()))
+        );
+        assert_eq!(
+            query.replacement_for(text, &matches[1], "$1"),
+            "bar".to_string()
+        );
+        assert_eq!(query.replace_all(text, &[], "x"), None);
+
+        let query = SearchQuery::new("$1", SearchOptions::default()).unwrap();
+        let text = "a $1 b";
+        let matches = query.find_matches(text);
+        assert_eq!(
+            query.replace_all(text, &matches, "$0"),
+            Some((2..4, "$0".to_string()))
+        );
+    }
+}
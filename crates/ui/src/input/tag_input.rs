@@ -0,0 +1,645 @@
+use gpui::{
+    anchored, canvas, deferred, div, prelude::FluentBuilder as _, px, App, AppContext as _, Bounds,
+    Context, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement,
+    ParentElement as _, Pixels, Render, ScrollHandle, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Subscription, Window,
+};
+
+use crate::{
+    dropdown::{DropdownDelegate, DropdownItem as _},
+    fuzzy, h_flex,
+    label::Label,
+    list::ListItem,
+    tag::Tag,
+    v_flex, ActiveTheme as _, Icon, IconName, Sizable, Size, StyleSized as _, StyledExt as _,
+};
+
+use super::{Backspace, Delete, Down, Escape, InputEvent, Left, Paste, Right, TextInput, Up};
+
+pub enum TagInputEvent {
+    /// A value is added by the user.
+    Add(SharedString),
+    /// A value is removed by the user.
+    Remove(SharedString),
+}
+
+/// An input to enter multiple values, the committed values are displayed as [`Tag`]s before the text.
+///
+/// The text is committed when pressing Enter, or typing (pasting) the separators.
+///
+/// The suggestions are the items of the [`DropdownDelegate`] that the title fuzzy matches the text,
+/// the title of the accepted item is added as the value.
+pub struct TagInput<D: DropdownDelegate + 'static = Vec<SharedString>> {
+    input: Entity<TextInput>,
+    values: Vec<SharedString>,
+    separators: Vec<char>,
+    validate: Option<Box<dyn Fn(&str) -> bool + 'static>>,
+    suggestions: Option<D>,
+    /// The indexes of the suggestions matched the text, and the positions of the matched chars.
+    matched_suggestions: Vec<(usize, Vec<usize>)>,
+    highlighted_suggestion: usize,
+    suggestions_scroll_handle: ScrollHandle,
+    /// The tag selected by the keyboard.
+    selected_tag: Option<usize>,
+    size: Size,
+    bounds: Bounds<Pixels>,
+    _subscriptions: Vec<Subscription>,
+    _synced_size: bool,
+}
+
+impl<D> TagInput<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new(window, cx).appearance(false));
+        let _subscriptions = vec![cx.subscribe_in(&input, window, Self::on_input_event)];
+
+        Self {
+            input,
+            values: vec![],
+            separators: vec![','],
+            validate: None,
+            suggestions: None,
+            matched_suggestions: vec![],
+            highlighted_suggestion: 0,
+            suggestions_scroll_handle: ScrollHandle::new(),
+            selected_tag: None,
+            size: Size::default(),
+            bounds: Bounds::default(),
+            _subscriptions,
+            _synced_size: false,
+        }
+    }
+
+    pub fn placeholder(
+        self,
+        placeholder: impl Into<SharedString>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        self.input
+            .update(cx, |input, _| input.set_placeholder(placeholder));
+        self
+    }
+
+    /// Set the separators to commit the text, default is `,`.
+    ///
+    /// The new line is always a separator when pasting.
+    pub fn separators(mut self, separators: impl Into<Vec<char>>) -> Self {
+        self.separators = separators.into();
+        self
+    }
+
+    /// Set the callback to validate the value before it is added, return false to reject it.
+    ///
+    /// The rejected text is kept in the input.
+    pub fn validate(mut self, validate: impl Fn(&str) -> bool + 'static) -> Self {
+        self.validate = Some(Box::new(validate));
+        self
+    }
+
+    /// Set the delegate to show the suggestions when typing.
+    pub fn suggestions(mut self, suggestions: D) -> Self {
+        self.suggestions = Some(suggestions);
+        self
+    }
+
+    pub fn set_size(&mut self, size: Size, window: &mut Window, cx: &mut Context<Self>) {
+        self.size = size;
+        self.sync_size_to_input_if_needed(window, cx);
+    }
+
+    pub fn set_disabled(&self, disabled: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.input
+            .update(cx, |input, cx| input.set_disabled(disabled, window, cx));
+    }
+
+    /// Returns the committed values.
+    pub fn values(&self) -> &[SharedString] {
+        &self.values
+    }
+
+    /// Set the values, the [`TagInputEvent`] will not be emitted.
+    pub fn set_values(
+        &mut self,
+        values: impl IntoIterator<Item = impl Into<SharedString>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.values.clear();
+        for value in values {
+            let value = value.into();
+            if !self.values.contains(&value) {
+                self.values.push(value);
+            }
+        }
+        self.selected_tag = None;
+        cx.notify();
+    }
+
+    /// Add a value, returns false if the value is empty, duplicated or rejected by the validate callback.
+    pub fn add_value(
+        &mut self,
+        value: impl Into<SharedString>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let value: SharedString = value.into();
+        let value: SharedString = value.trim().to_string().into();
+        if !self.is_valid(&value) {
+            return false;
+        }
+
+        self.values.push(value.clone());
+        cx.emit(TagInputEvent::Add(value));
+        cx.notify();
+        true
+    }
+
+    /// Remove the value at the `ix`.
+    pub fn remove_value(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Self>) {
+        if ix >= self.values.len() {
+            return;
+        }
+
+        let value = self.values.remove(ix);
+        self.selected_tag = None;
+        cx.emit(TagInputEvent::Remove(value));
+        cx.notify();
+    }
+
+    fn is_valid(&self, value: &str) -> bool {
+        !value.is_empty()
+            && !self.values.iter().any(|v| v.as_ref() == value)
+            && self
+                .validate
+                .as_ref()
+                .map_or(true, |validate| validate(value))
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        is_separator(&self.separators, c)
+    }
+
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        split_text(text, &self.separators)
+    }
+
+    fn is_disabled(&self, cx: &App) -> bool {
+        self.input.read(cx).disabled
+    }
+
+    /// Commit the segments of the text, returns the rejected segments to keep in the input.
+    fn commit(
+        &mut self,
+        segments: Vec<&str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> String {
+        let separator = self.separators.first().copied().unwrap_or(',');
+
+        let mut rejected = String::new();
+        for segment in segments {
+            let segment = segment.trim();
+            if segment.is_empty() || self.add_value(segment.to_string(), window, cx) {
+                continue;
+            }
+
+            rejected.push_str(segment);
+            rejected.push(separator);
+            rejected.push(' ');
+        }
+
+        rejected
+    }
+
+    fn set_text(&mut self, text: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.input.update(cx, |input, cx| {
+            if input.text().as_ref() == text {
+                return;
+            }
+
+            input.set_text(text, window, cx);
+            input.move_to(input.text().len(), window, cx);
+        });
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<TextInput>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                self.selected_tag = None;
+
+                // Commit the segments before the last separator, the last one is still typing.
+                let mut segments = self.split(text);
+                let typing = segments.pop().unwrap_or_default();
+                if !segments.is_empty() {
+                    let rejected = self.commit(segments, window, cx);
+                    self.set_text(rejected + typing.trim_start(), window, cx);
+                }
+
+                self.update_suggestions(window, cx);
+            }
+            InputEvent::PressEnter => {
+                if self.accept_suggestion(window, cx) {
+                    return;
+                }
+
+                let text = self.input.read(cx).text();
+                let rejected = self.commit(self.split(&text), window, cx);
+                let rejected = rejected
+                    .trim_end()
+                    .trim_end_matches(|c: char| self.is_separator(c));
+                self.set_text(rejected.to_string(), window, cx);
+            }
+            InputEvent::Blur => {
+                self.selected_tag = None;
+                self.matched_suggestions.clear();
+                cx.notify();
+            }
+            InputEvent::Focus => {}
+        }
+    }
+
+    fn update_suggestions(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        let text = self.input.read(cx).text();
+        let query = text.trim();
+        self.matched_suggestions = match self.suggestions.as_ref() {
+            Some(suggestions) if !query.is_empty() => {
+                let titles = (0..suggestions.len())
+                    .filter_map(|ix| suggestions.get(ix).map(|item| (ix, item.title())))
+                    .filter(|(_, title)| !self.values.contains(title))
+                    .collect::<Vec<_>>();
+                fuzzy::match_items(query, &titles, |(_, title)| title.clone())
+                    .into_iter()
+                    .map(|(ix, m)| (titles[ix].0, m.positions))
+                    .collect()
+            }
+            _ => vec![],
+        };
+        self.highlighted_suggestion = 0;
+        cx.notify();
+    }
+
+    /// Add the highlighted suggestion, returns false if there is no suggestion.
+    fn accept_suggestion(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(ix) = self
+            .matched_suggestions
+            .get(self.highlighted_suggestion)
+            .map(|(ix, _)| *ix)
+        else {
+            return false;
+        };
+
+        self.accept_suggestion_at(ix, window, cx);
+        true
+    }
+
+    fn accept_suggestion_at(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(title) = self
+            .suggestions
+            .as_ref()
+            .and_then(|suggestions| suggestions.get(ix))
+            .map(|item| item.title())
+        else {
+            return;
+        };
+
+        if self.add_value(title, window, cx) {
+            self.set_text(String::new(), window, cx);
+        }
+        self.matched_suggestions.clear();
+        cx.notify();
+    }
+
+    fn up(&mut self, _: &Up, _: &mut Window, cx: &mut Context<Self>) {
+        self.highlight_suggestion(-1, cx);
+    }
+
+    fn down(&mut self, _: &Down, _: &mut Window, cx: &mut Context<Self>) {
+        self.highlight_suggestion(1, cx);
+    }
+
+    fn highlight_suggestion(&mut self, delta: isize, cx: &mut Context<Self>) {
+        if self.matched_suggestions.is_empty() {
+            cx.propagate();
+            return;
+        }
+
+        let len = self.matched_suggestions.len() as isize;
+        self.highlighted_suggestion =
+            (self.highlighted_suggestion as isize + delta).rem_euclid(len) as usize;
+        self.suggestions_scroll_handle
+            .scroll_to_item(self.highlighted_suggestion);
+        cx.notify();
+    }
+
+    fn escape(&mut self, _: &Escape, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.matched_suggestions.is_empty() {
+            self.matched_suggestions.clear();
+        } else if self.selected_tag.is_some() {
+            self.selected_tag = None;
+        } else {
+            // Propagate the event to the parent view, for example to the Modal to support ESC to close.
+            cx.propagate();
+            return;
+        }
+
+        cx.notify();
+    }
+
+    /// Returns true if the cursor is at the start of the text without selection.
+    fn is_cursor_at_start(&self, cx: &App) -> bool {
+        let input = self.input.read(cx);
+        input.selected_range.is_empty() && input.cursor_offset() == 0
+    }
+
+    fn left(&mut self, _: &Left, _: &mut Window, cx: &mut Context<Self>) {
+        if self.is_disabled(cx) {
+            return;
+        }
+
+        self.selected_tag = match self.selected_tag {
+            Some(ix) => Some(ix.saturating_sub(1)),
+            None if !self.values.is_empty() && self.is_cursor_at_start(cx) => {
+                Some(self.values.len() - 1)
+            }
+            None => return,
+        };
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn right(&mut self, _: &Right, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.selected_tag else {
+            return;
+        };
+
+        // Move back to the text after the last tag.
+        self.selected_tag = (ix + 1 < self.values.len()).then_some(ix + 1);
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    /// Commit the pasted text split by the new lines and separators.
+    ///
+    /// The single line input strips the new lines when pasting, so the paste is handled here.
+    fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_disabled(cx) {
+            return;
+        }
+
+        let Some(pasted) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        if !pasted.contains(|c: char| self.is_separator(c)) {
+            return;
+        }
+
+        cx.stop_propagation();
+        self.selected_tag = None;
+
+        let input = self.input.read(cx);
+        let text = input.text();
+        let range = input.selected_range.clone();
+        let text = format!("{}{}{}", &text[..range.start], pasted, &text[range.end..]);
+
+        let rejected = self.commit(self.split(&text), window, cx);
+        let rejected = rejected
+            .trim_end()
+            .trim_end_matches(|c: char| self.is_separator(c));
+        self.set_text(rejected.to_string(), window, cx);
+        self.update_suggestions(window, cx);
+    }
+
+    fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_disabled(cx) {
+            return;
+        }
+
+        if let Some(ix) = self.selected_tag {
+            cx.stop_propagation();
+            self.remove_value(ix, window, cx);
+            self.selected_tag = ix.checked_sub(1).or((!self.values.is_empty()).then_some(0));
+        } else if !self.values.is_empty() && self.input.read(cx).text().is_empty() {
+            cx.stop_propagation();
+            self.remove_value(self.values.len() - 1, window, cx);
+        }
+    }
+
+    fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_disabled(cx) {
+            return;
+        }
+
+        if let Some(ix) = self.selected_tag {
+            cx.stop_propagation();
+            self.remove_value(ix, window, cx);
+            self.selected_tag = (ix < self.values.len()).then_some(ix);
+        }
+    }
+
+    fn sync_size_to_input_if_needed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self._synced_size {
+            self.input
+                .update(cx, |input, cx| input.set_size(self.size, window, cx));
+            self._synced_size = true;
+        }
+    }
+
+    fn render_suggestions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let suggestions = self.suggestions.as_ref();
+
+        deferred(
+            anchored().snap_to_window_with_margin(px(8.)).child(
+                v_flex()
+                    .id("suggestions")
+                    .occlude()
+                    .mt_1p5()
+                    .p_1()
+                    .gap_y_0p5()
+                    .w(self.bounds.size.width)
+                    .max_h(px(240.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.suggestions_scroll_handle)
+                    .popover_style(cx)
+                    .text_color(cx.theme().popover_foreground)
+                    .on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                        this.matched_suggestions.clear();
+                        cx.notify();
+                    }))
+                    .children(self.matched_suggestions.iter().enumerate().filter_map(
+                        |(ix, (item_ix, positions))| {
+                            let item_ix = *item_ix;
+                            let title = suggestions?.get(item_ix)?.title();
+
+                            Some(
+                                ListItem::new(("suggestion", ix))
+                                    .input_text_size(self.size)
+                                    .list_size(self.size)
+                                    .rounded_md()
+                                    .selected(ix == self.highlighted_suggestion)
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.accept_suggestion_at(item_ix, window, cx);
+                                    }))
+                                    .child(Label::new(title).highlights(positions.clone())),
+                            )
+                        },
+                    )),
+            ),
+        )
+        .with_priority(1)
+    }
+}
+
+impl<D> Focusable for TagInput<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl<D> EventEmitter<TagInputEvent> for TagInput<D> where D: DropdownDelegate + 'static {}
+impl<D> Sizable for TagInput<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+impl<D> Render for TagInput<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.input.focus_handle(cx).is_focused(window);
+        let disabled = self.is_disabled(cx);
+        let view = cx.model().clone();
+
+        // Sync size to input at first.
+        self.sync_size_to_input_if_needed(window, cx);
+
+        div()
+            .relative()
+            .w_full()
+            .capture_action(cx.listener(Self::left))
+            .capture_action(cx.listener(Self::right))
+            .capture_action(cx.listener(Self::backspace))
+            .capture_action(cx.listener(Self::delete))
+            .capture_action(cx.listener(Self::paste))
+            .on_action(cx.listener(Self::up))
+            .on_action(cx.listener(Self::down))
+            .on_action(cx.listener(Self::escape))
+            .child(
+                h_flex()
+                    .id("tag-input")
+                    .w_full()
+                    .flex_wrap()
+                    .gap_1()
+                    .input_px(self.size)
+                    .py_0p5()
+                    .input_text_size(self.size)
+                    .bg(if disabled {
+                        cx.theme().muted
+                    } else {
+                        cx.theme().background
+                    })
+                    .border_color(cx.theme().input)
+                    .border_1()
+                    .rounded(px(cx.theme().radius))
+                    .when(cx.theme().shadow, |this| this.shadow_sm())
+                    .when(focused, |this| this.outline(cx))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.input.focus_handle(cx).focus(window);
+                    }))
+                    .children(self.values.iter().enumerate().map(|(ix, value)| {
+                        let selected = self.selected_tag == Some(ix);
+
+                        div()
+                            .id(("tag", ix))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.selected_tag = Some(ix);
+                                this.input.focus_handle(cx).focus(window);
+                                cx.notify();
+                            }))
+                            .child(
+                                if selected {
+                                    Tag::primary()
+                                } else {
+                                    Tag::secondary()
+                                }
+                                .small()
+                                .child(
+                                    h_flex()
+                                        .gap_1()
+                                        .child(value.clone())
+                                        .when(!disabled, |this| {
+                                            this.child(
+                                                div()
+                                                    .id(("remove-tag", ix))
+                                                    .cursor_pointer()
+                                                    .on_click(cx.listener(
+                                                        move |this, _, window, cx| {
+                                                            cx.stop_propagation();
+                                                            this.remove_value(ix, window, cx);
+                                                        },
+                                                    ))
+                                                    .child(Icon::new(IconName::Close).xsmall()),
+                                            )
+                                        }),
+                                ),
+                            )
+                    }))
+                    .child(div().flex_1().min_w(px(80.)).child(self.input.clone()))
+                    .child(
+                        canvas(
+                            move |bounds, _, cx| view.update(cx, |r, _| r.bounds = bounds),
+                            |_, _, _, _| {},
+                        )
+                        .absolute()
+                        .size_full(),
+                    ),
+            )
+            .when(focused && !self.matched_suggestions.is_empty(), |this| {
+                this.child(self.render_suggestions(cx))
+            })
+    }
+}
+
+fn is_separator(separators: &[char], c: char) -> bool {
+    c == '\n' || separators.contains(&c)
+}
+
+/// Split the text by the new lines (`\n` or `\r\n`) and the separators.
+fn split_text<'a>(text: &'a str, separators: &[char]) -> Vec<&'a str> {
+    text.split('\n')
+        .flat_map(|line| line.strip_suffix('\r').unwrap_or(line).split(separators))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_text;
+
+    #[test]
+    fn test_split_text() {
+        assert_eq!(split_text("a, b,c", &[',']), vec!["a", " b", "c"]);
+        assert_eq!(split_text("a\nb\r\nc", &[',']), vec!["a", "b", "c"]);
+        assert_eq!(
+            split_text("a;b\r\nc,d\n", &[',', ';']),
+            vec!["a", "b", "c", "d", ""]
+        );
+        assert_eq!(split_text("a b", &[',']), vec!["a b"]);
+    }
+}
@@ -1,6 +1,8 @@
+use std::ops::Range;
+
 use gpui::{
-    div, prelude::FluentBuilder, rems, App, Div, IntoElement, ParentElement, RenderOnce,
-    SharedString, Styled, Window,
+    div, prelude::FluentBuilder, rems, AnyElement, App, Div, FontWeight, HighlightStyle,
+    IntoElement, ParentElement, RenderOnce, SharedString, Styled, StyledText, Window,
 };
 
 use crate::{h_flex, ActiveTheme};
@@ -21,6 +23,7 @@ pub struct Label {
     label: SharedString,
     align: TextAlign,
     marked: bool,
+    highlights: Vec<usize>,
 }
 
 impl Label {
@@ -30,6 +33,7 @@ impl Label {
             label: label.into(),
             align: TextAlign::default(),
             marked: false,
+            highlights: vec![],
         }
     }
 
@@ -57,6 +61,29 @@ impl Label {
         self.marked = masked;
         self
     }
+
+    /// Highlight the chars at the byte offsets, e.g.: the positions of a [`crate::fuzzy::FuzzyMatch`].
+    pub fn highlights(mut self, positions: impl Into<Vec<usize>>) -> Self {
+        self.highlights = positions.into();
+        self
+    }
+}
+
+/// Returns the byte ranges of the chars at the `positions`, the adjacent chars are merged.
+fn highlight_ranges(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for &ix in positions {
+        let Some(c) = text.get(ix..).and_then(|s| s.chars().next()) else {
+            continue;
+        };
+
+        let range = ix..ix + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range),
+        }
+    }
+    ranges
 }
 
 impl Styled for Label {
@@ -66,13 +93,25 @@ impl Styled for Label {
 }
 
 impl RenderOnce for Label {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let text = self.label;
 
-        let text_display = if self.marked {
-            MASKED.repeat(text.chars().count())
+        let text_display: AnyElement = if self.marked {
+            MASKED.repeat(text.chars().count()).into_any_element()
+        } else if !self.highlights.is_empty() {
+            let style = HighlightStyle {
+                color: Some(cx.theme().link),
+                font_weight: Some(FontWeight::SEMIBOLD),
+                ..Default::default()
+            };
+            let highlights = highlight_ranges(&text, &self.highlights)
+                .into_iter()
+                .map(|range| (range, style));
+            StyledText::new(text)
+                .with_highlights(&window.text_style(), highlights)
+                .into_any_element()
         } else {
-            text.to_string()
+            text.to_string().into_any_element()
         };
 
         div().text_color(cx.theme().foreground).child(
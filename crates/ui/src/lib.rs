@@ -18,12 +18,14 @@ pub mod button;
 pub mod checkbox;
 pub mod clipboard;
 pub mod color_picker;
+pub mod command_palette;
 pub mod context_menu;
 pub mod divider;
 pub mod dock;
 pub mod drawer;
 pub mod dropdown;
 pub mod form;
+pub mod fuzzy;
 pub mod history;
 pub mod indicator;
 pub mod input;
@@ -48,6 +50,7 @@ pub mod table;
 pub mod tag;
 pub mod theme;
 pub mod tooltip;
+pub mod tree;
 pub mod webview;
 
 use gpui::App;
@@ -87,6 +90,7 @@ pub struct Assets;
 /// You can initialize the UI module at your application's entry point.
 pub fn init(cx: &mut App) {
     theme::init(cx);
+    command_palette::init(cx);
     date_picker::init(cx);
     dock::init(cx);
     drawer::init(cx);
@@ -97,6 +101,7 @@ pub fn init(cx: &mut App) {
     popover::init(cx);
     popup_menu::init(cx);
     table::init(cx);
+    tree::init(cx);
 }
 
 #[inline]
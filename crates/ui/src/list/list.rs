@@ -3,22 +3,34 @@ use std::{cell::Cell, rc::Rc};
 
 use crate::Icon;
 use crate::{
+    h_flex,
     input::{InputEvent, TextInput},
     scroll::{Scrollbar, ScrollbarState},
-    v_flex, ActiveTheme, IconName, Size,
+    v_flex, ActiveTheme, IconName, Sizable as _, Size,
 };
 use gpui::{
-    actions, div, prelude::FluentBuilder, uniform_list, AnyElement, AppContext, Entity,
-    FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding, Length,
-    ListSizingBehavior, MouseButton, ParentElement, Render, SharedString, Styled, Task,
-    UniformListScrollHandle, Window,
+    actions, div, prelude::FluentBuilder, uniform_list, AnyElement, AppContext, DragMoveEvent,
+    Entity, EntityId, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding, Length,
+    ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement, Pixels, Render, SharedString,
+    StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window,
 };
 use gpui::{px, App, Context, EventEmitter, ScrollStrategy};
 use smol::Timer;
 
 use super::loading::Loading;
 
-actions!(list, [Cancel, Confirm, SelectPrev, SelectNext]);
+actions!(
+    list,
+    [
+        Cancel,
+        Confirm,
+        SelectPrev,
+        SelectNext,
+        SelectToPrev,
+        SelectToNext,
+        SelectAll
+    ]
+);
 
 pub fn init(cx: &mut App) {
     let context: Option<&str> = Some("List");
@@ -27,6 +39,12 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("enter", Confirm, context),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("shift-up", SelectToPrev, context),
+        KeyBinding::new("shift-down", SelectToNext, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", SelectAll, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", SelectAll, context),
     ]);
 }
 
@@ -34,12 +52,79 @@ pub fn init(cx: &mut App) {
 pub enum ListEvent {
     /// Move to select item.
     Select(usize),
+    /// The selected items are changed in the multiple selection mode.
+    SelectMultiple(Vec<usize>),
     /// Click on item or pressed Enter.
     Confirm(usize),
     /// Pressed ESC to deselect the item.
     Cancel,
 }
 
+/// The path of an item in a sectioned list, see [`ListDelegate::sections_count`].
+///
+/// A `usize` is converted to the index of the item in all the sections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexPath {
+    pub section: usize,
+    /// The index of the item in the section.
+    pub row: usize,
+}
+
+impl IndexPath {
+    pub fn new(section: usize, row: usize) -> Self {
+        Self { section, row }
+    }
+}
+
+impl From<usize> for IndexPath {
+    fn from(ix: usize) -> Self {
+        Self::new(0, ix)
+    }
+}
+
+impl From<(usize, usize)> for IndexPath {
+    fn from((section, row): (usize, usize)) -> Self {
+        Self::new(section, row)
+    }
+}
+
+/// The distance to the edges of the list to auto scroll when dragging an item.
+const AUTO_SCROLL_EDGE: Pixels = px(32.);
+/// The max distance to scroll in each frame when auto scrolling.
+const AUTO_SCROLL_SPEED: Pixels = px(8.);
+
+/// The payload of dragging an item to reorder, see [`List::reorderable`].
+#[derive(Clone)]
+pub(crate) struct DragListItem {
+    entity_id: EntityId,
+    ix: usize,
+    size: gpui::Size<Pixels>,
+}
+
+impl Render for DragListItem {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("drag-list-item")
+            .cursor_grab()
+            .w(self.size.width)
+            .h(self.size.height)
+            .border_1()
+            .border_color(cx.theme().drag_border)
+            .rounded_md()
+            .bg(cx.theme().list_active)
+            .opacity(0.75)
+    }
+}
+
+/// A row of the uniform list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListRow {
+    /// The header of the section.
+    Header(usize),
+    /// The item at the index of all the sections.
+    Item(usize),
+}
+
 /// A delegate for the List.
 #[allow(unused)]
 pub trait ListDelegate: Sized + 'static {
@@ -56,9 +141,40 @@ pub trait ListDelegate: Sized + 'static {
         Task::ready(())
     }
 
-    /// Return the number of items in the list.
+    /// Return the number of items in the list, the sum of the items in all the sections.
     fn items_count(&self, cx: &App) -> usize;
 
+    /// Return the number of the sections, default is 1.
+    ///
+    /// When there are more than one section, a header row is displayed before the items of each
+    /// non-empty section, and the header of the first visible section sticks to the top.
+    fn sections_count(&self, cx: &App) -> usize {
+        1
+    }
+
+    /// Return the number of items in the section.
+    ///
+    /// The items are indexed continuously across the sections in the other methods, e.g.: `render_item`.
+    fn section_items_count(&self, section: usize, cx: &App) -> usize {
+        if section == 0 {
+            self.items_count(cx)
+        } else {
+            0
+        }
+    }
+
+    /// Render the header of the section.
+    ///
+    /// The header should have the same height as the items, the list is a uniform list.
+    fn render_section_header(
+        &self,
+        section: usize,
+        window: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) -> Option<AnyElement> {
+        None
+    }
+
     /// Render the item at the given index.
     ///
     /// Return None will skip the item.
@@ -69,6 +185,13 @@ pub trait ListDelegate: Sized + 'static {
         cx: &mut Context<List<Self>>,
     ) -> Option<Self::Item>;
 
+    /// Returns false if the item at the given index can not be selected, e.g.: a disabled item.
+    ///
+    /// The keyboard navigation skips the items that can not be selected.
+    fn can_select(&self, ix: usize, cx: &App) -> bool {
+        true
+    }
+
     /// Return a Element to show when list is empty.
     fn render_empty(&self, window: &mut Window, cx: &mut Context<List<Self>>) -> impl IntoElement {
         div()
@@ -111,12 +234,35 @@ pub trait ListDelegate: Sized + 'static {
         cx: &mut Context<List<Self>>,
     );
 
+    /// Set the selected indices in the multiple selection mode, the indices are sorted.
+    ///
+    /// See [`List::multiple`].
+    fn set_selected_indices(
+        &mut self,
+        indices: &[usize],
+        window: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+    }
+
     /// Set the confirm and give the selected index, this is means user have clicked the item or pressed Enter.
     fn confirm(&mut self, ix: usize, window: &mut Window, cx: &mut Context<List<Self>>) {}
 
     /// Cancel the selection, e.g.: Pressed ESC.
     fn cancel(&mut self, window: &mut Window, cx: &mut Context<List<Self>>) {}
 
+    /// Move the item at `from` to `to` when the item is dropped in the reorder mode, see [`List::reorderable`].
+    ///
+    /// The `to` is the index after the item is removed, the same as `Vec::remove` then `Vec::insert`.
+    fn move_item(
+        &mut self,
+        from: usize,
+        to: usize,
+        window: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+    }
+
     /// Return true to enable load more data when scrolling to the bottom.
     ///
     /// Default: true
@@ -140,6 +286,22 @@ pub trait ListDelegate: Sized + 'static {
     /// This is always called when the table is near the bottom,
     /// so you must check if there is more data to load or lock the loading state.
     fn load_more(&mut self, window: &mut Window, cx: &mut Context<List<Self>>) {}
+
+    /// Return true to enable load more data when scrolling to the top.
+    ///
+    /// Default: false
+    fn can_load_more_before(&self, cx: &App) -> bool {
+        false
+    }
+
+    /// Load more data before the first item when the list is scrolled to the top,
+    /// the `load_more_threshold` is used as the remaining number of rows above the viewport.
+    ///
+    /// After inserting the items, call [`List::prepend_items`] to keep the first visible item in place.
+    ///
+    /// This is always called when the list is near the top,
+    /// so you must check if there is more data to load or lock the loading state.
+    fn load_more_before(&mut self, window: &mut Window, cx: &mut Context<List<Self>>) {}
 }
 
 pub struct List<D: ListDelegate> {
@@ -147,17 +309,35 @@ pub struct List<D: ListDelegate> {
     delegate: D,
     max_height: Option<Length>,
     query_input: Option<Entity<TextInput>>,
+    query_input_visible: bool,
     last_query: Option<String>,
     selectable: bool,
+    multiple: bool,
     querying: bool,
     scrollbar_visible: bool,
     vertical_scroll_handle: UniformListScrollHandle,
     scrollbar_state: Rc<Cell<ScrollbarState>>,
     pub(crate) size: Size,
     selected_index: Option<usize>,
+    /// The selected items in the multiple selection mode, sorted.
+    selected_indices: Vec<usize>,
+    /// The start of the range selection in the multiple selection mode.
+    anchor_index: Option<usize>,
     right_clicked_index: Option<usize>,
+    first_visible_index: usize,
     _search_task: Task<()>,
     _load_more_task: Task<()>,
+    _load_more_before_task: Task<()>,
+    stick_to_bottom: bool,
+    reorderable: bool,
+    /// The position to insert the dragging item, in `0..=items_count`.
+    drop_ix: Option<usize>,
+    /// The distance to scroll in each frame when dragging an item near the edges.
+    auto_scroll_delta: Pixels,
+    auto_scrolling: bool,
+    _auto_scroll_task: Task<()>,
+    /// The number of rows in the last render, to detect the new rows.
+    last_rows_count: usize,
 }
 
 impl<D> List<D>
@@ -180,18 +360,31 @@ where
             focus_handle: cx.focus_handle(),
             delegate,
             query_input: Some(query_input),
+            query_input_visible: true,
             last_query: None,
             selected_index: None,
+            selected_indices: vec![],
+            anchor_index: None,
             right_clicked_index: None,
+            first_visible_index: 0,
             vertical_scroll_handle: UniformListScrollHandle::new(),
             scrollbar_state: Rc::new(Cell::new(ScrollbarState::new())),
             max_height: None,
             scrollbar_visible: true,
             selectable: true,
+            multiple: false,
             querying: false,
             size: Size::default(),
             _search_task: Task::ready(()),
             _load_more_task: Task::ready(()),
+            _load_more_before_task: Task::ready(()),
+            stick_to_bottom: false,
+            reorderable: false,
+            drop_ix: None,
+            auto_scroll_delta: px(0.),
+            auto_scrolling: false,
+            _auto_scroll_task: Task::ready(()),
+            last_rows_count: 0,
         }
     }
 
@@ -216,6 +409,24 @@ where
         self
     }
 
+    /// Keep the list scrolled to the bottom when new items are added, e.g.: a chat or a log.
+    ///
+    /// The list stops following the new items when the user scrolls up,
+    /// and follows again after scrolling back to the bottom.
+    pub fn stick_to_bottom(mut self) -> Self {
+        self.stick_to_bottom = true;
+        self
+    }
+
+    /// Enable the reorder mode, a drag handle is displayed before each item,
+    /// drag it to move the item, see [`ListDelegate::move_item`].
+    ///
+    /// The list scrolls automatically when dragging near the top or bottom edge.
+    pub fn reorderable(mut self) -> Self {
+        self.reorderable = true;
+        self
+    }
+
     pub fn no_query(mut self) -> Self {
         self.query_input = None;
         self
@@ -227,6 +438,26 @@ where
         self
     }
 
+    /// Enable the multiple selection mode.
+    ///
+    /// - Ctrl (Cmd on macOS) click to toggle an item.
+    /// - Shift click or `shift-up`, `shift-down` to select a range.
+    /// - `ctrl-a` (`cmd-a` on macOS) to select all items, when the query input is not focused.
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Set the multiple selection mode, the selection is cleared when disabled, see [`List::multiple`].
+    pub fn set_multiple(&mut self, multiple: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.multiple = multiple;
+        if !multiple {
+            self.anchor_index = None;
+            self.set_selected_indices(vec![], window, cx);
+        }
+        cx.notify();
+    }
+
     pub fn set_query_input(
         &mut self,
         query_input: Entity<TextInput>,
@@ -238,6 +469,14 @@ where
         self.query_input = Some(query_input);
     }
 
+    /// Set the visibility of the query input, default is true.
+    ///
+    /// Hide it to search by an input rendered outside the list, see [`List::set_query_input`].
+    pub fn set_query_input_visible(&mut self, visible: bool, cx: &mut Context<Self>) {
+        self.query_input_visible = visible;
+        cx.notify();
+    }
+
     pub fn delegate(&self) -> &D {
         &self.delegate
     }
@@ -266,6 +505,44 @@ where
         self.selected_index
     }
 
+    /// Returns the selected items in the multiple selection mode, sorted.
+    pub fn selected_indices(&self) -> &[usize] {
+        &self.selected_indices
+    }
+
+    /// Set the selected items in the multiple selection mode, the items can not be selected are ignored.
+    pub fn set_selected_indices(
+        &mut self,
+        indices: impl Into<Vec<usize>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let items_count = self.delegate.items_count(cx);
+        let mut indices = indices.into();
+        indices.retain(|ix| *ix < items_count && self.delegate.can_select(*ix, cx));
+        indices.sort_unstable();
+        indices.dedup();
+        if indices == self.selected_indices {
+            return;
+        }
+
+        self.selected_indices = indices;
+        self.delegate
+            .set_selected_indices(&self.selected_indices, window, cx);
+        cx.emit(ListEvent::SelectMultiple(self.selected_indices.clone()));
+        cx.notify();
+    }
+
+    /// Select all the items in the multiple selection mode.
+    pub fn select_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.multiple {
+            return;
+        }
+
+        let items_count = self.delegate.items_count(cx);
+        self.set_selected_indices((0..items_count).collect::<Vec<_>>(), window, cx);
+    }
+
     /// Set the query_input text
     pub fn set_query(&mut self, query: &str, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(query_input) = &self.query_input {
@@ -291,10 +568,16 @@ where
         ))
     }
 
-    /// Scroll to the item at the given index.
-    pub fn scroll_to_item(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Self>) {
-        self.vertical_scroll_handle
-            .scroll_to_item(ix, ScrollStrategy::Top);
+    /// Scroll to the item at the given index, or the [`IndexPath`] of a sectioned list.
+    pub fn scroll_to_item(
+        &mut self,
+        path: impl Into<IndexPath>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let path = path.into();
+        let ix = self.section_start(path.section, cx) + path.row;
+        self.scroll_to_row_of_item(ix, cx);
         cx.notify();
     }
 
@@ -303,11 +586,151 @@ where
         &self.vertical_scroll_handle
     }
 
-    fn scroll_to_selected_item(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
-        if let Some(ix) = self.selected_index {
+    /// Tell the list that `count` items have been inserted before the first item in the delegate,
+    /// the scroll offset is adjusted to keep the first visible item in place.
+    ///
+    /// The selected items are moved with the inserted items.
+    pub fn prepend_items(&mut self, count: usize, _: &mut Window, cx: &mut Context<Self>) {
+        if count == 0 {
+            return;
+        }
+
+        self.selected_index = self.selected_index.map(|ix| ix + count);
+        self.anchor_index = self.anchor_index.map(|ix| ix + count);
+        self.right_clicked_index = self.right_clicked_index.map(|ix| ix + count);
+        self.selected_indices.iter_mut().for_each(|ix| *ix += count);
+
+        // The rows are uniform, so the height of a row is the content height of the last layout
+        // divided by the number of rows before inserting.
+        let last_rows_count = self.rows_count(cx).saturating_sub(count);
+        let mut state = self.vertical_scroll_handle.0.borrow_mut();
+        if let Some(size) = state.last_item_size.filter(|_| last_rows_count > 0) {
+            let row_height = size.contents.height / last_rows_count as f32;
+            let mut offset = state.base_handle.offset();
+            offset.y -= row_height * count as f32;
+            state.base_handle.set_offset(offset);
+        }
+        drop(state);
+
+        self.first_visible_index += count;
+        self.last_rows_count += count;
+        cx.notify();
+    }
+
+    /// Returns true if the list is scrolled to the bottom in the last layout.
+    fn is_scrolled_to_bottom(&self) -> bool {
+        let state = self.vertical_scroll_handle.0.borrow();
+        let Some(size) = state.last_item_size else {
+            return true;
+        };
+
+        let viewport_height = state.base_handle.bounds().size.height;
+        let max_offset = size.contents.height - viewport_height;
+        -state.base_handle.offset().y >= max_offset - px(1.)
+    }
+
+    /// Scroll to the last row when the new rows are added in the stick to bottom mode.
+    fn stick_to_bottom_if_need(&mut self, rows_count: usize) {
+        let last_rows_count = std::mem::replace(&mut self.last_rows_count, rows_count);
+        if !self.stick_to_bottom || rows_count <= last_rows_count || rows_count == 0 {
+            return;
+        }
+
+        if self.is_scrolled_to_bottom() {
             self.vertical_scroll_handle
-                .scroll_to_item(ix, ScrollStrategy::Top);
+                .scroll_to_item(rows_count - 1, ScrollStrategy::Top);
+        }
+    }
+
+    fn scroll_to_selected_item(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.scroll_to_row_of_item(ix, cx);
+        }
+    }
+
+    fn scroll_to_row_of_item(&mut self, ix: usize, cx: &App) {
+        let mut row_ix = self.row_ix(ix, cx);
+        // Keep the row before the item visible, the item is not covered by the sticky header.
+        if self.is_sectioned(cx) {
+            row_ix = row_ix.saturating_sub(1);
+        }
+
+        self.vertical_scroll_handle
+            .scroll_to_item(row_ix, ScrollStrategy::Top);
+    }
+
+    fn is_sectioned(&self, cx: &App) -> bool {
+        self.delegate.sections_count(cx) > 1
+    }
+
+    /// Returns the index of the first item in the section.
+    fn section_start(&self, section: usize, cx: &App) -> usize {
+        (0..section)
+            .map(|section| self.delegate.section_items_count(section, cx))
+            .sum()
+    }
+
+    /// Returns the number of rows, including the section headers.
+    fn rows_count(&self, cx: &App) -> usize {
+        let items_count = self.delegate.items_count(cx);
+        if !self.is_sectioned(cx) {
+            return items_count;
         }
+
+        let headers_count = (0..self.delegate.sections_count(cx))
+            .filter(|section| self.delegate.section_items_count(*section, cx) > 0)
+            .count();
+        items_count + headers_count
+    }
+
+    /// Returns the row at the `row_ix` of the uniform list.
+    fn row(&self, row_ix: usize, cx: &App) -> ListRow {
+        if !self.is_sectioned(cx) {
+            return ListRow::Item(row_ix);
+        }
+
+        let (mut row_start, mut item_start) = (0, 0);
+        for section in 0..self.delegate.sections_count(cx) {
+            let count = self.delegate.section_items_count(section, cx);
+            if count == 0 {
+                continue;
+            }
+
+            if row_ix == row_start {
+                return ListRow::Header(section);
+            }
+            if row_ix <= row_start + count {
+                return ListRow::Item(item_start + row_ix - row_start - 1);
+            }
+            row_start += count + 1;
+            item_start += count;
+        }
+
+        ListRow::Item(item_start + row_ix - row_start)
+    }
+
+    /// Returns the row index of the item at the `ix`.
+    fn row_ix(&self, ix: usize, cx: &App) -> usize {
+        if !self.is_sectioned(cx) {
+            return ix;
+        }
+
+        let mut item_start = 0;
+        let mut headers_count = 0;
+        for section in 0..self.delegate.sections_count(cx) {
+            let count = self.delegate.section_items_count(section, cx);
+            if count == 0 {
+                continue;
+            }
+
+            headers_count += 1;
+            if ix < item_start + count {
+                break;
+            }
+            item_start += count;
+        }
+
+        ix + headers_count
     }
 
     fn on_query_input_event(
@@ -318,34 +741,41 @@ where
         cx: &mut Context<Self>,
     ) {
         match event {
-            InputEvent::Change(text) => {
-                let text = text.trim().to_string();
-                if Some(&text) == self.last_query.as_ref() {
-                    return;
-                }
+            InputEvent::Change(text) => self.search(text, window, cx),
+            InputEvent::PressEnter => self.on_action_confirm(&Confirm, window, cx),
+            _ => {}
+        }
+    }
 
-                self.set_querying(true, window, cx);
-                let search = self.delegate.perform_search(&text, window, cx);
+    /// Perform the search by the delegate, it is called when the query input is changed.
+    pub fn search(&mut self, query: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let query = query.trim().to_string();
+        if Some(&query) == self.last_query.as_ref() {
+            return;
+        }
 
-                self._search_task = cx.spawn_in(window, |this, mut window| async move {
-                    search.await;
+        // The items are changed by the search.
+        self.anchor_index = None;
+        self.set_selected_indices(vec![], window, cx);
 
-                    _ = this.update_in(&mut window, |this, _, _| {
-                        this.vertical_scroll_handle
-                            .scroll_to_item(0, ScrollStrategy::Top);
-                        this.last_query = Some(text);
-                    });
+        self.set_querying(true, window, cx);
+        let search = self.delegate.perform_search(&query, window, cx);
 
-                    // Always wait 100ms to avoid flicker
-                    Timer::after(Duration::from_millis(100)).await;
-                    _ = this.update_in(&mut window, |this, window, cx| {
-                        this.set_querying(false, window, cx);
-                    });
-                });
-            }
-            InputEvent::PressEnter => self.on_action_confirm(&Confirm, window, cx),
-            _ => {}
-        }
+        self._search_task = cx.spawn_in(window, |this, mut window| async move {
+            search.await;
+
+            _ = this.update_in(&mut window, |this, _, _| {
+                this.vertical_scroll_handle
+                    .scroll_to_item(0, ScrollStrategy::Top);
+                this.last_query = Some(query);
+            });
+
+            // Always wait 100ms to avoid flicker
+            Timer::after(Duration::from_millis(100)).await;
+            _ = this.update_in(&mut window, |this, window, cx| {
+                this.set_querying(false, window, cx);
+            });
+        });
     }
 
     fn set_querying(&mut self, querying: bool, window: &mut Window, cx: &mut Context<Self>) {
@@ -379,8 +809,41 @@ where
         }
     }
 
+    /// Dispatch delegate's `load_more_before` method when the visible range is near the start.
+    fn load_more_before_if_need(
+        &mut self,
+        visible_start: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if visible_start >= self.delegate.load_more_threshold() {
+            return;
+        }
+        if !self.delegate.can_load_more_before(cx) {
+            return;
+        }
+
+        self._load_more_before_task = cx.spawn_in(window, |view, mut cx| async move {
+            _ = view.update_in(&mut cx, |view, window, cx| {
+                view.delegate.load_more_before(window, cx);
+            });
+        });
+    }
+
+    /// Store the first visible row to render the sticky header.
+    fn update_first_visible_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.first_visible_index == ix {
+            return;
+        }
+
+        self.first_visible_index = ix;
+        cx.notify();
+    }
+
     fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
         self.set_selected_index(None, window, cx);
+        self.anchor_index = None;
+        self.set_selected_indices(vec![], window, cx);
         self.delegate.cancel(window, cx);
         cx.emit(ListEvent::Cancel);
         cx.notify();
@@ -404,55 +867,361 @@ where
         self.selected_index = Some(ix);
         self.delegate.set_selected_index(Some(ix), window, cx);
         self.scroll_to_selected_item(window, cx);
+        if self.multiple {
+            self.anchor_index = Some(ix);
+            self.set_selected_indices(vec![ix], window, cx);
+        }
         cx.emit(ListEvent::Select(ix));
         cx.notify();
     }
 
+    /// Move the selected item to `ix` and select the range from the anchor to it.
+    fn select_range_to(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let anchor = *self
+            .anchor_index
+            .get_or_insert(self.selected_index.unwrap_or(ix));
+        self.selected_index = Some(ix);
+        self.delegate.set_selected_index(Some(ix), window, cx);
+        self.scroll_to_selected_item(window, cx);
+        self.set_selected_indices(
+            (anchor.min(ix)..=anchor.max(ix)).collect::<Vec<_>>(),
+            window,
+            cx,
+        );
+        cx.emit(ListEvent::Select(ix));
+    }
+
+    /// Toggle the item at `ix` in the selected items.
+    fn toggle_selected_item(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let mut indices = self.selected_indices.clone();
+        match indices.binary_search(&ix) {
+            Ok(pos) => {
+                indices.remove(pos);
+            }
+            Err(pos) => indices.insert(pos, ix),
+        }
+
+        self.anchor_index = Some(ix);
+        self.selected_index = Some(ix);
+        self.delegate.set_selected_index(Some(ix), window, cx);
+        self.set_selected_indices(indices, window, cx);
+        cx.emit(ListEvent::Select(ix));
+    }
+
+    /// Select the previous item, the same as pressing the up key.
+    pub fn select_prev(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_action_select_prev(&SelectPrev, window, cx);
+    }
+
+    /// Select the next item, the same as pressing the down key.
+    pub fn select_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_action_select_next(&SelectNext, window, cx);
+    }
+
+    /// Returns the next index can be selected from the `ix` in the direction, wraps around the list.
+    fn next_selectable_index(&self, ix: Option<usize>, forward: bool, cx: &App) -> Option<usize> {
+        let items_count = self.delegate.items_count(cx);
+        let mut ix = ix;
+        for _ in 0..items_count {
+            let next_ix = match ix {
+                // When no selected index, select the first or the last item.
+                None if forward => 0,
+                None => items_count - 1,
+                Some(ix) if forward => {
+                    if ix < items_count - 1 {
+                        ix + 1
+                    } else {
+                        // When the last item is selected, select the first item.
+                        0
+                    }
+                }
+                Some(ix) => {
+                    if ix > 0 {
+                        (ix - 1).min(items_count - 1)
+                    } else {
+                        items_count - 1
+                    }
+                }
+            };
+
+            if self.delegate.can_select(next_ix, cx) {
+                return Some(next_ix);
+            }
+            ix = Some(next_ix);
+        }
+
+        None
+    }
+
     fn on_action_select_prev(
         &mut self,
         _: &SelectPrev,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let items_count = self.delegate.items_count(cx);
-        if items_count == 0 {
+        if let Some(ix) = self.next_selectable_index(self.selected_index, false, cx) {
+            self.select_item(ix, window, cx);
+        }
+    }
+
+    fn on_action_select_next(
+        &mut self,
+        _: &SelectNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.next_selectable_index(self.selected_index, true, cx) {
+            self.select_item(ix, window, cx);
+        }
+    }
+
+    fn on_action_select_to_prev(
+        &mut self,
+        _: &SelectToPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.multiple {
+            self.select_prev(window, cx);
             return;
         }
 
-        let mut selected_index = self.selected_index.unwrap_or(0);
-        if selected_index > 0 {
-            selected_index = selected_index - 1;
-        } else {
-            selected_index = items_count - 1;
+        // Stop at the first item, instead of wrapping around.
+        if let Some(ix) = self
+            .next_selectable_index(self.selected_index, false, cx)
+            .filter(|ix| self.selected_index.map_or(true, |selected| *ix < selected))
+        {
+            self.select_range_to(ix, window, cx);
         }
-        self.select_item(selected_index, window, cx);
     }
 
-    fn on_action_select_next(
+    fn on_action_select_to_next(
         &mut self,
-        _: &SelectNext,
+        _: &SelectToNext,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let items_count = self.delegate.items_count(cx);
-        if items_count == 0 {
+        if !self.multiple {
+            self.select_next(window, cx);
             return;
         }
 
-        let selected_index;
-        if let Some(ix) = self.selected_index {
-            if ix < items_count - 1 {
-                selected_index = ix + 1;
+        // Stop at the last item, instead of wrapping around.
+        if let Some(ix) = self
+            .next_selectable_index(self.selected_index, true, cx)
+            .filter(|ix| self.selected_index.map_or(true, |selected| *ix > selected))
+        {
+            self.select_range_to(ix, window, cx);
+        }
+    }
+
+    fn on_action_select_all(&mut self, _: &SelectAll, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.multiple {
+            cx.propagate();
+            return;
+        }
+
+        self.select_all(window, cx);
+    }
+
+    /// Update the drop position and the auto scrolling by the position of the dragging item.
+    fn on_item_drag_move(
+        &mut self,
+        event: &DragMoveEvent<DragListItem>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if event.drag(cx).entity_id != cx.entity_id() {
+            return;
+        }
+
+        let bounds = event.bounds;
+        let position = event.event.position;
+        if !bounds.contains(&position) {
+            self.drop_ix = None;
+            self.auto_scroll_delta = px(0.);
+            cx.notify();
+            return;
+        }
+
+        let rows_count = self.rows_count(cx);
+        let state = self.vertical_scroll_handle.0.borrow();
+        let Some(size) = state.last_item_size.filter(|_| rows_count > 0) else {
+            return;
+        };
+        let row_height = size.contents.height / rows_count as f32;
+        let y = position.y - bounds.top() - state.base_handle.offset().y;
+        drop(state);
+
+        // Insert before the row when the mouse is at the upper half of the row, otherwise after it.
+        let row = (y / row_height).max(0.);
+        let row_ix = (row as usize).min(rows_count - 1);
+        let after = row.fract() >= 0.5;
+        let drop_ix = match self.row(row_ix, cx) {
+            ListRow::Header(section) => self.section_start(section, cx),
+            ListRow::Item(ix) if after => ix + 1,
+            ListRow::Item(ix) => ix,
+        };
+        if self.drop_ix != Some(drop_ix) {
+            self.drop_ix = Some(drop_ix);
+            cx.notify();
+        }
+
+        let to_top = position.y - bounds.top();
+        let to_bottom = bounds.bottom() - position.y;
+        self.auto_scroll_delta = if to_top < AUTO_SCROLL_EDGE {
+            AUTO_SCROLL_SPEED * ((AUTO_SCROLL_EDGE - to_top) / AUTO_SCROLL_EDGE)
+        } else if to_bottom < AUTO_SCROLL_EDGE {
+            -AUTO_SCROLL_SPEED * ((AUTO_SCROLL_EDGE - to_bottom) / AUTO_SCROLL_EDGE)
+        } else {
+            px(0.)
+        };
+        if self.auto_scroll_delta != px(0.) && !self.auto_scrolling {
+            self.auto_scrolling = true;
+            self._auto_scroll_task = cx.spawn_in(window, |this, mut cx| async move {
+                loop {
+                    Timer::after(Duration::from_millis(16)).await;
+                    let scrolling = this
+                        .update_in(&mut cx, |this, _, cx| this.auto_scroll(cx))
+                        .unwrap_or(false);
+                    if !scrolling {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    /// Scroll by the `auto_scroll_delta` while dragging an item, returns false to stop.
+    fn auto_scroll(&mut self, cx: &mut Context<Self>) -> bool {
+        if !cx.has_active_drag() || self.auto_scroll_delta == px(0.) {
+            self.auto_scroll_delta = px(0.);
+            self.auto_scrolling = false;
+            return false;
+        }
+
+        let state = self.vertical_scroll_handle.0.borrow();
+        if let Some(size) = state.last_item_size {
+            let viewport_height = state.base_handle.bounds().size.height;
+            let max_offset = (size.contents.height - viewport_height).max(px(0.));
+            let mut offset = state.base_handle.offset();
+            offset.y = (offset.y + self.auto_scroll_delta).clamp(-max_offset, px(0.));
+            state.base_handle.set_offset(offset);
+        }
+        drop(state);
+
+        cx.notify();
+        true
+    }
+
+    fn on_item_drop(&mut self, drag: &DragListItem, window: &mut Window, cx: &mut Context<Self>) {
+        self.auto_scroll_delta = px(0.);
+        let Some(drop_ix) = self.drop_ix.take() else {
+            return;
+        };
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        cx.stop_propagation();
+        let from = drag.ix;
+        let to = if drop_ix > from { drop_ix - 1 } else { drop_ix };
+        if to == from {
+            cx.notify();
+            return;
+        }
+
+        self.delegate.move_item(from, to, window, cx);
+
+        // Keep the selection on the same items.
+        let move_ix = |ix: usize| {
+            if ix == from {
+                to
+            } else if from < ix && ix <= to {
+                ix - 1
+            } else if to <= ix && ix < from {
+                ix + 1
             } else {
-                // When the last item is selected, select the first item.
-                selected_index = 0;
+                ix
+            }
+        };
+        self.selected_index = self.selected_index.map(move_ix);
+        self.anchor_index = self.anchor_index.map(move_ix);
+        self.right_clicked_index = None;
+        if !self.selected_indices.is_empty() {
+            let indices = self.selected_indices.iter().copied().map(move_ix);
+            self.selected_indices = indices.collect();
+            self.selected_indices.sort_unstable();
+            self.delegate
+                .set_selected_indices(&self.selected_indices, window, cx);
+        }
+        self.delegate
+            .set_selected_index(self.selected_index, window, cx);
+        cx.notify();
+    }
+
+    fn render_drag_handle(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.vertical_scroll_handle.0.borrow();
+        let rows_count = self.rows_count(cx).max(1);
+        let size = gpui::Size {
+            width: state.base_handle.bounds().size.width,
+            height: state
+                .last_item_size
+                .map_or(px(32.), |size| size.contents.height / rows_count as f32),
+        };
+
+        div()
+            .id("drag-handle")
+            .flex_none()
+            .px_1()
+            .cursor_grab()
+            .child(
+                Icon::new(IconName::GripVertical)
+                    .xsmall()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            // Avoid to select or confirm the item.
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .on_drag(
+                DragListItem {
+                    entity_id: cx.entity_id(),
+                    ix,
+                    size,
+                },
+                |drag, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| drag.clone())
+                },
+            )
+    }
+
+    /// Returns the section of the item at the `ix`.
+    fn section_of_item(&self, ix: usize, cx: &App) -> usize {
+        let mut item_start = 0;
+        for section in 0..self.delegate.sections_count(cx) {
+            item_start += self.delegate.section_items_count(section, cx);
+            if ix < item_start {
+                return section;
             }
-        } else {
-            // When no selected index, select the first item.
-            selected_index = 0;
         }
 
-        self.select_item(selected_index, window, cx);
+        0
+    }
+
+    fn render_row(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        match self.row(row_ix, cx) {
+            ListRow::Header(section) => div()
+                .id(("section-header", section))
+                .w_full()
+                .children(self.delegate.render_section_header(section, window, cx))
+                .into_any_element(),
+            ListRow::Item(ix) => self.render_list_item(ix, window, cx).into_any_element(),
+        }
     }
 
     fn render_list_item(
@@ -461,15 +1230,60 @@ where
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let selected = self.selected_index == Some(ix);
+        let selected = self.selected_index == Some(ix)
+            || (self.multiple && self.selected_indices.binary_search(&ix).is_ok());
         let right_clicked = self.right_clicked_index == Some(ix);
+        let selectable = self.selectable && self.delegate.can_select(ix, cx);
+        let items_count = self.delegate.items_count(cx);
+        // The drop indicator before the item, or after the last item.
+        let drop_indicator = self
+            .drop_ix
+            .filter(|_| cx.has_active_drag())
+            .and_then(|drop_ix| {
+                if drop_ix == ix {
+                    Some(true)
+                } else if drop_ix == items_count && ix + 1 == items_count {
+                    Some(false)
+                } else {
+                    None
+                }
+            });
 
         div()
             .id("list-item")
             .w_full()
             .relative()
-            .children(self.delegate.render_item(ix, window, cx))
-            .when(self.selectable, |this| {
+            .map(|this| {
+                let item = self.delegate.render_item(ix, window, cx);
+                if !self.reorderable {
+                    return this.children(item);
+                }
+
+                this.child(
+                    h_flex()
+                        .w_full()
+                        .child(self.render_drag_handle(ix, cx))
+                        .child(div().flex_1().overflow_hidden().children(item)),
+                )
+            })
+            .when_some(drop_indicator, |this, before| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left_0()
+                        .right_0()
+                        .h(px(2.))
+                        .map(|this| {
+                            if before {
+                                this.top_0()
+                            } else {
+                                this.bottom_0()
+                            }
+                        })
+                        .bg(cx.theme().drag_border),
+                )
+            })
+            .when(selectable, |this| {
                 this.when(selected || right_clicked, |this| {
                     this.child(
                         div()
@@ -485,8 +1299,22 @@ where
                 })
                 .on_mouse_down(
                     MouseButton::Left,
-                    cx.listener(move |this, _, window, cx| {
+                    cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
                         this.right_clicked_index = None;
+                        if this.multiple {
+                            if ev.modifiers.shift {
+                                this.select_range_to(ix, window, cx);
+                                return;
+                            }
+                            if ev.modifiers.secondary() {
+                                this.toggle_selected_item(ix, window, cx);
+                                return;
+                            }
+
+                            this.anchor_index = Some(ix);
+                            this.set_selected_indices(vec![ix], window, cx);
+                        }
+
                         this.selected_index = Some(ix);
                         this.on_action_confirm(&Confirm, window, cx);
                     }),
@@ -540,6 +1368,28 @@ where
             None
         };
 
+        let rows_count = self.rows_count(cx);
+        self.stick_to_bottom_if_need(rows_count);
+        let sticky_header = if rows_count > 0 && self.is_sectioned(cx) {
+            let row_ix = self.first_visible_index.min(rows_count - 1);
+            match self.row(row_ix, cx) {
+                ListRow::Header(section) => {
+                    self.delegate.render_section_header(section, window, cx)
+                }
+                ListRow::Item(ix) => {
+                    let section = self.section_of_item(ix, cx);
+                    self.delegate.render_section_header(section, window, cx)
+                }
+            }
+        } else {
+            None
+        };
+
+        let query_input = self
+            .query_input
+            .clone()
+            .filter(|_| self.query_input_visible);
+
         v_flex()
             .key_context("List")
             .id("list")
@@ -547,7 +1397,7 @@ where
             .size_full()
             .relative()
             .overflow_hidden()
-            .when_some(self.query_input.clone(), |this, input| {
+            .when_some(query_input, |this, input| {
                 this.child(
                     div()
                         .map(|this| match self.size {
@@ -567,6 +1417,9 @@ where
                     .on_action(cx.listener(Self::on_action_confirm))
                     .on_action(cx.listener(Self::on_action_select_next))
                     .on_action(cx.listener(Self::on_action_select_prev))
+                    .on_action(cx.listener(Self::on_action_select_to_next))
+                    .on_action(cx.listener(Self::on_action_select_to_prev))
+                    .on_action(cx.listener(Self::on_action_select_all))
                     .map(|this| {
                         if let Some(view) = initial_view {
                             this.child(view)
@@ -577,23 +1430,36 @@ where
                                     .relative()
                                     .when_some(self.max_height, |this, h| this.max_h(h))
                                     .overflow_hidden()
+                                    .when(self.reorderable, |this| {
+                                        this.on_drag_move(cx.listener(Self::on_item_drag_move))
+                                            .on_drop(cx.listener(Self::on_item_drop))
+                                    })
                                     .when(items_count == 0, |this| {
                                         this.child(self.delegate().render_empty(window, cx))
                                     })
                                     .when(items_count > 0, |this| {
                                         this.child(
-                                            uniform_list(view, "uniform-list", items_count, {
+                                            uniform_list(view, "uniform-list", rows_count, {
                                                 move |list, visible_range, window, cx| {
                                                     list.load_more_if_need(
-                                                        items_count,
+                                                        rows_count,
                                                         visible_range.end,
                                                         window,
                                                         cx,
                                                     );
+                                                    list.load_more_before_if_need(
+                                                        visible_range.start,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                    list.update_first_visible_index(
+                                                        visible_range.start,
+                                                        cx,
+                                                    );
 
                                                     visible_range
-                                                        .map(|ix| {
-                                                            list.render_list_item(ix, window, cx)
+                                                        .map(|row_ix| {
+                                                            list.render_row(row_ix, window, cx)
                                                         })
                                                         .collect::<Vec<_>>()
                                                 }
@@ -604,6 +1470,16 @@ where
                                             .into_any_element(),
                                         )
                                     })
+                                    .when_some(sticky_header, |this, header| {
+                                        this.child(
+                                            div()
+                                                .absolute()
+                                                .top_0()
+                                                .left_0()
+                                                .right_0()
+                                                .child(header),
+                                        )
+                                    })
                                     .children(self.render_scrollbar(window, cx)),
                             )
                         }
@@ -1,4 +1,5 @@
 use crate::{
+    command_palette::{CommandPalette, ToggleCommandPalette},
     drawer::Drawer,
     modal::Modal,
     notification::{Notification, NotificationList},
@@ -390,6 +391,9 @@ impl Render for Root {
                 .font_family(".SystemUIFont")
                 .bg(cx.theme().background)
                 .text_color(cx.theme().foreground)
+                .on_action(|_: &ToggleCommandPalette, window, cx| {
+                    CommandPalette::open(window, cx);
+                })
                 .child(self.view.clone()),
         )
     }
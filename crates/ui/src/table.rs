@@ -1,30 +1,48 @@
 use std::{cell::Cell, ops::Range, rc::Rc, time::Duration};
 
 use crate::{
+    button::{Button, ButtonVariants as _},
     context_menu::ContextMenuExt,
     h_flex,
+    indicator::Indicator,
+    input::{InputEvent, TextInput},
+    popover::Popover,
     popup_menu::PopupMenu,
     scroll::{ScrollableMask, Scrollbar, ScrollbarState},
     v_flex, ActiveTheme, Icon, IconName, Sizable, Size, StyleSized as _,
 };
 use gpui::{
     actions, canvas, div, prelude::FluentBuilder, px, uniform_list, App, AppContext, Axis, Bounds,
-    Context, Div, DragMoveEvent, Edges, Empty, EntityId, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, KeyBinding, ListSizingBehavior, MouseButton, MouseDownEvent,
-    ParentElement, Pixels, Point, Render, ScrollHandle, ScrollStrategy, SharedString, Stateful,
-    StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window,
+    ClipboardItem, Context, Corner, Div, DragMoveEvent, Edges, Empty, Entity, EntityId,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
+    ListSizingBehavior, MouseButton, MouseDownEvent, MouseMoveEvent, ParentElement, Pixels, Point,
+    Render, ScrollHandle, ScrollStrategy, SharedString, Stateful, StatefulInteractiveElement as _,
+    Styled, Subscription, Task, UniformListScrollHandle, Window,
 };
 
+mod filter;
 mod loading;
 
+pub use filter::ColFilter;
+use filter::ColFilterPopover;
+
 actions!(
     table,
     [
         Cancel,
         SelectPrev,
         SelectNext,
+        SelectToPrev,
+        SelectToNext,
+        SelectAll,
         SelectPrevColumn,
-        SelectNextColumn
+        SelectNextColumn,
+        SelectToPrevColumn,
+        SelectToNextColumn,
+        Copy,
+        StartCellEdit,
+        EditNextCell,
+        EditPrevCell
     ]
 );
 
@@ -34,8 +52,24 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("escape", Cancel, context),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("shift-up", SelectToPrev, context),
+        KeyBinding::new("shift-down", SelectToNext, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", SelectAll, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", SelectAll, context),
         KeyBinding::new("left", SelectPrevColumn, context),
         KeyBinding::new("right", SelectNextColumn, context),
+        KeyBinding::new("shift-left", SelectToPrevColumn, context),
+        KeyBinding::new("shift-right", SelectToNextColumn, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", Copy, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", Copy, context),
+        KeyBinding::new("enter", StartCellEdit, context),
+        KeyBinding::new("f2", StartCellEdit, context),
+        KeyBinding::new("tab", EditNextCell, context),
+        KeyBinding::new("shift-tab", EditPrevCell, context),
     ]);
 }
 
@@ -44,13 +78,14 @@ pub enum ColFixed {
     Left,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(crate) struct ColGroup {
     pub(crate) width: Pixels,
     pub(crate) bounds: Bounds<Pixels>,
     pub(crate) sort: Option<ColSort>,
     pub(crate) fixed: Option<ColFixed>,
     pub(crate) padding: Option<Edges<Pixels>>,
+    pub(crate) filter: Option<ColFilter>,
 }
 
 #[derive(Clone)]
@@ -101,17 +136,36 @@ impl Render for ResizeCol {
 enum SelectionState {
     Column,
     Row,
+    Cell,
 }
 
 #[derive(Clone)]
 pub enum TableEvent {
     /// Single click or move to selected row.
     SelectRow(usize),
+    /// The selected rows are changed in the multiple selection mode, sorted.
+    SelectRows(Vec<usize>),
     /// Double click on the row.
     DoubleClickedRow(usize),
     SelectCol(usize),
+    /// The selected cells are changed in the cell selection mode, the ranges of the rows and columns.
+    SelectCells(Range<usize>, Range<usize>),
     ColWidthsChanged(Vec<Pixels>),
     MoveCol(usize, usize),
+    /// The tree row is expanded.
+    ExpandRow(usize),
+    /// The tree row is collapsed.
+    CollapseRow(usize),
+}
+
+/// The editor of the cell that is being edited.
+struct CellEditor {
+    /// The cell `(row_ix, col_ix)` that is being edited.
+    cell: (usize, usize),
+    input: Entity<TextInput>,
+    /// The error message returned by [`TableDelegate::commit_cell_edit`].
+    error: Option<SharedString>,
+    _subscription: Subscription,
 }
 
 #[derive(Clone, Copy, Default)]
@@ -160,6 +214,22 @@ pub struct Table<D: TableDelegate> {
 
     scrollbar_visible: Edges<bool>,
     selected_row: Option<usize>,
+    /// Enable the multiple rows selection.
+    multiple: bool,
+    /// The selected rows in the multiple selection mode, sorted.
+    selected_rows: Vec<usize>,
+    /// The start of the range selection in the multiple selection mode.
+    anchor_row: Option<usize>,
+    /// Enable the cell range selection.
+    cell_selection: bool,
+    /// The active cell `(row_ix, col_ix)` in the cell selection mode.
+    selected_cell: Option<(usize, usize)>,
+    /// The start cell of the selected range in the cell selection mode.
+    anchor_cell: Option<(usize, usize)>,
+    /// Whether the mouse is dragging to select the cells.
+    selecting_cells: bool,
+    /// The editor of the cell that is being edited.
+    cell_editor: Option<CellEditor>,
     selection_state: SelectionState,
     right_clicked_row: Option<usize>,
     selected_col: Option<usize>,
@@ -173,6 +243,8 @@ pub struct Table<D: TableDelegate> {
     border: bool,
     /// The cell size of the table.
     size: Size,
+    /// The indentation width of each level of the tree rows.
+    indent: Pixels,
     /// The visible range of the rows and columns.
     visible_range: VisibleRangeState,
 
@@ -239,6 +311,27 @@ pub trait TableDelegate: Sized + 'static {
     ) {
     }
 
+    /// Return true to show the filter icon in the header of the column, default: false
+    fn can_filter_col(&self, col_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Return the values for the checklist filter of the column, default: None to use a text filter.
+    fn col_filter_values(&self, col_ix: usize, cx: &App) -> Option<Vec<SharedString>> {
+        None
+    }
+
+    /// Perform filter on the rows, the `filters` are the active filters `(col_ix, filter)` of the columns.
+    ///
+    /// This is called when the filter of any column is changed.
+    fn perform_filter(
+        &mut self,
+        filters: Vec<(usize, ColFilter)>,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) {
+    }
+
     /// Render the header cell at the given column index, default to the column name.
     fn render_th(
         &self,
@@ -249,10 +342,13 @@ pub trait TableDelegate: Sized + 'static {
         div().size_full().child(self.col_name(col_ix, cx))
     }
 
-    /// Render the row at the given row and column.
+    /// Render the row at the given row index.
+    ///
+    /// The `selected` is true if the row is the selected row or in the selected rows of the multiple selection mode.
     fn render_tr(
         &self,
         row_ix: usize,
+        selected: bool,
         window: &mut Window,
         cx: &mut Context<Table<Self>>,
     ) -> Stateful<Div> {
@@ -273,6 +369,44 @@ pub trait TableDelegate: Sized + 'static {
         cx: &mut Context<Table<Self>>,
     ) -> impl IntoElement;
 
+    /// Return true to allow editing the cell at the given row and column, default: false
+    ///
+    /// The editor is started by `enter`, `f2` or double click, and `tab`, `shift-tab` move to the next editable cell.
+    fn can_edit_cell(&self, row_ix: usize, col_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Render the editor of the cell that is being edited, default to render the `input`.
+    ///
+    /// The `input` is created with the [`TableDelegate::cell_text`] of the cell.
+    fn render_cell_editor(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        input: &Entity<TextInput>,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> impl IntoElement {
+        input.clone()
+    }
+
+    /// Commit the edited `value` of the cell, return an error message to reject the value and keep editing.
+    fn commit_cell_edit(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        value: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> Result<(), SharedString> {
+        Ok(())
+    }
+
+    /// Return the plain text of the cell at the given row and column, it is used to copy the selected cells.
+    fn cell_text(&self, row_ix: usize, col_ix: usize, cx: &App) -> SharedString {
+        SharedString::default()
+    }
+
     /// Return true to enable loop selection on the table.
     ///
     /// When the prev/next selection is out of the table bounds, the selection will loop to the other side.
@@ -349,6 +483,43 @@ pub trait TableDelegate: Sized + 'static {
     /// so you must check if there is more data to load or lock the loading state.
     fn load_more(&mut self, window: &mut Window, cx: &mut Context<Table<Self>>) {}
 
+    /// Return true to display the rows as a tree, the first column is indented by the
+    /// [`TableDelegate::row_depth`] with an expand/collapse toggle.
+    ///
+    /// Default: false
+    fn is_tree(&self, cx: &App) -> bool {
+        false
+    }
+
+    /// Returns the depth of the row in the tree, the root rows are 0.
+    fn row_depth(&self, row_ix: usize, cx: &App) -> usize {
+        0
+    }
+
+    /// Returns true if the row may have children, a toggle is displayed before the first cell.
+    fn row_expandable(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Returns true if the children of the row are displayed.
+    fn row_expanded(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Return true to show a loading indicator instead of the toggle, when the children are loading.
+    fn row_loading(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Expand the row, the children rows should be inserted after it.
+    ///
+    /// Like the `load_more`, the children can be loaded in a background task,
+    /// and report the loading state by [`TableDelegate::row_loading`].
+    fn expand_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Table<Self>>) {}
+
+    /// Collapse the row, the descendant rows should be removed.
+    fn collapse_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Table<Self>>) {}
+
     /// Render the last empty column, default to empty.
     fn render_last_empty_col(&mut self, window: &mut Window, cx: &mut Context<Table<Self>>) -> Div {
         h_flex().w_5().h_full().flex_shrink_0()
@@ -399,6 +570,14 @@ where
             horizontal_scrollbar_state: Rc::new(Cell::new(ScrollbarState::new())),
             selection_state: SelectionState::Row,
             selected_row: None,
+            multiple: false,
+            selected_rows: vec![],
+            anchor_row: None,
+            cell_selection: false,
+            selected_cell: None,
+            anchor_cell: None,
+            selecting_cells: false,
+            cell_editor: None,
             right_clicked_row: None,
             selected_col: None,
             resizing_col: None,
@@ -408,6 +587,7 @@ where
             stripe: false,
             border: true,
             size: Size::default(),
+            indent: px(16.),
             scrollbar_visible: Edges::all(true),
             visible_range: VisibleRangeState::default(),
             _load_more_task: Task::ready(()),
@@ -437,6 +617,12 @@ where
         cx.notify();
     }
 
+    /// Set the indentation width of each level of the tree rows, default is 16px.
+    pub fn indent(mut self, indent: impl Into<Pixels>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
     /// Set to use border style of the table, default to true.
     pub fn border(mut self, border: bool) -> Self {
         self.border = border;
@@ -454,6 +640,43 @@ where
         self.size
     }
 
+    /// Enable the multiple rows selection mode.
+    ///
+    /// - Ctrl (Cmd on macOS) click to toggle a row.
+    /// - Shift click or `shift-up`, `shift-down` to select a range of rows.
+    /// - `ctrl-a` (`cmd-a` on macOS) to select all rows.
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Set the multiple rows selection mode, the selected rows are cleared when disabled, see [`Table::multiple`].
+    pub fn set_multiple(&mut self, multiple: bool, cx: &mut Context<Self>) {
+        self.multiple = multiple;
+        if !multiple {
+            self.anchor_row = None;
+            self.set_selected_rows(vec![], cx);
+        }
+        cx.notify();
+    }
+
+    /// Enable the cell range selection mode, the rows are not selectable in this mode.
+    ///
+    /// - Click and drag, or shift click to select a range of cells.
+    /// - Arrow keys to move the active cell, `shift` + arrow keys to extend the range.
+    /// - `ctrl-c` (`cmd-c` on macOS) to copy the selected cells as tab-separated text,
+    ///   see [`TableDelegate::cell_text`].
+    pub fn cell_selection(mut self) -> Self {
+        self.cell_selection = true;
+        self
+    }
+
+    /// Set the cell range selection mode, see [`Table::cell_selection`].
+    pub fn set_cell_selection(&mut self, cell_selection: bool, cx: &mut Context<Self>) {
+        self.cell_selection = cell_selection;
+        self.clear_selection(cx);
+    }
+
     /// Set scrollbar visibility.
     pub fn scrollbar_visible(mut self, vertical: bool, horizontal: bool) -> Self {
         self.scrollbar_visible = Edges {
@@ -470,8 +693,15 @@ where
     }
 
     fn prepare_col_groups(&mut self, cx: &mut Context<Self>) {
+        // Keep the filters, they are not provided by the delegate.
+        let mut filters = self
+            .col_groups
+            .iter_mut()
+            .map(|col| col.filter.take())
+            .collect::<Vec<_>>();
         self.col_groups = (0..self.delegate.cols_count(cx))
             .map(|col_ix| ColGroup {
+                filter: filters.get_mut(col_ix).and_then(|filter| filter.take()),
                 width: self.delegate.col_width(col_ix, cx),
                 padding: self.delegate.col_padding(col_ix, cx),
                 bounds: Bounds::default(),
@@ -507,6 +737,8 @@ where
     }
 
     /// Sets the selected row to the given index.
+    ///
+    /// In the multiple selection mode, the selected rows are reset to the row.
     pub fn set_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
         self.selection_state = SelectionState::Row;
         self.right_clicked_row = None;
@@ -515,6 +747,83 @@ where
             self.vertical_scroll_handle
                 .scroll_to_item(row_ix, ScrollStrategy::Top);
         }
+        if self.multiple {
+            self.anchor_row = Some(row_ix);
+            self.set_selected_rows(vec![row_ix], cx);
+        }
+        cx.emit(TableEvent::SelectRow(row_ix));
+        cx.notify();
+    }
+
+    /// Returns the selected rows in the multiple selection mode, sorted.
+    pub fn selected_rows(&self) -> &[usize] {
+        &self.selected_rows
+    }
+
+    /// Returns true if the row is the selected row or in the selected rows.
+    pub fn is_row_selected(&self, row_ix: usize) -> bool {
+        self.selected_row == Some(row_ix)
+            || (self.multiple && self.selected_rows.binary_search(&row_ix).is_ok())
+    }
+
+    /// Set the selected rows in the multiple selection mode, the rows out of range are ignored.
+    pub fn set_selected_rows(&mut self, rows: impl Into<Vec<usize>>, cx: &mut Context<Self>) {
+        let rows_count = self.delegate.rows_count(cx);
+        let mut rows = rows.into();
+        rows.retain(|row_ix| *row_ix < rows_count);
+        rows.sort_unstable();
+        rows.dedup();
+        if rows == self.selected_rows {
+            return;
+        }
+
+        self.selection_state = SelectionState::Row;
+        self.selected_rows = rows;
+        cx.emit(TableEvent::SelectRows(self.selected_rows.clone()));
+        cx.notify();
+    }
+
+    /// Select all the rows in the multiple selection mode.
+    pub fn select_all(&mut self, cx: &mut Context<Self>) {
+        if !self.multiple {
+            return;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        self.set_selected_rows((0..rows_count).collect::<Vec<_>>(), cx);
+    }
+
+    /// Move the selected row to `row_ix` and select the range from the anchor to it.
+    fn select_rows_to(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let anchor = *self
+            .anchor_row
+            .get_or_insert(self.selected_row.unwrap_or(row_ix));
+        self.right_clicked_row = None;
+        self.selected_row = Some(row_ix);
+        self.vertical_scroll_handle
+            .scroll_to_item(row_ix, ScrollStrategy::Top);
+        self.set_selected_rows(
+            (anchor.min(row_ix)..=anchor.max(row_ix)).collect::<Vec<_>>(),
+            cx,
+        );
+        cx.emit(TableEvent::SelectRow(row_ix));
+        cx.notify();
+    }
+
+    /// Toggle the row at `row_ix` in the selected rows.
+    fn toggle_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let mut rows = self.selected_rows.clone();
+        match rows.binary_search(&row_ix) {
+            Ok(pos) => {
+                rows.remove(pos);
+            }
+            Err(pos) => rows.insert(pos, row_ix),
+        }
+
+        self.anchor_row = Some(row_ix);
+        self.right_clicked_row = None;
+        self.selected_row = Some(row_ix);
+        self.set_selected_rows(rows, cx);
         cx.emit(TableEvent::SelectRow(row_ix));
         cx.notify();
     }
@@ -538,11 +847,84 @@ where
         cx.notify();
     }
 
+    /// Returns the ranges of the rows and columns of the selected cells in the cell selection mode.
+    pub fn selected_cells(&self) -> Option<(Range<usize>, Range<usize>)> {
+        if !self.cell_selection || self.selection_state != SelectionState::Cell {
+            return None;
+        }
+
+        let (row_ix, col_ix) = self.selected_cell?;
+        let (anchor_row_ix, anchor_col_ix) = self.anchor_cell.unwrap_or((row_ix, col_ix));
+        Some((
+            row_ix.min(anchor_row_ix)..row_ix.max(anchor_row_ix) + 1,
+            col_ix.min(anchor_col_ix)..col_ix.max(anchor_col_ix) + 1,
+        ))
+    }
+
+    /// Select the cells from the `anchor` to the `cell` in the cell selection mode, the `cell` is the active cell.
+    pub fn set_selected_cells(
+        &mut self,
+        anchor: (usize, usize),
+        cell: (usize, usize),
+        cx: &mut Context<Self>,
+    ) {
+        if !self.cell_selection {
+            return;
+        }
+
+        self.anchor_cell = Some(anchor);
+        self.select_cell(cell, true, cx);
+    }
+
+    /// Select the cell, extend the range from the anchor cell if `extend` is true.
+    fn select_cell(&mut self, cell: (usize, usize), extend: bool, cx: &mut Context<Self>) {
+        if !extend || self.anchor_cell.is_none() {
+            self.anchor_cell = Some(cell);
+        }
+        self.selection_state = SelectionState::Cell;
+        self.right_clicked_row = None;
+        self.selected_cell = Some(cell);
+        self.vertical_scroll_handle
+            .scroll_to_item(cell.0, ScrollStrategy::Top);
+        if let Some((rows, cols)) = self.selected_cells() {
+            cx.emit(TableEvent::SelectCells(rows, cols));
+        }
+        cx.notify();
+    }
+
+    /// Move the active cell by the given delta, it stops at the edges of the table.
+    fn move_selected_cell(
+        &mut self,
+        row_delta: isize,
+        col_delta: isize,
+        extend: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let rows_count = self.delegate.rows_count(cx);
+        let cols_count = self.delegate.cols_count(cx);
+        if rows_count == 0 || cols_count == 0 {
+            return;
+        }
+
+        let cell = match self.selected_cell {
+            Some((row_ix, col_ix)) => (
+                row_ix.saturating_add_signed(row_delta).min(rows_count - 1),
+                col_ix.saturating_add_signed(col_delta).min(cols_count - 1),
+            ),
+            None => (0, 0),
+        };
+        self.select_cell(cell, extend, cx);
+    }
+
     /// Clear the selection of the table.
     pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
         self.selection_state = SelectionState::Row;
         self.selected_row = None;
         self.selected_col = None;
+        self.selected_cell = None;
+        self.anchor_cell = None;
+        self.anchor_row = None;
+        self.set_selected_rows(vec![], cx);
         cx.notify();
     }
 
@@ -561,7 +943,15 @@ where
         if ev.button == MouseButton::Right {
             self.right_clicked_row = Some(row_ix);
         } else {
-            self.set_selected_row(row_ix, cx);
+            if self.cell_selection {
+                // The cells are selected by the mouse down on the cell.
+            } else if self.multiple && ev.modifiers.shift {
+                self.select_rows_to(row_ix, cx);
+            } else if self.multiple && ev.modifiers.secondary() {
+                self.toggle_selected_row(row_ix, cx);
+            } else {
+                self.set_selected_row(row_ix, cx);
+            }
 
             if ev.click_count == 2 {
                 cx.emit(TableEvent::DoubleClickedRow(row_ix));
@@ -577,13 +967,301 @@ where
         self.set_selected_col(col_ix, cx)
     }
 
-    fn action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+    /// Expand the tree row at the given index, see [`TableDelegate::expand_row`].
+    pub fn expand_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.delegate.row_expandable(row_ix, cx) || self.delegate.row_expanded(row_ix, cx) {
+            return;
+        }
+
+        self.keep_selection_to_row(row_ix, window, cx);
+        self.delegate.expand_row(row_ix, window, cx);
+        cx.emit(TableEvent::ExpandRow(row_ix));
+        cx.notify();
+    }
+
+    /// Collapse the tree row at the given index, see [`TableDelegate::collapse_row`].
+    pub fn collapse_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.delegate.row_expanded(row_ix, cx) {
+            return;
+        }
+
+        self.keep_selection_to_row(row_ix, window, cx);
+        self.delegate.collapse_row(row_ix, window, cx);
+        cx.emit(TableEvent::CollapseRow(row_ix));
+        cx.notify();
+    }
+
+    fn toggle_row_expanded(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.delegate.row_expanded(row_ix, cx) {
+            self.collapse_row(row_ix, window, cx);
+        } else {
+            self.expand_row(row_ix, window, cx);
+        }
+    }
+
+    /// The rows after `row_ix` are going to be inserted or removed,
+    /// move the selection after it to the row, and cancel the editing after it.
+    fn keep_selection_to_row(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .cell_editor
+            .as_ref()
+            .is_some_and(|editor| editor.cell.0 > row_ix)
+        {
+            self.cancel_cell_edit(window, cx);
+        }
+
+        match self.selection_state {
+            SelectionState::Row => {
+                if self.selected_row.is_some_and(|ix| ix > row_ix)
+                    || self.selected_rows.last().is_some_and(|ix| *ix > row_ix)
+                {
+                    self.set_selected_row(row_ix, cx);
+                }
+            }
+            SelectionState::Cell => {
+                if let Some((rows, cols)) = self.selected_cells() {
+                    if rows.end > row_ix + 1 {
+                        self.select_cell((row_ix, cols.start), false, cx);
+                    }
+                }
+            }
+            SelectionState::Column => {}
+        }
+    }
+
+    /// Returns the index of the parent row of the tree row.
+    fn parent_row(&self, row_ix: usize, cx: &App) -> Option<usize> {
+        let depth = self.delegate.row_depth(row_ix, cx);
+        (0..row_ix)
+            .rev()
+            .find(|ix| self.delegate.row_depth(*ix, cx) < depth)
+    }
+
+    /// Returns the selected row to expand or collapse by the keyboard.
+    fn selected_tree_row(&self) -> Option<usize> {
+        self.selected_row
+            .filter(|_| self.selection_state == SelectionState::Row)
+    }
+
+    /// Returns the cell `(row_ix, col_ix)` that is being edited.
+    pub fn editing_cell(&self) -> Option<(usize, usize)> {
+        self.cell_editor.as_ref().map(|editor| editor.cell)
+    }
+
+    /// Start editing the cell at the given row and column, if the cell can be edited.
+    pub fn start_cell_edit(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.delegate.can_edit_cell(row_ix, col_ix, cx) {
+            return;
+        }
+
+        if let Some(editor) = self.cell_editor.as_ref() {
+            if editor.cell == (row_ix, col_ix) {
+                editor.input.focus_handle(cx).focus(window);
+                return;
+            }
+        }
+
+        if self.cell_selection {
+            self.select_cell((row_ix, col_ix), false, cx);
+        } else if self.selected_row != Some(row_ix) {
+            self.set_selected_row(row_ix, cx);
+        }
+
+        let text = self.delegate.cell_text(row_ix, col_ix, cx);
+        let input = cx.new(|cx| {
+            let mut input = TextInput::new(window, cx)
+                .appearance(false)
+                .with_size(self.size);
+            input.set_text(text, window, cx);
+            input
+        });
+        let _subscription = cx.subscribe_in(
+            &input,
+            window,
+            |this, _, event: &InputEvent, window, cx| match event {
+                InputEvent::PressEnter => {
+                    this.commit_cell_edit(window, cx);
+                }
+                // Commit the value when clicking outside of the editor.
+                InputEvent::Blur => {
+                    this.commit_cell_edit(window, cx);
+                }
+                _ => {}
+            },
+        );
+        input.focus_handle(cx).focus(window);
+        self.cell_editor = Some(CellEditor {
+            cell: (row_ix, col_ix),
+            input,
+            error: None,
+            _subscription,
+        });
+        cx.notify();
+    }
+
+    /// Commit the editing cell by [`TableDelegate::commit_cell_edit`], returns false if the value is rejected.
+    pub fn commit_cell_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(editor) = self.cell_editor.as_mut() else {
+            return true;
+        };
+
+        let (row_ix, col_ix) = editor.cell;
+        let value = editor.input.read(cx).text();
+        let focused = editor.input.focus_handle(cx).is_focused(window);
+        match self
+            .delegate
+            .commit_cell_edit(row_ix, col_ix, value, window, cx)
+        {
+            Ok(()) => {
+                self.cell_editor = None;
+                if focused {
+                    self.focus_handle.focus(window);
+                }
+                cx.notify();
+                true
+            }
+            Err(error) => {
+                editor.error = Some(error);
+                cx.notify();
+                false
+            }
+        }
+    }
+
+    /// Cancel editing the cell, the value is discarded.
+    pub fn cancel_cell_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_editor.take().is_some() {
+            self.focus_handle.focus(window);
+            cx.notify();
+        }
+    }
+
+    /// Returns the current cell to edit, the active cell in the cell selection mode,
+    /// otherwise the first editable cell of the selected row.
+    fn current_cell(&self, cx: &App) -> Option<(usize, usize)> {
+        if self.cell_selection {
+            return self.selected_cell;
+        }
+
+        let row_ix = self.selected_row?;
+        if let Some(col_ix) = self
+            .selected_col
+            .filter(|col_ix| self.delegate.can_edit_cell(row_ix, *col_ix, cx))
+        {
+            return Some((row_ix, col_ix));
+        }
+
+        (0..self.delegate.cols_count(cx))
+            .find(|col_ix| self.delegate.can_edit_cell(row_ix, *col_ix, cx))
+            .map(|col_ix| (row_ix, col_ix))
+    }
+
+    /// Returns the next (or previous if `forward` is false) editable cell after the `cell`, in the row-major order.
+    fn next_editable_cell(
+        &self,
+        (row_ix, col_ix): (usize, usize),
+        forward: bool,
+        cx: &App,
+    ) -> Option<(usize, usize)> {
+        let rows_count = self.delegate.rows_count(cx);
+        let cols_count = self.delegate.cols_count(cx);
+        let ix = row_ix * cols_count + col_ix;
+        let is_editable = |ix: &usize| {
+            self.delegate
+                .can_edit_cell(ix / cols_count, ix % cols_count, cx)
+        };
+
+        if forward {
+            (ix + 1..rows_count * cols_count).find(is_editable)
+        } else {
+            (0..ix).rev().find(is_editable)
+        }
+        .map(|ix| (ix / cols_count, ix % cols_count))
+    }
+
+    /// Commit the editing cell and move to edit the next editable cell.
+    fn edit_next_cell(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(cell) = self.editing_cell() else {
+            cx.propagate();
+            return;
+        };
+
+        if !self.commit_cell_edit(window, cx) {
+            return;
+        }
+
+        if let Some((row_ix, col_ix)) = self.next_editable_cell(cell, forward, cx) {
+            self.start_cell_edit(row_ix, col_ix, window, cx);
+        }
+    }
+
+    fn action_start_cell_edit(
+        &mut self,
+        _: &StartCellEdit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((row_ix, col_ix)) = self
+            .current_cell(cx)
+            .filter(|(row_ix, col_ix)| self.delegate.can_edit_cell(*row_ix, *col_ix, cx))
+        else {
+            cx.propagate();
+            return;
+        };
+
+        self.start_cell_edit(row_ix, col_ix, window, cx);
+    }
+
+    fn action_edit_next_cell(
+        &mut self,
+        _: &EditNextCell,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.edit_next_cell(true, window, cx);
+    }
+
+    fn action_edit_prev_cell(
+        &mut self,
+        _: &EditPrevCell,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.edit_next_cell(false, window, cx);
+    }
+
+    fn action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_editor.is_some() {
+            self.cancel_cell_edit(window, cx);
+            return;
+        }
+
         self.clear_selection(cx);
     }
 
     fn action_select_prev(&mut self, _: &SelectPrev, _: &mut Window, cx: &mut Context<Self>) {
-        let mut selected_row = self.selected_row.unwrap_or(0);
+        if self.cell_selection {
+            self.move_selected_cell(-1, 0, false, cx);
+            return;
+        }
+
         let rows_count = self.delegate.rows_count(cx);
+        if rows_count == 0 {
+            return;
+        }
+
+        let mut selected_row = self.selected_row.unwrap_or(0);
         if selected_row > 0 {
             selected_row = selected_row - 1;
         } else {
@@ -596,8 +1274,18 @@ where
     }
 
     fn action_select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selection {
+            self.move_selected_cell(1, 0, false, cx);
+            return;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        if rows_count == 0 {
+            return;
+        }
+
         let mut selected_row = self.selected_row.unwrap_or(0);
-        if selected_row < self.delegate.rows_count(cx) - 1 {
+        if selected_row < rows_count - 1 {
             selected_row += 1;
         } else {
             if self.delegate.can_loop_select(cx) {
@@ -608,12 +1296,98 @@ where
         self.set_selected_row(selected_row, cx);
     }
 
+    fn action_select_to_prev(
+        &mut self,
+        _: &SelectToPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.cell_selection {
+            self.move_selected_cell(-1, 0, true, cx);
+            return;
+        }
+
+        if !self.multiple {
+            self.action_select_prev(&SelectPrev, window, cx);
+            return;
+        }
+
+        // Stop at the first row, instead of wrapping around.
+        match self.selected_row {
+            Some(row_ix) if row_ix > 0 => self.select_rows_to(row_ix - 1, cx),
+            Some(_) => {}
+            None if self.delegate.rows_count(cx) > 0 => self.select_rows_to(0, cx),
+            None => {}
+        }
+    }
+
+    fn action_select_to_next(
+        &mut self,
+        _: &SelectToNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.cell_selection {
+            self.move_selected_cell(1, 0, true, cx);
+            return;
+        }
+
+        if !self.multiple {
+            self.action_select_next(&SelectNext, window, cx);
+            return;
+        }
+
+        // Stop at the last row, instead of wrapping around.
+        let rows_count = self.delegate.rows_count(cx);
+        match self.selected_row {
+            Some(row_ix) if row_ix + 1 < rows_count => self.select_rows_to(row_ix + 1, cx),
+            Some(_) => {}
+            None if rows_count > 0 => self.select_rows_to(0, cx),
+            None => {}
+        }
+    }
+
+    fn action_select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selection {
+            let rows_count = self.delegate.rows_count(cx);
+            let cols_count = self.delegate.cols_count(cx);
+            if rows_count > 0 && cols_count > 0 {
+                self.set_selected_cells((0, 0), (rows_count - 1, cols_count - 1), cx);
+            }
+            return;
+        }
+
+        if !self.multiple {
+            cx.propagate();
+            return;
+        }
+
+        self.select_all(cx);
+    }
+
     fn action_select_prev_col(
         &mut self,
         _: &SelectPrevColumn,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.cell_selection {
+            self.move_selected_cell(0, -1, false, cx);
+            return;
+        }
+
+        // Collapse the selected tree row, or move to the parent if it is collapsed.
+        if self.delegate.is_tree(cx) {
+            if let Some(row_ix) = self.selected_tree_row() {
+                if self.delegate.row_expanded(row_ix, cx) {
+                    self.collapse_row(row_ix, window, cx);
+                } else if let Some(parent_ix) = self.parent_row(row_ix, cx) {
+                    self.set_selected_row(parent_ix, cx);
+                }
+            }
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
         let cols_count = self.delegate.cols_count(cx);
         if selected_col > 0 {
@@ -629,9 +1403,28 @@ where
     fn action_select_next_col(
         &mut self,
         _: &SelectNextColumn,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.cell_selection {
+            self.move_selected_cell(0, 1, false, cx);
+            return;
+        }
+
+        // Expand the selected tree row, or move to the first child if it is expanded.
+        if self.delegate.is_tree(cx) {
+            if let Some(row_ix) = self.selected_tree_row() {
+                if !self.delegate.row_expanded(row_ix, cx) {
+                    self.expand_row(row_ix, window, cx);
+                } else if row_ix + 1 < self.delegate.rows_count(cx)
+                    && self.delegate.row_depth(row_ix + 1, cx) > self.delegate.row_depth(row_ix, cx)
+                {
+                    self.set_selected_row(row_ix + 1, cx);
+                }
+            }
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
         if selected_col < self.delegate.cols_count(cx) - 1 {
             selected_col += 1;
@@ -644,6 +1437,58 @@ where
         self.set_selected_col(selected_col, cx);
     }
 
+    fn action_select_to_prev_col(
+        &mut self,
+        _: &SelectToPrevColumn,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.cell_selection {
+            self.action_select_prev_col(&SelectPrevColumn, window, cx);
+            return;
+        }
+
+        self.move_selected_cell(0, -1, true, cx);
+    }
+
+    fn action_select_to_next_col(
+        &mut self,
+        _: &SelectToNextColumn,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.cell_selection {
+            self.action_select_next_col(&SelectNextColumn, window, cx);
+            return;
+        }
+
+        self.move_selected_cell(0, 1, true, cx);
+    }
+
+    /// Copy the selected cells as tab-separated text, the rows are separated by newlines.
+    fn action_copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((rows, cols)) = self.selected_cells() else {
+            cx.propagate();
+            return;
+        };
+
+        let text = rows
+            .map(|row_ix| {
+                cols.clone()
+                    .map(|col_ix| {
+                        // The tabs and newlines in the cell would break the rows and columns.
+                        self.delegate
+                            .cell_text(row_ix, col_ix, cx)
+                            .replace(['\t', '\r', '\n'], " ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+    }
+
     /// Scroll table when mouse position is near the edge of the table bounds.
     fn scroll_table_by_col_resizing(
         &mut self,
@@ -724,6 +1569,45 @@ where
         cx.notify();
     }
 
+    /// Returns the filter of the column at the given index.
+    pub fn col_filter(&self, col_ix: usize) -> Option<&ColFilter> {
+        self.col_groups.get(col_ix)?.filter.as_ref()
+    }
+
+    /// Returns the active filters `(col_ix, filter)` of the columns.
+    pub fn col_filters(&self) -> Vec<(usize, ColFilter)> {
+        self.col_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(col_ix, col)| Some((col_ix, col.filter.clone()?)))
+            .collect()
+    }
+
+    /// Set the filter of the column at the given index, and perform filter by [`TableDelegate::perform_filter`].
+    pub fn set_col_filter(
+        &mut self,
+        col_ix: usize,
+        filter: Option<ColFilter>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+        if col_group.filter == filter {
+            return;
+        }
+
+        col_group.filter = filter;
+        let filters = self.col_filters();
+        // The rows are changed by the filter, so the selection and the editing cell are invalid.
+        // Don't move the focus from the filter to the table.
+        self.cell_editor = None;
+        self.clear_selection(cx);
+        self.delegate.perform_filter(filters, window, cx);
+        cx.notify();
+    }
+
     fn move_col(
         &mut self,
         col_ix: usize,
@@ -852,6 +1736,153 @@ where
         }
     }
 
+    /// Render the cell at the given row and column, with the cell selection style and events.
+    fn render_td_wrap(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let selected_cells = self
+            .selected_cells()
+            .filter(|(rows, cols)| rows.contains(&row_ix) && cols.contains(&col_ix));
+        let editor = self
+            .cell_editor
+            .as_ref()
+            .filter(|editor| editor.cell == (row_ix, col_ix))
+            .map(|editor| (editor.input.clone(), editor.error.is_some()));
+        let editable = self.delegate.can_edit_cell(row_ix, col_ix, cx);
+
+        let content = match editor.as_ref() {
+            Some((input, _)) => self
+                .delegate
+                .render_cell_editor(row_ix, col_ix, input, window, cx)
+                .into_any_element(),
+            None => self
+                .measure_render_td(row_ix, col_ix, window, cx)
+                .into_any_element(),
+        };
+        let cell = self.render_cell(col_ix, window, cx);
+        let cell = if col_ix == 0 && self.delegate.is_tree(cx) {
+            cell.child(
+                h_flex()
+                    .h_full()
+                    .pl(self.indent * self.delegate.row_depth(row_ix, cx) as f32)
+                    .child(self.render_tree_toggle(row_ix, cx))
+                    .child(div().flex_1().overflow_hidden().child(content)),
+            )
+        } else {
+            cell.child(content)
+        };
+
+        self.render_col_wrap(col_ix, window, cx)
+            .child(cell)
+            .when(editable, |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
+                        if ev.click_count == 2 {
+                            this.start_cell_edit(row_ix, col_ix, window, cx);
+                            // Keep the focus on the editor, instead of the table.
+                            window.prevent_default();
+                        }
+                    }),
+                )
+            })
+            .when(self.cell_selection, |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, ev: &MouseDownEvent, _, cx| {
+                        this.selecting_cells = true;
+                        this.select_cell((row_ix, col_ix), ev.modifiers.shift, cx);
+                    }),
+                )
+                .on_mouse_move(cx.listener(
+                    move |this, ev: &MouseMoveEvent, _, cx| {
+                        if this.selecting_cells
+                            && ev.pressed_button == Some(MouseButton::Left)
+                            && this.selected_cell != Some((row_ix, col_ix))
+                        {
+                            this.select_cell((row_ix, col_ix), true, cx);
+                        }
+                    },
+                ))
+            })
+            // Cell selected style, the border is drawn at the edges of the range.
+            .when_some(selected_cells, |this, (rows, cols)| {
+                this.relative().child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .bottom_0()
+                        .bg(cx.theme().table_active)
+                        .border_color(cx.theme().table_active_border)
+                        .when(row_ix == rows.start, |this| this.border_t_1())
+                        .when(row_ix + 1 == rows.end, |this| this.border_b_1())
+                        .when(col_ix == cols.start, |this| this.border_l_1())
+                        .when(col_ix + 1 == cols.end, |this| this.border_r_1()),
+                )
+            })
+            // Cell editing style
+            .when_some(editor, |this, (_, has_error)| {
+                this.relative().child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .bottom_0()
+                        .border_1()
+                        .border_color(if has_error {
+                            cx.theme().danger
+                        } else {
+                            cx.theme().ring
+                        }),
+                )
+            })
+    }
+
+    /// Render the expand/collapse toggle of the tree row, or a loading indicator when the children are loading.
+    fn render_tree_toggle(&self, row_ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let expandable = self.delegate.row_expandable(row_ix, cx);
+        let loading = self.delegate.row_loading(row_ix, cx);
+        let expanded = self.delegate.row_expanded(row_ix, cx);
+
+        div()
+            .id(("tree-toggle", row_ix))
+            .flex_none()
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(self.indent)
+            .when(loading, |this| {
+                this.child(Indicator::new().xsmall().color(cx.theme().muted_foreground))
+            })
+            .when(expandable && !loading, |this| {
+                this.child(
+                    Icon::new(if expanded {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronRight
+                    })
+                    .xsmall()
+                    .text_color(cx.theme().muted_foreground),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, window, cx| {
+                        // Toggle the row, instead of selecting the row or cell.
+                        cx.stop_propagation();
+                        this.focus_handle.focus(window);
+                        this.toggle_row_expanded(row_ix, window, cx);
+                    }),
+                )
+            })
+    }
+
     fn render_vertical_scrollbar(
         &self,
         _: &mut Window,
@@ -944,12 +1975,16 @@ where
                             let ix = *ix;
                             view.resizing_col = Some(ix);
 
-                            let col_group =
-                                *view.col_groups.get(ix).expect("BUG: invalid col index");
+                            let col_left = view
+                                .col_groups
+                                .get(ix)
+                                .expect("BUG: invalid col index")
+                                .bounds
+                                .left();
 
                             view.resize_cols(
                                 ix,
-                                e.event.position.x - HANDLE_SIZE - col_group.bounds.left(),
+                                e.event.position.x - HANDLE_SIZE - col_left,
                                 window,
                                 cx,
                             );
@@ -1026,6 +2061,40 @@ where
         )
     }
 
+    fn render_filter_icon(
+        &self,
+        col_ix: usize,
+        col_group: &ColGroup,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if !self.delegate.can_filter_col(col_ix, cx) {
+            return None;
+        }
+
+        let is_on = col_group.filter.is_some();
+        let table = cx.model().downgrade();
+
+        Some(
+            Popover::new(("table-filter", col_ix))
+                .anchor(Corner::TopRight)
+                .trigger(
+                    Button::new(("icon-filter", col_ix)).ghost().xsmall().icon(
+                        Icon::new(IconName::Filter)
+                            .size_3()
+                            .map(|this| match is_on {
+                                true => this.text_color(cx.theme().primary),
+                                false => this.text_color(cx.theme().muted_foreground),
+                            }),
+                    ),
+                )
+                .content(move |window, cx| {
+                    let table = table.clone();
+                    cx.new(|cx| ColFilterPopover::new(table, col_ix, window, cx))
+                }),
+        )
+    }
+
     /// Render the column header.
     /// The children must be one by one items.
     /// Because the horizontal scroll handle will use the child_item_bounds to
@@ -1064,7 +2133,16 @@ where
                                     self.size.table_cell_padding().right - paddings.right;
                                 this.pr(offset_pr.max(px(0.)))
                             })
-                            .children(self.render_sort_icon(col_ix, &col_group, window, cx)),
+                            .child(
+                                h_flex()
+                                    .gap_0p5()
+                                    .children(
+                                        self.render_filter_icon(col_ix, &col_group, window, cx),
+                                    )
+                                    .children(
+                                        self.render_sort_icon(col_ix, &col_group, window, cx),
+                                    ),
+                            ),
                     )
                     .when(moveable, |this| {
                         this.on_drag(
@@ -1217,12 +2295,12 @@ where
     ) -> impl IntoElement {
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
         let is_stripe_row = self.stripe && row_ix % 2 != 0;
-        let is_selected = self.selected_row == Some(row_ix);
+        let is_selected = self.is_row_selected(row_ix);
         let view = cx.model().clone();
 
         if row_ix < rows_count {
             self.delegate
-                .render_tr(row_ix, window, cx)
+                .render_tr(row_ix, is_selected, window, cx)
                 .w_full()
                 .h(self.size.table_row_height())
                 .border_b_1()
@@ -1248,11 +2326,7 @@ where
                                 let mut items = Vec::with_capacity(left_cols_count);
 
                                 (0..left_cols_count).for_each(|col_ix| {
-                                    items.push(self.render_col_wrap(col_ix, window, cx).child(
-                                        self.render_cell(col_ix, window, cx).child(
-                                            self.measure_render_td(row_ix, col_ix, window, cx),
-                                        ),
-                                    ));
+                                    items.push(self.render_td_wrap(row_ix, col_ix, window, cx));
                                 });
 
                                 items
@@ -1298,16 +2372,9 @@ where
 
                                         visible_range.for_each(|col_ix| {
                                             let col_ix = col_ix + left_cols_count;
-                                            let el =
-                                                table.render_col_wrap(col_ix, window, cx).child(
-                                                    table.render_cell(col_ix, window, cx).child(
-                                                        table.measure_render_td(
-                                                            row_ix, col_ix, window, cx,
-                                                        ),
-                                                    ),
-                                                );
-
-                                            items.push(el);
+                                            items.push(
+                                                table.render_td_wrap(row_ix, col_ix, window, cx),
+                                            );
                                         });
 
                                         items
@@ -1319,24 +2386,22 @@ where
                         .child(self.delegate.render_last_empty_col(window, cx)),
                 )
                 // Row selected style
-                .when_some(self.selected_row, |this, _| {
-                    this.when(
-                        is_selected && self.selection_state == SelectionState::Row,
-                        |this| {
-                            this.border_color(gpui::transparent_white()).child(
-                                div()
-                                    .top(if row_ix == 0 { px(0.) } else { px(-1.) })
-                                    .left(px(0.))
-                                    .right(px(0.))
-                                    .bottom_0()
-                                    .absolute()
-                                    .bg(cx.theme().table_active)
-                                    .border_1()
-                                    .border_color(cx.theme().table_active_border),
-                            )
-                        },
-                    )
-                })
+                .when(
+                    is_selected && self.selection_state == SelectionState::Row,
+                    |this| {
+                        this.border_color(gpui::transparent_white()).child(
+                            div()
+                                .top(if row_ix == 0 { px(0.) } else { px(-1.) })
+                                .left(px(0.))
+                                .right(px(0.))
+                                .bottom_0()
+                                .absolute()
+                                .bg(cx.theme().table_active)
+                                .border_1()
+                                .border_color(cx.theme().table_active_border),
+                        )
+                    },
+                )
                 // Row right click row style
                 .when(self.right_clicked_row == Some(row_ix), |this| {
                     this.border_color(gpui::transparent_white()).child(
@@ -1365,7 +2430,7 @@ where
         } else {
             // Render fake rows to fill the rest table space
             self.delegate
-                .render_tr(row_ix, window, cx)
+                .render_tr(row_ix, false, window, cx)
                 .w_full()
                 .h_full()
                 .border_t_1()
@@ -1494,8 +2559,17 @@ where
             .on_action(cx.listener(Self::action_cancel))
             .on_action(cx.listener(Self::action_select_next))
             .on_action(cx.listener(Self::action_select_prev))
+            .on_action(cx.listener(Self::action_select_to_next))
+            .on_action(cx.listener(Self::action_select_to_prev))
+            .on_action(cx.listener(Self::action_select_all))
             .on_action(cx.listener(Self::action_select_next_col))
             .on_action(cx.listener(Self::action_select_prev_col))
+            .on_action(cx.listener(Self::action_select_to_next_col))
+            .on_action(cx.listener(Self::action_select_to_prev_col))
+            .on_action(cx.listener(Self::action_copy))
+            .on_action(cx.listener(Self::action_start_cell_edit))
+            .on_action(cx.listener(Self::action_edit_next_cell))
+            .on_action(cx.listener(Self::action_edit_prev_cell))
             .size_full()
             .overflow_hidden()
             .child(self.render_table_head(left_cols_count, window, cx))
@@ -1607,6 +2681,16 @@ where
                         Axis::Horizontal,
                         &horizontal_scroll_handle,
                     ))
+                    .when(self.selecting_cells, |this| {
+                        this.on_mouse_up(
+                            MouseButton::Left,
+                            cx.listener(|this, _, _, _| this.selecting_cells = false),
+                        )
+                        .on_mouse_up_out(
+                            MouseButton::Left,
+                            cx.listener(|this, _, _, _| this.selecting_cells = false),
+                        )
+                    })
                     .when(self.right_clicked_row.is_some(), |this| {
                         this.on_mouse_down_out(cx.listener(|this, _, _, cx| {
                             this.right_clicked_row = None;
@@ -1630,5 +2714,25 @@ where
                         this.child(self.render_horizontal_scrollbar(window, cx))
                     }),
             )
+            .when_some(
+                self.cell_editor
+                    .as_ref()
+                    .and_then(|editor| editor.error.clone()),
+                |this, error| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .left_2()
+                            .bottom_2()
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_xs()
+                            .bg(cx.theme().danger)
+                            .text_color(cx.theme().danger_foreground)
+                            .child(error),
+                    )
+                },
+            )
     }
 }
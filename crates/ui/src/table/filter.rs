@@ -0,0 +1,268 @@
+use gpui::{
+    div, prelude::FluentBuilder as _, px, App, AppContext as _, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, ParentElement,
+    Render, SharedString, StatefulInteractiveElement as _, Styled, Subscription, WeakEntity,
+    Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{InputEvent, TextInput},
+    popover::Escape,
+    v_flex, Sizable as _,
+};
+
+use super::{Table, TableDelegate};
+
+/// The filter of a column, see [`TableDelegate::perform_filter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColFilter {
+    /// Match the rows that the cell text contains the text, case insensitive.
+    Text(SharedString),
+    /// Match the rows that the cell text is one of the checked values.
+    Values(Vec<SharedString>),
+}
+
+impl ColFilter {
+    /// Returns true if the cell `text` matches the filter.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Text(query) => text.to_lowercase().contains(&query.to_lowercase()),
+            Self::Values(values) => values.iter().any(|value| value.as_ref() == text),
+        }
+    }
+}
+
+/// The popover content to edit the filter of a column.
+///
+/// A text filter is used, unless the delegate supplies the values by [`TableDelegate::col_filter_values`].
+pub(crate) struct ColFilterPopover<D: TableDelegate> {
+    focus_handle: FocusHandle,
+    table: WeakEntity<Table<D>>,
+    col_ix: usize,
+    /// The input of the text filter.
+    input: Option<Entity<TextInput>>,
+    /// The values of the checklist filter, and whether they are checked.
+    values: Vec<(SharedString, bool)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<D> ColFilterPopover<D>
+where
+    D: TableDelegate,
+{
+    pub(crate) fn new(
+        table: WeakEntity<Table<D>>,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (filter, values) = table
+            .upgrade()
+            .map(|table| {
+                let table = table.read(cx);
+                (
+                    table.col_filter(col_ix).cloned(),
+                    table.delegate().col_filter_values(col_ix, cx),
+                )
+            })
+            .unwrap_or_default();
+
+        let mut input = None;
+        let mut _subscriptions = vec![];
+        let values = match values {
+            Some(values) => values
+                .into_iter()
+                .map(|value| {
+                    let checked = match &filter {
+                        Some(filter) => filter.matches(&value),
+                        None => true,
+                    };
+                    (value, checked)
+                })
+                .collect(),
+            None => {
+                let text_input = cx.new(|cx| {
+                    let mut text_input = TextInput::new(window, cx)
+                        .small()
+                        .placeholder(t!("Table.filter_placeholder").to_string());
+                    if let Some(ColFilter::Text(text)) = &filter {
+                        text_input.set_text(text.clone(), window, cx);
+                    }
+                    text_input
+                });
+                _subscriptions.push(cx.subscribe_in(
+                    &text_input,
+                    window,
+                    |this, _, event: &InputEvent, window, cx| {
+                        if let InputEvent::PressEnter = event {
+                            this.apply(window, cx);
+                        }
+                    },
+                ));
+                input = Some(text_input);
+                vec![]
+            }
+        };
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            table,
+            col_ix,
+            input,
+            values,
+            _subscriptions,
+        }
+    }
+
+    fn set_filter(
+        &mut self,
+        filter: Option<ColFilter>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let col_ix = self.col_ix;
+        _ = self.table.update(cx, |table, cx| {
+            table.set_col_filter(col_ix, filter, window, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn apply(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let filter = match &self.input {
+            Some(input) => {
+                let text = input.read(cx).text();
+                let text = text.trim();
+                (!text.is_empty()).then(|| ColFilter::Text(text.to_string().into()))
+            }
+            // All values are checked, that is no filter.
+            None if self.values.iter().all(|(_, checked)| *checked) => None,
+            None => Some(ColFilter::Values(
+                self.values
+                    .iter()
+                    .filter(|(_, checked)| *checked)
+                    .map(|(value, _)| value.clone())
+                    .collect(),
+            )),
+        };
+
+        self.set_filter(filter, window, cx);
+    }
+}
+
+impl<D> EventEmitter<DismissEvent> for ColFilterPopover<D> where D: TableDelegate {}
+
+impl<D> Focusable for ColFilterPopover<D>
+where
+    D: TableDelegate,
+{
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.input {
+            Some(input) => input.focus_handle(cx),
+            None => self.focus_handle.clone(),
+        }
+    }
+}
+
+impl<D> Render for ColFilterPopover<D>
+where
+    D: TableDelegate,
+{
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let all_checked = self.values.iter().all(|(_, checked)| *checked);
+
+        v_flex()
+            .key_context("Popover")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|_, _: &Escape, _, cx| cx.emit(DismissEvent)))
+            .w(px(220.))
+            .p_2()
+            .gap_2()
+            .map(|this| match &self.input {
+                Some(input) => this.child(input.clone()),
+                None => this
+                    .child(
+                        Checkbox::new("select-all")
+                            .label(t!("Table.select_all").to_string())
+                            .checked(all_checked)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.values
+                                    .iter_mut()
+                                    .for_each(|(_, value_checked)| *value_checked = *checked);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("values")
+                            .max_h(px(240.))
+                            .overflow_y_scroll()
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .children(self.values.iter().enumerate().map(
+                                        |(ix, (value, checked))| {
+                                            Checkbox::new(("value", ix))
+                                                .label(value.clone())
+                                                .checked(*checked)
+                                                .on_click(cx.listener(
+                                                    move |this, checked: &bool, _, cx| {
+                                                        if let Some((_, value_checked)) =
+                                                            this.values.get_mut(ix)
+                                                        {
+                                                            *value_checked = *checked;
+                                                        }
+                                                        cx.notify();
+                                                    },
+                                                ))
+                                        },
+                                    )),
+                            ),
+                    ),
+            })
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        Button::new("clear")
+                            .small()
+                            .ghost()
+                            .label(t!("Table.clear").to_string())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_filter(None, window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("apply")
+                            .small()
+                            .primary()
+                            .label(t!("Table.apply").to_string())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.apply(window, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColFilter;
+
+    #[test]
+    fn test_col_filter_matches() {
+        let filter = ColFilter::Text("app".into());
+        assert!(filter.matches("Apple Inc."));
+        assert!(filter.matches("SNAPP"));
+        assert!(!filter.matches("Microsoft"));
+
+        let filter = ColFilter::Values(vec!["NASDAQ".into(), "NYSE".into()]);
+        assert!(filter.matches("NYSE"));
+        assert!(!filter.matches("nyse"));
+        assert!(!filter.matches("HKEX"));
+    }
+}
@@ -127,6 +127,7 @@ pub struct ThemeColor {
     pub table_row_border: Hsla,
     pub title_bar: Hsla,
     pub title_bar_border: Hsla,
+    pub warning: Hsla,
     pub window_border: Hsla,
 }
 
@@ -204,6 +205,7 @@ impl ThemeColor {
             table_row_border: hsl(240.0, 7.7, 94.5),
             title_bar: hsl(0.0, 0.0, 100.),
             title_bar_border: hsl(240.0, 5.9, 90.0),
+            warning: hsl(37.7, 92.1, 50.2),
             window_border: hsl(240.0, 5.9, 78.0),
         }
     }
@@ -281,6 +283,7 @@ impl ThemeColor {
             table_row_border: hsl(240.0, 3.7, 16.9).opacity(0.5),
             title_bar: hsl(0., 0., 9.7),
             title_bar_border: hsl(240.0, 3.7, 15.9),
+            warning: hsl(32.1, 94.6, 43.7),
             window_border: hsl(240.0, 3.7, 28.0),
         }
     }
@@ -350,6 +353,7 @@ impl Theme {
         // self.danger_hover = self.danger_hover.apply(mask_color);
         // self.danger_active = self.danger_active.apply(mask_color);
         // self.danger_foreground = self.danger_foreground.apply(mask_color);
+        // self.warning = self.warning.apply(mask_color);
         self.muted = self.muted.apply(mask_color);
         self.muted_foreground = self.muted_foreground.apply(mask_color);
         self.accent = self.accent.apply(mask_color);
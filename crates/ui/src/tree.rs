@@ -0,0 +1,1001 @@
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    hash::Hash,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, px, uniform_list, App, AppContext, ClickEvent,
+    Context, EntityId, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement,
+    KeyBinding, KeyDownEvent, MouseButton, MouseDownEvent, ParentElement, Pixels, Render,
+    ScrollStrategy, SharedString, StatefulInteractiveElement as _, Styled, Task,
+    UniformListScrollHandle, Window,
+};
+
+use crate::{
+    h_flex,
+    indicator::Indicator,
+    list::ListItem,
+    scroll::{Scrollbar, ScrollbarState},
+    v_flex, ActiveTheme, Icon, IconName, Sizable as _,
+};
+
+actions!(
+    tree,
+    [
+        Cancel,
+        Confirm,
+        SelectPrev,
+        SelectNext,
+        SelectToPrev,
+        SelectToNext,
+        SelectAll,
+        Expand,
+        Collapse
+    ]
+);
+
+/// The typed chars of the type-ahead are reset after this duration.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub fn init(cx: &mut App) {
+    let context: Option<&str> = Some("Tree");
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, context),
+        KeyBinding::new("enter", Confirm, context),
+        KeyBinding::new("up", SelectPrev, context),
+        KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("shift-up", SelectToPrev, context),
+        KeyBinding::new("shift-down", SelectToNext, context),
+        KeyBinding::new("right", Expand, context),
+        KeyBinding::new("left", Collapse, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", SelectAll, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", SelectAll, context),
+    ]);
+}
+
+#[derive(Clone)]
+pub enum TreeEvent<N> {
+    /// Move to select the node.
+    Select(N),
+    /// The selected nodes are changed in the multiple selection mode.
+    SelectMultiple(Vec<N>),
+    /// Click on the node or pressed Enter.
+    Confirm(N),
+    /// The node is expanded.
+    Expand(N),
+    /// The node is collapsed.
+    Collapse(N),
+    /// Pressed ESC to deselect the nodes.
+    Cancel,
+}
+
+/// A visible row of the tree.
+#[derive(Debug, Clone)]
+pub struct TreeEntry<N> {
+    pub node: N,
+    /// The depth of the node, the root nodes are 0.
+    pub depth: usize,
+    /// The node may have children, see [`TreeDelegate::has_children`].
+    pub has_children: bool,
+    pub expanded: bool,
+    /// The children of the node are loading.
+    pub loading: bool,
+    /// The index of the parent entry.
+    parent_ix: Option<usize>,
+}
+
+/// The payload of dragging the nodes of a tree, the dragged nodes are kept in the [`Tree`].
+#[derive(Clone)]
+pub(crate) struct DragTreeNodes {
+    entity_id: EntityId,
+    label: SharedString,
+    count: usize,
+}
+
+impl Render for DragTreeNodes {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .id("drag-tree-nodes")
+            .cursor_grab()
+            .gap_1()
+            .py_1()
+            .px_3()
+            .max_w_64()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .bg(cx.theme().background)
+            .opacity(0.9)
+            .shadow_md()
+            .child(self.label.clone())
+            .when(self.count > 1, |this| {
+                this.child(
+                    div()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("+{}", self.count - 1)),
+                )
+            })
+    }
+}
+
+/// A delegate for the Tree.
+///
+/// The nodes are identified by the `Node` type, e.g.: the path of a file,
+/// the tree keeps the expanded and selected nodes by it.
+#[allow(unused)]
+pub trait TreeDelegate: Sized + 'static {
+    type Node: Clone + Eq + Hash + 'static;
+    type Item: IntoElement;
+
+    /// Returns the children of the `parent` node, or the root nodes if the `parent` is None.
+    ///
+    /// Returns None if the children are not loaded yet, then the `load_children` is called when the node is expanded.
+    fn children(&self, parent: Option<&Self::Node>, cx: &App) -> Option<Vec<Self::Node>>;
+
+    /// Returns true if the node may have children, a toggle is displayed before the node.
+    fn has_children(&self, node: &Self::Node, cx: &App) -> bool;
+
+    /// Load the children of the node, this is called when a node without loaded children is expanded.
+    ///
+    /// A loading indicator is displayed until the returned task is finished, then the tree is refreshed.
+    fn load_children(
+        &mut self,
+        node: &Self::Node,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    /// Returns the label of the node, it is used by the type-ahead to find the nodes.
+    fn label(&self, node: &Self::Node, cx: &App) -> SharedString;
+
+    /// Render the content of the entry, the indentation and the toggle are rendered by the tree.
+    fn render_item(
+        &self,
+        entry: &TreeEntry<Self::Node>,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Option<Self::Item>;
+
+    /// Return a Element to show when tree is empty.
+    fn render_empty(&self, window: &mut Window, cx: &mut Context<Tree<Self>>) -> impl IntoElement {
+        div()
+    }
+
+    /// Set the selected node, just store the node, don't confirm.
+    fn set_selected_node(
+        &mut self,
+        node: Option<&Self::Node>,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+
+    /// Set the selected nodes in the multiple selection mode, in the order of the tree.
+    ///
+    /// See [`Tree::multiple`].
+    fn set_selected_nodes(
+        &mut self,
+        nodes: &[Self::Node],
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+
+    /// Set the confirm and give the selected node, this is means user have clicked the node or pressed Enter.
+    fn confirm(&mut self, node: &Self::Node, window: &mut Window, cx: &mut Context<Tree<Self>>) {}
+
+    /// Returns true if the node can be dragged, default is false.
+    ///
+    /// When a selected node is dragged in the multiple selection mode, all the selected nodes are dragged.
+    fn can_drag(&self, node: &Self::Node, cx: &App) -> bool {
+        false
+    }
+
+    /// Returns true if the dragged `nodes` can be moved into the `parent` node, default is true.
+    ///
+    /// Only the nodes have children are the drop targets,
+    /// and the nodes can never be dropped into themselves or their descendants.
+    fn can_drop(&self, nodes: &[Self::Node], parent: &Self::Node, cx: &App) -> bool {
+        true
+    }
+
+    /// Move the dragged `nodes` into the `parent` node, the `parent` is expanded after moving.
+    fn move_nodes(
+        &mut self,
+        nodes: Vec<Self::Node>,
+        parent: &Self::Node,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+}
+
+/// A virtualized tree, the nodes are provided by the [`TreeDelegate`].
+///
+/// - `left` and `right` to collapse and expand the nodes, or move to the parent and the first child.
+/// - Type the label of a node to select it.
+pub struct Tree<D: TreeDelegate> {
+    focus_handle: FocusHandle,
+    delegate: D,
+    entries: Vec<TreeEntry<D::Node>>,
+    expanded: HashSet<D::Node>,
+    loading: HashSet<D::Node>,
+    indent: Pixels,
+    multiple: bool,
+    selected_node: Option<D::Node>,
+    /// The selected nodes in the multiple selection mode.
+    selected_nodes: HashSet<D::Node>,
+    /// The start of the range selection in the multiple selection mode.
+    anchor_node: Option<D::Node>,
+    /// The nodes are dragging.
+    dragging: Vec<D::Node>,
+    type_ahead: String,
+    last_typed_at: Option<Instant>,
+    scrollbar_visible: bool,
+    vertical_scroll_handle: UniformListScrollHandle,
+    scrollbar_state: Rc<Cell<ScrollbarState>>,
+}
+
+impl<D> Tree<D>
+where
+    D: TreeDelegate,
+{
+    pub fn new(delegate: D, _: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            focus_handle: cx.focus_handle(),
+            delegate,
+            entries: vec![],
+            expanded: HashSet::new(),
+            loading: HashSet::new(),
+            indent: px(16.),
+            multiple: false,
+            selected_node: None,
+            selected_nodes: HashSet::new(),
+            anchor_node: None,
+            dragging: vec![],
+            type_ahead: String::new(),
+            last_typed_at: None,
+            scrollbar_visible: true,
+            vertical_scroll_handle: UniformListScrollHandle::new(),
+            scrollbar_state: Rc::new(Cell::new(ScrollbarState::new())),
+        };
+        this.refresh(cx);
+        this
+    }
+
+    /// Set the indentation width of each level, default is 16px.
+    pub fn indent(mut self, indent: impl Into<Pixels>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// Set the visibility of the scrollbar, default is true.
+    pub fn scrollbar_visible(mut self, visible: bool) -> Self {
+        self.scrollbar_visible = visible;
+        self
+    }
+
+    /// Enable the multiple selection mode.
+    ///
+    /// - Ctrl (Cmd on macOS) click to toggle a node.
+    /// - Shift click or `shift-up`, `shift-down` to select a range.
+    /// - `ctrl-a` (`cmd-a` on macOS) to select all visible nodes.
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Set the multiple selection mode, the selection is cleared when disabled, see [`Tree::multiple`].
+    pub fn set_multiple(&mut self, multiple: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.multiple = multiple;
+        if !multiple {
+            self.anchor_node = None;
+            self.set_selected_nodes(vec![], window, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn delegate(&self) -> &D {
+        &self.delegate
+    }
+
+    pub fn delegate_mut(&mut self) -> &mut D {
+        &mut self.delegate
+    }
+
+    /// Returns the visible rows of the tree.
+    pub fn entries(&self) -> &[TreeEntry<D::Node>] {
+        &self.entries
+    }
+
+    /// Get scroll handle
+    pub fn scroll_handle(&self) -> &UniformListScrollHandle {
+        &self.vertical_scroll_handle
+    }
+
+    /// Rebuild the visible rows from the delegate, call this after the nodes are changed.
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let mut entries = vec![];
+        self.push_entries(None, None, 0, &mut entries, cx);
+        self.entries = entries;
+        cx.notify();
+    }
+
+    fn push_entries(
+        &self,
+        parent: Option<&D::Node>,
+        parent_ix: Option<usize>,
+        depth: usize,
+        entries: &mut Vec<TreeEntry<D::Node>>,
+        cx: &App,
+    ) {
+        let Some(children) = self.delegate.children(parent, cx) else {
+            return;
+        };
+
+        for node in children {
+            let has_children = self.delegate.has_children(&node, cx);
+            let expanded = has_children && self.expanded.contains(&node);
+            let ix = entries.len();
+            entries.push(TreeEntry {
+                node: node.clone(),
+                depth,
+                has_children,
+                expanded,
+                loading: self.loading.contains(&node),
+                parent_ix,
+            });
+
+            if expanded {
+                self.push_entries(Some(&node), Some(ix), depth + 1, entries, cx);
+            }
+        }
+    }
+
+    /// Returns the index of the visible row of the node.
+    fn ix_of(&self, node: &D::Node) -> Option<usize> {
+        self.entries.iter().position(|entry| &entry.node == node)
+    }
+
+    pub fn is_expanded(&self, node: &D::Node) -> bool {
+        self.expanded.contains(node)
+    }
+
+    /// Expand the node, the children are loaded by [`TreeDelegate::load_children`] if not loaded yet.
+    pub fn expand(&mut self, node: &D::Node, window: &mut Window, cx: &mut Context<Self>) {
+        if self.expanded.contains(node) || !self.delegate.has_children(node, cx) {
+            return;
+        }
+
+        self.expanded.insert(node.clone());
+        if self.delegate.children(Some(node), cx).is_none() && !self.loading.contains(node) {
+            self.loading.insert(node.clone());
+            let load = self.delegate.load_children(node, window, cx);
+            let node = node.clone();
+            cx.spawn_in(window, |this, mut cx| async move {
+                load.await;
+
+                _ = this.update_in(&mut cx, |this, _, cx| {
+                    this.loading.remove(&node);
+                    this.refresh(cx);
+                });
+            })
+            .detach();
+        }
+
+        self.refresh(cx);
+        cx.emit(TreeEvent::Expand(node.clone()));
+    }
+
+    /// Collapse the node, the selected node is moved to it if the selected node is hidden.
+    pub fn collapse(&mut self, node: &D::Node, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.expanded.remove(node) {
+            return;
+        }
+
+        let selected_visible = self.selected_ix().is_some();
+        self.refresh(cx);
+        if selected_visible && self.selected_ix().is_none() {
+            if let Some(ix) = self.ix_of(node) {
+                self.select_entry(ix, window, cx);
+            }
+        }
+        cx.emit(TreeEvent::Collapse(node.clone()));
+    }
+
+    fn toggle_expanded(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+
+        let node = entry.node.clone();
+        if entry.expanded {
+            self.collapse(&node, window, cx);
+        } else {
+            self.expand(&node, window, cx);
+        }
+    }
+
+    pub fn focus(&mut self, window: &mut Window, _: &mut App) {
+        window.focus(&self.focus_handle);
+    }
+
+    pub fn selected_node(&self) -> Option<&D::Node> {
+        self.selected_node.as_ref()
+    }
+
+    /// Set the selected node of the tree, this will also scroll to the node if it is visible.
+    pub fn set_selected_node(
+        &mut self,
+        node: Option<D::Node>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_node = node;
+        self.delegate
+            .set_selected_node(self.selected_node.as_ref(), window, cx);
+        self.scroll_to_selected_node();
+        cx.notify();
+    }
+
+    /// Returns the selected nodes in the multiple selection mode, in the order of the tree.
+    pub fn selected_nodes(&self) -> Vec<D::Node> {
+        self.entries
+            .iter()
+            .filter(|entry| self.selected_nodes.contains(&entry.node))
+            .map(|entry| entry.node.clone())
+            .collect()
+    }
+
+    /// Set the selected nodes in the multiple selection mode, the nodes not visible are ignored.
+    pub fn set_selected_nodes(
+        &mut self,
+        nodes: impl IntoIterator<Item = D::Node>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let nodes = nodes.into_iter().collect::<HashSet<_>>();
+        let nodes = self
+            .entries
+            .iter()
+            .filter(|entry| nodes.contains(&entry.node))
+            .map(|entry| entry.node.clone())
+            .collect::<HashSet<_>>();
+        if nodes == self.selected_nodes {
+            return;
+        }
+
+        self.selected_nodes = nodes;
+        let nodes = self.selected_nodes();
+        self.delegate.set_selected_nodes(&nodes, window, cx);
+        cx.emit(TreeEvent::SelectMultiple(nodes));
+        cx.notify();
+    }
+
+    /// Select all the visible nodes in the multiple selection mode.
+    pub fn select_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.multiple {
+            return;
+        }
+
+        let nodes = self
+            .entries
+            .iter()
+            .map(|entry| entry.node.clone())
+            .collect::<Vec<_>>();
+        self.set_selected_nodes(nodes, window, cx);
+    }
+
+    /// Returns the index of the visible row of the selected node.
+    fn selected_ix(&self) -> Option<usize> {
+        self.selected_node
+            .as_ref()
+            .and_then(|node| self.ix_of(node))
+    }
+
+    fn is_selected(&self, ix: usize) -> bool {
+        let node = &self.entries[ix].node;
+        self.selected_node.as_ref() == Some(node)
+            || (self.multiple && self.selected_nodes.contains(node))
+    }
+
+    fn scroll_to_selected_node(&mut self) {
+        if let Some(ix) = self.selected_ix() {
+            self.vertical_scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Top);
+        }
+    }
+
+    fn select_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let node = self.entries[ix].node.clone();
+        self.set_selected_node(Some(node.clone()), window, cx);
+        if self.multiple {
+            self.anchor_node = Some(node.clone());
+            self.set_selected_nodes(vec![node.clone()], window, cx);
+        }
+        cx.emit(TreeEvent::Select(node));
+    }
+
+    /// Move the selected node to `ix` and select the range from the anchor to it.
+    fn select_range_to(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let anchor_ix = self
+            .anchor_node
+            .as_ref()
+            .and_then(|node| self.ix_of(node))
+            .or(self.selected_ix())
+            .unwrap_or(ix);
+        self.anchor_node = Some(self.entries[anchor_ix].node.clone());
+
+        let node = self.entries[ix].node.clone();
+        self.set_selected_node(Some(node.clone()), window, cx);
+        let nodes = self.entries[anchor_ix.min(ix)..=anchor_ix.max(ix)]
+            .iter()
+            .map(|entry| entry.node.clone())
+            .collect::<Vec<_>>();
+        self.set_selected_nodes(nodes, window, cx);
+        cx.emit(TreeEvent::Select(node));
+    }
+
+    /// Toggle the node at `ix` in the selected nodes.
+    fn toggle_selected_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let node = self.entries[ix].node.clone();
+        let mut nodes = self.selected_nodes.clone();
+        if !nodes.remove(&node) {
+            nodes.insert(node.clone());
+        }
+
+        self.anchor_node = Some(node.clone());
+        self.set_selected_node(Some(node.clone()), window, cx);
+        self.set_selected_nodes(nodes, window, cx);
+        cx.emit(TreeEvent::Select(node));
+    }
+
+    fn confirm_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let node = self.entries[ix].node.clone();
+        self.delegate.confirm(&node, window, cx);
+        cx.emit(TreeEvent::Confirm(node));
+        cx.notify();
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_selected_node(None, window, cx);
+        self.anchor_node = None;
+        self.set_selected_nodes(vec![], window, cx);
+        cx.emit(TreeEvent::Cancel);
+    }
+
+    fn on_action_confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_ix() {
+            self.confirm_entry(ix, window, cx);
+        }
+    }
+
+    /// Returns the index of the previous or next row, stop at the first or the last row.
+    fn next_ix(&self, forward: bool) -> Option<usize> {
+        let last_ix = self.entries.len().checked_sub(1)?;
+        Some(match self.selected_ix() {
+            None if forward => 0,
+            None => last_ix,
+            Some(ix) if forward => (ix + 1).min(last_ix),
+            Some(ix) => ix.saturating_sub(1),
+        })
+    }
+
+    fn on_action_select_prev(
+        &mut self,
+        _: &SelectPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.next_ix(false) {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn on_action_select_next(
+        &mut self,
+        _: &SelectNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.next_ix(true) {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn on_action_select_to_prev(
+        &mut self,
+        _: &SelectToPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.next_ix(false) {
+            if self.multiple {
+                self.select_range_to(ix, window, cx);
+            } else {
+                self.select_entry(ix, window, cx);
+            }
+        }
+    }
+
+    fn on_action_select_to_next(
+        &mut self,
+        _: &SelectToNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.next_ix(true) {
+            if self.multiple {
+                self.select_range_to(ix, window, cx);
+            } else {
+                self.select_entry(ix, window, cx);
+            }
+        }
+    }
+
+    fn on_action_select_all(&mut self, _: &SelectAll, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.multiple {
+            cx.propagate();
+            return;
+        }
+
+        self.select_all(window, cx);
+    }
+
+    /// Expand the selected node, or move to the first child if it is expanded.
+    fn on_action_expand(&mut self, _: &Expand, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.selected_ix() else {
+            return;
+        };
+
+        let entry = &self.entries[ix];
+        if !entry.has_children {
+            return;
+        }
+        if !entry.expanded {
+            let node = entry.node.clone();
+            self.expand(&node, window, cx);
+        } else if self
+            .entries
+            .get(ix + 1)
+            .is_some_and(|child| child.parent_ix == Some(ix))
+        {
+            self.select_entry(ix + 1, window, cx);
+        }
+    }
+
+    /// Collapse the selected node, or move to the parent if it is collapsed.
+    fn on_action_collapse(&mut self, _: &Collapse, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.selected_ix() else {
+            return;
+        };
+
+        let entry = &self.entries[ix];
+        if entry.expanded {
+            let node = entry.node.clone();
+            self.collapse(&node, window, cx);
+        } else if let Some(parent_ix) = entry.parent_ix {
+            self.select_entry(parent_ix, window, cx);
+        }
+    }
+
+    /// Type-ahead: select the next node that the label starts with the typed chars.
+    ///
+    /// The typed chars are reset after 1 second, typing the same char repeatedly cycles
+    /// through the nodes start with it.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = &keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform || modifiers.function {
+            return;
+        }
+        let mut chars = keystroke.key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return;
+        };
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        if self
+            .last_typed_at
+            .map_or(true, |at| now.duration_since(at) > TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead.clear();
+        }
+        self.last_typed_at = Some(now);
+
+        let c = c.to_lowercase().to_string();
+        let cycle = self.type_ahead == c;
+        if !cycle {
+            self.type_ahead.push_str(&c);
+        }
+
+        // Search after the selected node when cycling, otherwise the selected node is also a candidate.
+        let start = match self.selected_ix() {
+            Some(ix) if cycle || self.type_ahead.chars().count() == 1 => ix + 1,
+            Some(ix) => ix,
+            None => 0,
+        };
+        let count = self.entries.len();
+        if let Some(ix) = (0..count).map(|i| (start + i) % count).find(|ix| {
+            self.delegate
+                .label(&self.entries[*ix].node, cx)
+                .to_lowercase()
+                .starts_with(&self.type_ahead)
+        }) {
+            self.select_entry(ix, window, cx);
+        }
+
+        cx.stop_propagation();
+    }
+
+    /// Returns true if the dragging nodes can be dropped into the node at `ix`.
+    fn can_drop_into(&self, ix: usize, cx: &App) -> bool {
+        if self.dragging.is_empty() || !cx.has_active_drag() {
+            return false;
+        }
+
+        // Never drop into the dragging nodes or their descendants.
+        let mut ancestor_ix = Some(ix);
+        while let Some(ix) = ancestor_ix {
+            let entry = &self.entries[ix];
+            if self.dragging.contains(&entry.node) {
+                return false;
+            }
+            ancestor_ix = entry.parent_ix;
+        }
+
+        let entry = &self.entries[ix];
+        entry.has_children && self.delegate.can_drop(&self.dragging, &entry.node, cx)
+    }
+
+    fn start_drag(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let node = self.entries[ix].node.clone();
+        self.dragging = if self.multiple && self.selected_nodes.contains(&node) {
+            self.selected_nodes()
+        } else {
+            vec![node]
+        };
+        cx.notify();
+    }
+
+    fn drop_into(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.can_drop_into(ix, cx) {
+            return;
+        }
+
+        let nodes = std::mem::take(&mut self.dragging);
+        let parent = self.entries[ix].node.clone();
+        self.delegate.move_nodes(nodes, &parent, window, cx);
+        if self.expanded.contains(&parent) {
+            self.refresh(cx);
+        } else {
+            self.expand(&parent, window, cx);
+        }
+    }
+
+    fn render_entry(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let view = cx.model().clone();
+        let entity_id = cx.entity_id();
+        let indent = self.indent;
+        let entry = self.entries[ix].clone();
+        let selected = self.is_selected(ix);
+        let draggable = self.delegate.can_drag(&entry.node, cx);
+        let droppable = self.can_drop_into(ix, cx);
+        // The left padding of the ListItem.
+        let padding = px(8.);
+
+        let toggle = div()
+            .id(("tree-toggle", ix))
+            .flex_none()
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(indent)
+            .when(entry.loading, |this| {
+                this.child(Indicator::new().xsmall().color(cx.theme().muted_foreground))
+            })
+            .when(entry.has_children && !entry.loading, |this| {
+                this.child(
+                    Icon::new(if entry.expanded {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronRight
+                    })
+                    .xsmall()
+                    .text_color(cx.theme().muted_foreground),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, window, cx| {
+                        cx.stop_propagation();
+                        this.toggle_expanded(ix, window, cx);
+                    }),
+                )
+            });
+
+        div()
+            .id(("tree-entry", ix))
+            .w_full()
+            .child(
+                ListItem::new(("tree-item", ix))
+                    .selected(selected)
+                    .pl(padding + indent * entry.depth as f32)
+                    // Indentation guides, in the center of the toggle of each ancestor.
+                    .children((0..entry.depth).map(|level| {
+                        div()
+                            .absolute()
+                            .top_0()
+                            .bottom_0()
+                            .left(padding + indent * (level as f32 + 0.5))
+                            .w(px(1.))
+                            .bg(cx.theme().border)
+                    }))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .overflow_hidden()
+                            .child(toggle)
+                            .children(self.delegate.render_item(&entry, window, cx)),
+                    ),
+            )
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
+                    window.focus(&this.focus_handle);
+                    if this.multiple {
+                        if ev.modifiers.shift {
+                            this.select_range_to(ix, window, cx);
+                            return;
+                        }
+                        if ev.modifiers.secondary() {
+                            this.toggle_selected_entry(ix, window, cx);
+                            return;
+                        }
+                    }
+
+                    this.select_entry(ix, window, cx);
+                }),
+            )
+            .on_click(cx.listener(move |this, ev: &ClickEvent, window, cx| {
+                let modifiers = ev.down.modifiers;
+                if this.multiple && (modifiers.shift || modifiers.secondary()) {
+                    return;
+                }
+
+                if this.entries.get(ix).is_some_and(|entry| entry.has_children) {
+                    this.toggle_expanded(ix, window, cx);
+                }
+                this.confirm_entry(ix, window, cx);
+            }))
+            .when(draggable, |this| {
+                let label = self.delegate.label(&entry.node, cx);
+                let count = if self.multiple && self.selected_nodes.contains(&entry.node) {
+                    self.selected_nodes.len()
+                } else {
+                    1
+                };
+
+                this.on_drag(
+                    DragTreeNodes {
+                        entity_id,
+                        label,
+                        count,
+                    },
+                    move |drag, _, _, cx| {
+                        cx.stop_propagation();
+                        view.update(cx, |tree, cx| tree.start_drag(ix, cx));
+                        cx.new(|_| drag.clone())
+                    },
+                )
+            })
+            .when(droppable, |this| {
+                this.drag_over::<DragTreeNodes>(|this, _, _, cx| {
+                    this.bg(cx.theme().drop_target)
+                        .border_1()
+                        .border_color(cx.theme().drag_border)
+                })
+                .on_drop(cx.listener(
+                    move |this, drag: &DragTreeNodes, window, cx| {
+                        if drag.entity_id != cx.entity_id() {
+                            return;
+                        }
+
+                        cx.stop_propagation();
+                        this.drop_into(ix, window, cx);
+                    },
+                ))
+            })
+    }
+
+    fn render_scrollbar(&self, _: &mut Window, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        if !self.scrollbar_visible {
+            return None;
+        }
+
+        Some(Scrollbar::uniform_scroll(
+            cx.model().entity_id(),
+            self.scrollbar_state.clone(),
+            self.vertical_scroll_handle.clone(),
+        ))
+    }
+}
+
+impl<D> Focusable for Tree<D>
+where
+    D: TreeDelegate,
+{
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+impl<D> EventEmitter<TreeEvent<D::Node>> for Tree<D> where D: TreeDelegate {}
+impl<D> Render for Tree<D>
+where
+    D: TreeDelegate,
+{
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.model().clone();
+        let entries_count = self.entries.len();
+
+        // The drag is ended without dropping into a node, e.g. dropped outside of the tree.
+        if !self.dragging.is_empty() && !cx.has_active_drag() {
+            self.dragging.clear();
+        }
+
+        v_flex()
+            .key_context("Tree")
+            .id("tree")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .relative()
+            .overflow_hidden()
+            .on_action(cx.listener(Self::on_action_cancel))
+            .on_action(cx.listener(Self::on_action_confirm))
+            .on_action(cx.listener(Self::on_action_select_next))
+            .on_action(cx.listener(Self::on_action_select_prev))
+            .on_action(cx.listener(Self::on_action_select_to_next))
+            .on_action(cx.listener(Self::on_action_select_to_prev))
+            .on_action(cx.listener(Self::on_action_select_all))
+            .on_action(cx.listener(Self::on_action_expand))
+            .on_action(cx.listener(Self::on_action_collapse))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_drop(cx.listener(|this, _: &DragTreeNodes, _, cx| {
+                this.dragging.clear();
+                cx.notify();
+            }))
+            .when(entries_count == 0, |this| {
+                this.child(self.delegate.render_empty(window, cx))
+            })
+            .when(entries_count > 0, |this| {
+                this.child(
+                    uniform_list(view, "uniform-list", entries_count, {
+                        move |tree, visible_range, window, cx| {
+                            visible_range
+                                .map(|ix| tree.render_entry(ix, window, cx))
+                                .collect::<Vec<_>>()
+                        }
+                    })
+                    .flex_grow()
+                    .track_scroll(self.vertical_scroll_handle.clone()),
+                )
+            })
+            .children(self.render_scrollbar(window, cx))
+    }
+}